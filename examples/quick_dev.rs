@@ -24,7 +24,11 @@ async fn main() -> Result<()> {
             "method": "create_bounty",
             "params": {
                 "data": {
-                    "title" : "AUX-01"
+                    "bounty_title": "AUX-01",
+                    "bounty_assignee": "",
+                    "bounty_reward": "100",
+                    "bounty_deliverables": "",
+                    "bounty_description": ""
                 }
             }
         }),
@@ -38,7 +42,7 @@ async fn main() -> Result<()> {
             "params": {
                 "id": 1000,
                 "data": {
-                    "title" : "AUX-02"
+                    "bounty_title": "AUX-02"
                 }
             }
         }),
@@ -59,10 +63,7 @@ async fn main() -> Result<()> {
             "id": "1",
             "method": "delete_bounty",
             "params": {
-                "id": 1000,
-                "data": {
-                    "title" : "AUX-02"
-                }
+                "id": 1000
             }
         }),
     );
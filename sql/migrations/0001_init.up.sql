@@ -22,7 +22,7 @@ CREATE TABLE bounty (
 -- Project
 CREATE TABLE project (
   id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
-  project_id varchar(256) NOT NULL,
+  project_id varchar(256) NOT NULL UNIQUE,
   project_name varchar(256) NOT NULL,
   project_lead_address varchar(256) NOT NULL,
   project_budget varchar(256) NOT NULL,
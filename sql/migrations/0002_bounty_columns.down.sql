@@ -0,0 +1,7 @@
+ALTER TABLE bounty
+  DROP COLUMN bounty_assignee,
+  DROP COLUMN bounty_reward,
+  DROP COLUMN bounty_deliverables,
+  DROP COLUMN bounty_description;
+
+ALTER TABLE bounty RENAME COLUMN bounty_title TO title;
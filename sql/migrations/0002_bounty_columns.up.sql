@@ -0,0 +1,15 @@
+-- Bounty columns matching `model::bounty::Bounty`
+ALTER TABLE bounty RENAME COLUMN title TO bounty_title;
+
+ALTER TABLE bounty
+  ADD COLUMN bounty_assignee varchar(256) NOT NULL DEFAULT '',
+  ADD COLUMN bounty_reward varchar(256) NOT NULL DEFAULT '',
+  ADD COLUMN bounty_deliverables varchar(256) NOT NULL DEFAULT '',
+  ADD COLUMN bounty_description varchar(256) NOT NULL DEFAULT '';
+
+-- Defaults were only for the existing rows.
+ALTER TABLE bounty
+  ALTER COLUMN bounty_assignee DROP DEFAULT,
+  ALTER COLUMN bounty_reward DROP DEFAULT,
+  ALTER COLUMN bounty_deliverables DROP DEFAULT,
+  ALTER COLUMN bounty_description DROP DEFAULT;
@@ -0,0 +1,2 @@
+ALTER TABLE project DROP COLUMN version;
+ALTER TABLE bounty DROP COLUMN version;
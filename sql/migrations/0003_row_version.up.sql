@@ -0,0 +1,3 @@
+-- Row versions for the optimistic concurrency control on update.
+ALTER TABLE bounty ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
+ALTER TABLE project ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
@@ -0,0 +1 @@
+ALTER TABLE bounty DROP COLUMN bounty_project_id;
@@ -0,0 +1,3 @@
+-- Project of the bounty (optional, for the bounties created before).
+ALTER TABLE bounty
+  ADD COLUMN bounty_project_id BIGINT REFERENCES project(id);
@@ -0,0 +1,2 @@
+ALTER TABLE bounty DROP COLUMN bounty_status;
+DROP TYPE bounty_status;
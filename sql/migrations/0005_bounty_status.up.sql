@@ -0,0 +1,13 @@
+-- Bounty lifecycle status.
+CREATE TYPE bounty_status AS ENUM (
+  'open',
+  'assigned',
+  'submitted',
+  'approved',
+  'paid',
+  'expired',
+  'cancelled'
+);
+
+ALTER TABLE bounty
+  ADD COLUMN bounty_status bounty_status NOT NULL DEFAULT 'open';
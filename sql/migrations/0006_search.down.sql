@@ -0,0 +1,7 @@
+DROP TRIGGER bounty_search_tsv ON bounty;
+DROP FUNCTION bounty_search_tsv();
+ALTER TABLE bounty DROP COLUMN search_tsv;
+
+DROP TRIGGER project_search_tsv ON project;
+DROP FUNCTION project_search_tsv();
+ALTER TABLE project DROP COLUMN search_tsv;
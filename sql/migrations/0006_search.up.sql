@@ -0,0 +1,43 @@
+-- Full-text search documents, maintained by triggers.
+-- Weights: A - titles/names, B - secondary labels, C - descriptions.
+
+-- Project
+ALTER TABLE project ADD COLUMN search_tsv tsvector;
+
+CREATE FUNCTION project_search_tsv() RETURNS trigger AS $$
+BEGIN
+  NEW.search_tsv :=
+    setweight(to_tsvector('english', coalesce(NEW.project_name, '')), 'A') ||
+    setweight(to_tsvector('english', coalesce(NEW.project_id, '')), 'B') ||
+    setweight(to_tsvector('english', coalesce(NEW.project_type, '')), 'B') ||
+    setweight(to_tsvector('english', coalesce(NEW.project_description, '')), 'C');
+  RETURN NEW;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER project_search_tsv BEFORE INSERT OR UPDATE ON project
+  FOR EACH ROW EXECUTE FUNCTION project_search_tsv();
+
+UPDATE project SET search_tsv = NULL; -- (computed by the trigger)
+
+CREATE INDEX project_search_tsv_idx ON project USING GIN (search_tsv);
+
+-- Bounty
+ALTER TABLE bounty ADD COLUMN search_tsv tsvector;
+
+CREATE FUNCTION bounty_search_tsv() RETURNS trigger AS $$
+BEGIN
+  NEW.search_tsv :=
+    setweight(to_tsvector('english', coalesce(NEW.bounty_title, '')), 'A') ||
+    setweight(to_tsvector('english', coalesce(NEW.bounty_deliverables, '')), 'B') ||
+    setweight(to_tsvector('english', coalesce(NEW.bounty_description, '')), 'C');
+  RETURN NEW;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER bounty_search_tsv BEFORE INSERT OR UPDATE ON bounty
+  FOR EACH ROW EXECUTE FUNCTION bounty_search_tsv();
+
+UPDATE bounty SET search_tsv = NULL; -- (computed by the trigger)
+
+CREATE INDEX bounty_search_tsv_idx ON bounty USING GIN (search_tsv);
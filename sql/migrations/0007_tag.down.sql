@@ -0,0 +1,3 @@
+DROP TABLE bounty_tag;
+DROP TABLE project_tag;
+DROP TABLE tag;
@@ -0,0 +1,19 @@
+-- Tags (e.g., "rust", "frontend"), linked to projects and bounties.
+CREATE TABLE tag (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  name varchar(32) NOT NULL UNIQUE
+);
+
+CREATE TABLE project_tag (
+  project_id BIGINT NOT NULL REFERENCES project(id) ON DELETE CASCADE,
+  tag_id BIGINT NOT NULL REFERENCES tag(id) ON DELETE CASCADE,
+  PRIMARY KEY (project_id, tag_id)
+);
+CREATE INDEX project_tag_tag_id_idx ON project_tag (tag_id);
+
+CREATE TABLE bounty_tag (
+  bounty_id BIGINT NOT NULL REFERENCES bounty(id) ON DELETE CASCADE,
+  tag_id BIGINT NOT NULL REFERENCES tag(id) ON DELETE CASCADE,
+  PRIMARY KEY (bounty_id, tag_id)
+);
+CREATE INDEX bounty_tag_tag_id_idx ON bounty_tag (tag_id);
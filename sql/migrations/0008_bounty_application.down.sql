@@ -0,0 +1,2 @@
+DROP TABLE bounty_application;
+DROP TYPE application_status;
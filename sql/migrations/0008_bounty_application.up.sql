@@ -0,0 +1,19 @@
+-- Bounty applications, contributors proposing to work on an open bounty.
+CREATE TYPE application_status AS ENUM (
+  'pending',
+  'accepted',
+  'rejected'
+);
+
+CREATE TABLE bounty_application (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  application_bounty_id BIGINT NOT NULL REFERENCES bounty(id) ON DELETE CASCADE,
+  application_applicant_id BIGINT NOT NULL REFERENCES "user"(id) ON DELETE CASCADE,
+  application_message varchar(2048) NOT NULL,
+  application_estimated_days INTEGER NOT NULL CHECK (application_estimated_days > 0),
+  application_address varchar(256) NOT NULL,
+  application_status application_status NOT NULL DEFAULT 'pending',
+
+  -- One application per contributor and bounty.
+  UNIQUE (application_bounty_id, application_applicant_id)
+);
@@ -0,0 +1,4 @@
+DROP TABLE submission_item;
+DROP TABLE submission;
+DROP TYPE submission_status;
+DROP TABLE deliverable;
@@ -0,0 +1,39 @@
+-- Deliverables checklist of a bounty.
+CREATE TABLE deliverable (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  deliverable_bounty_id BIGINT NOT NULL REFERENCES bounty(id) ON DELETE CASCADE,
+  deliverable_title varchar(256) NOT NULL,
+  deliverable_description varchar(2048) NOT NULL
+);
+CREATE INDEX deliverable_bounty_id_idx ON deliverable (deliverable_bounty_id);
+
+-- Work submissions of the bounty assignee, with the review decision.
+-- (a resubmission is a new row, the previous ones being the history)
+CREATE TYPE submission_status AS ENUM (
+  'pending',
+  'approved',
+  'changes_requested',
+  'rejected'
+);
+
+CREATE TABLE submission (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  submission_bounty_id BIGINT NOT NULL REFERENCES bounty(id) ON DELETE CASCADE,
+  submission_submitter_id BIGINT NOT NULL REFERENCES "user"(id) ON DELETE CASCADE,
+  submission_notes varchar(2048) NOT NULL,
+  submission_status submission_status NOT NULL DEFAULT 'pending',
+  submission_reviewer_id BIGINT REFERENCES "user"(id) ON DELETE SET NULL,
+  submission_review_comment varchar(2048)
+);
+CREATE INDEX submission_bounty_id_idx ON submission (submission_bounty_id);
+
+-- Link and notes of a submission, per deliverable.
+CREATE TABLE submission_item (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  item_submission_id BIGINT NOT NULL REFERENCES submission(id) ON DELETE CASCADE,
+  item_deliverable_id BIGINT NOT NULL REFERENCES deliverable(id) ON DELETE CASCADE,
+  item_link varchar(1024) NOT NULL,
+  item_notes varchar(2048) NOT NULL,
+
+  UNIQUE (item_submission_id, item_deliverable_id)
+);
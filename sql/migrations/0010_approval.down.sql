@@ -0,0 +1,2 @@
+DROP TABLE approval_vote;
+ALTER TABLE project DROP COLUMN project_approval_policy;
@@ -0,0 +1,12 @@
+-- Approval policy of the project submissions (null being a single approval).
+ALTER TABLE project ADD COLUMN project_approval_policy JSONB;
+
+-- Approval votes of the reviewers, per submission.
+CREATE TABLE approval_vote (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  vote_submission_id BIGINT NOT NULL REFERENCES submission(id) ON DELETE CASCADE,
+  vote_reviewer_id BIGINT NOT NULL REFERENCES "user"(id) ON DELETE CASCADE,
+
+  -- One vote per reviewer and submission.
+  UNIQUE (vote_submission_id, vote_reviewer_id)
+);
@@ -0,0 +1,7 @@
+DROP TABLE ledger_line;
+DROP TABLE ledger_entry;
+DROP TABLE ledger_account;
+DROP FUNCTION ledger_entry_balanced();
+DROP FUNCTION ledger_immutable();
+DROP TYPE ledger_entry_kind;
+DROP TYPE ledger_account_kind;
@@ -0,0 +1,76 @@
+-- Double-entry ledger of the project budgets, escrows and payouts.
+-- Amounts are in the base unit of the denom (e.g., uatom).
+
+CREATE TYPE ledger_account_kind AS ENUM (
+  'external',
+  'project_budget',
+  'project_escrow',
+  'project_paid',
+  'contributor'
+);
+
+CREATE TABLE ledger_account (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  -- e.g., `project:1000:project_budget:uatom`, `contributor:cosmos1...:uatom`
+  account_key varchar(512) NOT NULL UNIQUE,
+  account_kind ledger_account_kind NOT NULL,
+  account_project_id BIGINT REFERENCES project(id),
+  account_owner varchar(256),
+  account_denom varchar(128) NOT NULL
+);
+
+CREATE TYPE ledger_entry_kind AS ENUM (
+  'funding',
+  'commitment',
+  'approval',
+  'payout',
+  'release'
+);
+
+CREATE TABLE ledger_entry (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  entry_kind ledger_entry_kind NOT NULL,
+  entry_project_id BIGINT NOT NULL REFERENCES project(id),
+  entry_bounty_id BIGINT REFERENCES bounty(id),
+  entry_memo varchar(512) NOT NULL,
+  entry_ctime timestamptz NOT NULL DEFAULT now()
+);
+CREATE INDEX ledger_entry_project_id_idx ON ledger_entry (entry_project_id);
+CREATE INDEX ledger_entry_bounty_id_idx ON ledger_entry (entry_bounty_id);
+
+CREATE TABLE ledger_line (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  line_entry_id BIGINT NOT NULL REFERENCES ledger_entry(id),
+  line_account_id BIGINT NOT NULL REFERENCES ledger_account(id),
+  -- Positive to debit the account, negative to credit it.
+  line_amount BIGINT NOT NULL CHECK (line_amount <> 0)
+);
+CREATE INDEX ledger_line_entry_id_idx ON ledger_line (line_entry_id);
+CREATE INDEX ledger_line_account_id_idx ON ledger_line (line_account_id);
+
+-- The journal is append only.
+CREATE FUNCTION ledger_immutable() RETURNS trigger AS $$
+BEGIN
+  RAISE EXCEPTION 'ledger % rows are immutable', TG_TABLE_NAME;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER ledger_entry_immutable BEFORE UPDATE OR DELETE ON ledger_entry
+  FOR EACH ROW EXECUTE FUNCTION ledger_immutable();
+CREATE TRIGGER ledger_line_immutable BEFORE UPDATE OR DELETE ON ledger_line
+  FOR EACH ROW EXECUTE FUNCTION ledger_immutable();
+
+-- Each entry lines sum to zero (checked at commit, once all lines are in).
+CREATE FUNCTION ledger_entry_balanced() RETURNS trigger AS $$
+BEGIN
+  IF (SELECT sum(line_amount) FROM ledger_line WHERE line_entry_id = NEW.line_entry_id) <> 0 THEN
+    RAISE EXCEPTION 'ledger entry % is not balanced', NEW.line_entry_id
+      USING ERRCODE = 'check_violation';
+  END IF;
+  RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE CONSTRAINT TRIGGER ledger_line_balanced AFTER INSERT ON ledger_line
+  DEFERRABLE INITIALLY DEFERRED
+  FOR EACH ROW EXECUTE FUNCTION ledger_entry_balanced();
@@ -0,0 +1,3 @@
+DROP TABLE payout_bounty;
+DROP TABLE payout;
+DROP TYPE payout_status;
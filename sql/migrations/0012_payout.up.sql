@@ -0,0 +1,26 @@
+-- Payout transactions of the approved bounties, built unsigned for the
+-- project lead wallet, then confirmed with the hash of the signed tx.
+CREATE TYPE payout_status AS ENUM (
+  'pending',
+  'confirmed'
+);
+
+CREATE TABLE payout (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  payout_from_address varchar(256) NOT NULL,
+  payout_chain_id varchar(64) NOT NULL,
+  -- The amino JSON sign doc, as the exact bytes to sign.
+  payout_sign_doc TEXT NOT NULL,
+  payout_status payout_status NOT NULL DEFAULT 'pending',
+  payout_tx_hash varchar(64) UNIQUE
+);
+
+-- Bounties paid by a payout.
+CREATE TABLE payout_bounty (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  payout_id BIGINT NOT NULL REFERENCES payout(id) ON DELETE CASCADE,
+  bounty_id BIGINT NOT NULL REFERENCES bounty(id) ON DELETE CASCADE,
+
+  UNIQUE (payout_id, bounty_id)
+);
+CREATE INDEX payout_bounty_bounty_id_idx ON payout_bounty (bounty_id);
@@ -0,0 +1,9 @@
+-- Wallet addresses linked by the users (normalized lowercase bech32).
+CREATE TABLE user_wallet (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  wallet_user_id BIGINT NOT NULL REFERENCES "user"(id) ON DELETE CASCADE,
+  wallet_address varchar(256) NOT NULL UNIQUE
+    CHECK (wallet_address = lower(wallet_address)),
+  wallet_label varchar(128) NOT NULL
+);
+CREATE INDEX user_wallet_user_id_idx ON user_wallet (wallet_user_id);
@@ -0,0 +1,11 @@
+-- Registry of the denoms the projects pay in, amounts being stored in the
+-- base denom (e.g., `uatom`), and displayed in the display denom
+-- (e.g., `atom`, 10^exponent base units).
+CREATE TABLE denom (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  denom_base varchar(128) NOT NULL UNIQUE,
+  denom_display varchar(128) NOT NULL,
+  denom_exponent INT NOT NULL CHECK (denom_exponent BETWEEN 0 AND 18),
+  denom_symbol varchar(32) NOT NULL,
+  denom_enabled BOOLEAN NOT NULL DEFAULT true
+);
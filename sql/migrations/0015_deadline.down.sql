@@ -0,0 +1,21 @@
+-- (enum values cannot be dropped, so the type is recreated without 'lapsed')
+DROP INDEX application_deadline_idx;
+DROP INDEX bounty_deadline_idx;
+
+UPDATE bounty_application SET application_status = 'rejected'
+  WHERE application_status = 'lapsed';
+ALTER TYPE application_status RENAME TO application_status_old;
+CREATE TYPE application_status AS ENUM (
+  'pending',
+  'accepted',
+  'rejected'
+);
+ALTER TABLE bounty_application
+  ALTER COLUMN application_status DROP DEFAULT,
+  ALTER COLUMN application_status TYPE application_status
+    USING application_status::text::application_status,
+  ALTER COLUMN application_status SET DEFAULT 'pending';
+DROP TYPE application_status_old;
+
+ALTER TABLE bounty_application DROP COLUMN application_deadline;
+ALTER TABLE bounty DROP COLUMN bounty_deadline;
@@ -0,0 +1,12 @@
+-- Bounty deadlines (expired by the scheduler, see `scheduler`), and the
+-- delivery deadline of the applicants (un-assigned when missed).
+ALTER TABLE bounty ADD COLUMN bounty_deadline timestamptz;
+ALTER TABLE bounty_application ADD COLUMN application_deadline timestamptz;
+
+-- An accepted application whose deadline passed before the submission.
+ALTER TYPE application_status ADD VALUE 'lapsed';
+
+CREATE INDEX bounty_deadline_idx ON bounty (bounty_deadline)
+  WHERE bounty_deadline IS NOT NULL;
+CREATE INDEX application_deadline_idx ON bounty_application (application_deadline)
+  WHERE application_deadline IS NOT NULL;
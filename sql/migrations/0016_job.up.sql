@@ -0,0 +1,27 @@
+-- Background job queue (see `model::job` and `worker`).
+CREATE TYPE job_status AS ENUM (
+  'queued',
+  'running',
+  'succeeded',
+  'dead',
+  'cancelled'
+);
+
+CREATE TABLE job (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  job_kind varchar(64) NOT NULL,
+  job_payload jsonb NOT NULL DEFAULT '{}',
+  job_status job_status NOT NULL DEFAULT 'queued',
+  job_attempts INTEGER NOT NULL DEFAULT 0 CHECK (job_attempts >= 0),
+  job_max_attempts INTEGER NOT NULL DEFAULT 5 CHECK (job_max_attempts > 0),
+  -- Not run before (the retry backoff).
+  job_run_at timestamptz NOT NULL DEFAULT now(),
+  job_locked_at timestamptz,
+  job_locked_by varchar(64),
+  job_last_error TEXT,
+  job_ctime timestamptz NOT NULL DEFAULT now()
+);
+
+-- The claim query scans the due queued jobs, and the expired locks.
+CREATE INDEX job_queued_idx ON job (job_run_at) WHERE job_status = 'queued';
+CREATE INDEX job_running_idx ON job (job_locked_at) WHERE job_status = 'running';
@@ -0,0 +1,28 @@
+-- Project webhook subscriptions (see `model::webhook` and `worker::webhook`).
+CREATE TABLE webhook (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  webhook_project_id BIGINT NOT NULL REFERENCES project(id) ON DELETE CASCADE,
+  webhook_url varchar(2048) NOT NULL,
+  -- Comma separated event types (e.g., `bounty.transitioned,bounty.*`).
+  webhook_events varchar(1024) NOT NULL,
+  -- The HMAC key of the payload signatures.
+  webhook_secret varchar(256) NOT NULL,
+  webhook_enabled BOOLEAN NOT NULL DEFAULT true
+);
+
+CREATE INDEX webhook_project_idx ON webhook (webhook_project_id);
+
+-- Delivery log, one row per attempt.
+CREATE TABLE webhook_delivery (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  delivery_webhook_id BIGINT NOT NULL REFERENCES webhook(id) ON DELETE CASCADE,
+  delivery_event_id varchar(64) NOT NULL,
+  delivery_event_type varchar(64) NOT NULL,
+  delivery_attempt INTEGER NOT NULL CHECK (delivery_attempt > 0),
+  delivery_status_code INTEGER,
+  delivery_error TEXT,
+  delivery_ok BOOLEAN NOT NULL,
+  delivery_time timestamptz NOT NULL
+);
+
+CREATE INDEX webhook_delivery_webhook_idx ON webhook_delivery (delivery_webhook_id, delivery_event_id);
@@ -0,0 +1,3 @@
+DROP TABLE notification_pref;
+DROP TABLE notification;
+DROP TYPE notification_kind;
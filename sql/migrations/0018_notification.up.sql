@@ -0,0 +1,34 @@
+-- In-app notification inbox (see `model::notification`).
+CREATE TYPE notification_kind AS ENUM (
+  'assignment',
+  'review',
+  'payout',
+  'comment',
+  'mention'
+);
+
+CREATE TABLE notification (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  notification_user_id BIGINT NOT NULL REFERENCES "user"(id) ON DELETE CASCADE,
+  notification_kind notification_kind NOT NULL,
+  -- The entity the notification is about (e.g., `submission` 1000).
+  notification_entity varchar(64) NOT NULL,
+  notification_entity_id BIGINT NOT NULL,
+  notification_message TEXT NOT NULL,
+  notification_read BOOLEAN NOT NULL DEFAULT false,
+  notification_ctime timestamptz NOT NULL
+);
+
+CREATE INDEX notification_user_unread_idx ON notification (notification_user_id)
+  WHERE NOT notification_read;
+
+-- Per-user notification preferences (no row, all enabled).
+CREATE TABLE notification_pref (
+  id BIGINT GENERATED BY DEFAULT AS IDENTITY (START WITH 1000) PRIMARY KEY,
+  pref_user_id BIGINT NOT NULL UNIQUE REFERENCES "user"(id) ON DELETE CASCADE,
+  pref_assignment BOOLEAN NOT NULL DEFAULT true,
+  pref_review BOOLEAN NOT NULL DEFAULT true,
+  pref_payout BOOLEAN NOT NULL DEFAULT true,
+  pref_comment BOOLEAN NOT NULL DEFAULT true,
+  pref_mention BOOLEAN NOT NULL DEFAULT true
+);
@@ -0,0 +1,3 @@
+-- Admins manage the platform settings (e.g., denoms, jobs) and fund any
+-- project (see `UserBmc::check_admin`).
+ALTER TABLE "user" ADD COLUMN admin boolean NOT NULL DEFAULT false;
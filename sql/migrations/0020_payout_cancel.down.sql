@@ -0,0 +1,13 @@
+-- (enum values cannot be dropped, so the type is recreated without 'cancelled')
+DELETE FROM payout WHERE payout_status = 'cancelled';
+ALTER TYPE payout_status RENAME TO payout_status_old;
+CREATE TYPE payout_status AS ENUM (
+  'pending',
+  'confirmed'
+);
+ALTER TABLE payout
+  ALTER COLUMN payout_status DROP DEFAULT,
+  ALTER COLUMN payout_status TYPE payout_status
+    USING payout_status::text::payout_status,
+  ALTER COLUMN payout_status SET DEFAULT 'pending';
+DROP TYPE payout_status_old;
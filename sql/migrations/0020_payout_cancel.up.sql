@@ -0,0 +1,3 @@
+-- A pending payout cancelled by the project lead (e.g., never broadcast),
+-- releasing its bounties for a new payout.
+ALTER TYPE payout_status ADD VALUE 'cancelled';
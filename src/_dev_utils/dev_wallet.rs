@@ -0,0 +1,62 @@
+//! Wallets of deterministic dev keys (e.g., `lead`, `alice`), to link in
+//! the seeds and tests through the same signed challenge as the real ones.
+//!
+//! NOTE: The keys derive from their public names, never use them on a chain.
+//!
+
+use crate::crypt::adr036;
+use crate::ctx::Ctx;
+use crate::model::address;
+use crate::model::wallet::{WalletBmc, WalletForChallenge, WalletForLink};
+use crate::model::{self, ModelManager};
+use openssl::ec::EcKey;
+use openssl::pkey::Private;
+use sha2::{Digest, Sha256};
+
+pub struct DevWallet {
+    key: EcKey<Private>,
+}
+
+impl DevWallet {
+    /// The wallet of the dev key `name`.
+    pub fn new(name: &str) -> Self {
+        let secret = Sha256::digest(format!("porta dev wallet: {name}").as_bytes());
+        let key = adr036::key_from_secret(&secret).expect("dev wallet key");
+
+        DevWallet { key }
+    }
+
+    /// The address on the `hrp` chain (e.g., `cosmos`).
+    pub fn address(&self, hrp: &str) -> String {
+        let pub_key = adr036::pub_key(&self.key).expect("dev wallet pub key");
+        address::from_pub_key(hrp, &pub_key).expect("dev wallet address")
+    }
+
+    /// The link data of the wallet, with its signature of the `challenge`.
+    pub fn sign_link(&self, wallet_address: &str, challenge: &str) -> WalletForLink {
+        let pub_key = adr036::pub_key(&self.key).expect("dev wallet pub key");
+        let signature = adr036::sign(wallet_address, challenge.as_bytes(), &self.key)
+            .expect("dev wallet signature");
+
+        WalletForLink {
+            wallet_address: wallet_address.to_string(),
+            wallet_label: String::new(),
+            challenge: challenge.to_string(),
+            pub_key: adr036::b64_encode(&pub_key),
+            signature: adr036::b64_encode(&signature),
+        }
+    }
+
+    /// Link the wallet address on the `hrp` chain to the `Ctx` user.
+    pub async fn link(&self, ctx: &Ctx, mm: &ModelManager, hrp: &str) -> model::Result<i64> {
+        let challenge = WalletBmc::challenge(
+            ctx,
+            WalletForChallenge {
+                wallet_address: self.address(hrp),
+            },
+        )?;
+        let wallet_l = self.sign_link(&challenge.wallet_address, &challenge.message);
+
+        WalletBmc::link(ctx, mm, wallet_l).await
+    }
+}
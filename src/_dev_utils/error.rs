@@ -0,0 +1,50 @@
+use crate::{ctx, model};
+use serde::Serialize;
+use serde_with::{serde_as, DisplayFromStr};
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+pub enum Error {
+    // -- Seed
+    SeedReadFail { file: String, cause: String },
+    SeedParseFail { file: String, cause: String },
+    SeedFormatUnknown { file: String },
+    SeedRefNotFound { kind: &'static str, key: String },
+    SeedInvalid(#[serde_as(as = "DisplayFromStr")] validator::ValidationErrors),
+
+    // -- Modules
+    Ctx(ctx::Error),
+    Model(model::Error),
+}
+
+// region:    --- Froms
+impl From<ctx::Error> for Error {
+    fn from(val: ctx::Error) -> Self {
+        Self::Ctx(val)
+    }
+}
+
+impl From<model::Error> for Error {
+    fn from(val: model::Error) -> Self {
+        Self::Model(val)
+    }
+}
+
+impl From<validator::ValidationErrors> for Error {
+    fn from(val: validator::ValidationErrors) -> Self {
+        Self::SeedInvalid(val)
+    }
+}
+// endregion: --- Froms
+
+// region:    --- Error Boilerplate
+impl core::fmt::Display for Error {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
+        write!(fmt, "{self:?}")
+    }
+}
+
+impl std::error::Error for Error {}
+// endregion: --- Error Boilerplate
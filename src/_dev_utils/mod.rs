@@ -1,6 +1,11 @@
 // region:    --- Modules
 
 mod dev_db;
+pub mod dev_wallet;
+mod error;
+pub mod seed;
+
+pub use self::error::{Error, Result};
 
 use crate::ctx::Ctx;
 use crate::model::bounty::{Bounty, BountyBmc, BountyForCreate};
@@ -12,7 +17,7 @@ use tracing::info;
 // endregion: --- Modules
 
 /// Initialize environment for local development.
-/// (for early development, will be called from main()).
+/// (called by the `dev` command only, it drops and recreates the dev db).
 pub async fn init_dev() {
     static INIT: OnceCell<()> = OnceCell::const_new();
 
@@ -24,18 +29,32 @@ pub async fn init_dev() {
     .await;
 }
 
-/// Initialize test environment.
+/// Initialize an in-memory ModelManager for local demos (no db needed).
+pub async fn init_dev_memory() -> ModelManager {
+    let mm = ModelManager::new_in_memory();
+    dev_db::init_dev_memory(&mm).await.unwrap();
+
+    mm
+}
+
+/// Initialize test environment, with a new isolated db for each call
+/// (cloned from the migrated and seeded test template), so tests can run
+/// in parallel and do not see each other fixtures.
+/// (each test db is dropped with the last clone of its ModelManager)
 pub async fn init_test() -> ModelManager {
-    static INIT: OnceCell<ModelManager> = OnceCell::const_new();
+    static INIT: OnceCell<()> = OnceCell::const_new();
 
-    let mm = INIT
-        .get_or_init(|| async {
-            init_dev().await;
-            ModelManager::new().await.unwrap()
-        })
-        .await;
+    INIT.get_or_init(|| async {
+        init_dev().await;
+        dev_db::init_test_template().await.unwrap();
+    })
+    .await;
 
-    mm.clone()
+    let test_db = dev_db::new_test_db().await.unwrap();
+    ModelManager::new_with_db_url(&test_db.url())
+        .await
+        .unwrap()
+        .with_drop_guard(test_db)
 }
 
 pub async fn seed_tasks(
@@ -57,6 +76,8 @@ pub async fn seed_tasks(
 
                 bounty_description: title.to_string(),
                 bounty_reward: title.to_string(),
+                bounty_project_id: None,
+                bounty_deadline: None,
             },
         )
         .await?;
@@ -0,0 +1,315 @@
+//! Declarative fixtures (users, denoms, projects, bounties) from a seed file.
+//!
+//! Design:
+//!
+//! - A seed file is `.toml` or `.json`, with `users`, `wallets`, `denoms`,
+//!   `projects` and `bounties` lists, each item being the `*ForCreate` data
+//!   of the entity (the wallets being of the dev keys, see `DevWallet`).
+//!   Users can be seeded as admins (`admin = true`).
+//! - Projects are referenced by their `key` (defaults to their `project_id`),
+//!   e.g., `project = "porta"` on a bounty. Bounties can also have a `key`,
+//!   to find their id in the returned `Seeded`.
+//! - The seed is applied through the `*Bmc` layer (same validation and
+//!   events as the app), in a single transaction.
+//!
+
+use crate::_dev_utils::dev_wallet::DevWallet;
+use crate::_dev_utils::{Error, Result};
+use crate::ctx::Ctx;
+use crate::model::bounty::{BountyBmc, BountyForCreate};
+use crate::model::denom::{DenomBmc, DenomForCreate};
+use crate::model::project::{ProjectBmc, ProjectForCreate};
+use crate::model::user::{User, UserBmc, UserForCreate};
+use crate::model::ModelManager;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tracing::info;
+use validator::Validate;
+
+// region:    --- Seed Types
+
+#[derive(Default, Deserialize)]
+pub struct Seed {
+    #[serde(default)]
+    pub users: Vec<SeedUser>,
+    #[serde(default)]
+    pub wallets: Vec<SeedWallet>,
+    #[serde(default)]
+    pub denoms: Vec<DenomForCreate>,
+    #[serde(default)]
+    pub projects: Vec<SeedProject>,
+    #[serde(default)]
+    pub bounties: Vec<SeedBounty>,
+}
+
+#[derive(Deserialize)]
+pub struct SeedUser {
+    /// If the user is an admin (see `UserBmc::check_admin`).
+    #[serde(default)]
+    pub admin: bool,
+    #[serde(flatten)]
+    pub data: UserForCreate,
+}
+
+#[derive(Deserialize)]
+pub struct SeedWallet {
+    /// The user linking the wallet (of the seed, or already in the db).
+    pub username: String,
+    /// Name of the dev key of the wallet.
+    pub key: String,
+    /// Bech32 prefix of the address (defaults to `cosmos`).
+    pub hrp: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SeedProject {
+    /// Reference key for the bounties (defaults to `project_id`).
+    pub key: Option<String>,
+    #[serde(flatten)]
+    pub data: ProjectForCreate,
+}
+
+#[derive(Deserialize)]
+pub struct SeedBounty {
+    pub key: Option<String>,
+    /// Key of the bounty project.
+    pub project: Option<String>,
+    #[serde(flatten)]
+    pub data: BountyForCreate,
+}
+
+/// The ids of the seeded entities, by username or key.
+#[derive(Debug, Default)]
+pub struct Seeded {
+    pub users: HashMap<String, i64>,
+    pub projects: HashMap<String, i64>,
+    pub bounties: HashMap<String, i64>,
+}
+
+// endregion: --- Seed Types
+
+impl Seed {
+    /// Load a `.toml` or `.json` seed file.
+    pub fn from_file(file: impl AsRef<Path>) -> Result<Self> {
+        let file = file.as_ref();
+        let file_name = file.to_string_lossy().to_string();
+        let content = fs::read_to_string(file).map_err(|ex| Error::SeedReadFail {
+            file: file_name.clone(),
+            cause: ex.to_string(),
+        })?;
+
+        let seed = match file.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&content).map_err(|ex| ex.to_string()),
+            Some("json") => serde_json::from_str(&content).map_err(|ex| ex.to_string()),
+            _ => return Err(Error::SeedFormatUnknown { file: file_name }),
+        };
+
+        seed.map_err(|cause| Error::SeedParseFail {
+            file: file_name,
+            cause,
+        })
+    }
+
+    /// Create the seed entities, all or nothing.
+    pub async fn apply(self, ctx: &Ctx, mm: &ModelManager) -> Result<Seeded> {
+        mm.transaction(|mm| async move { self.apply_in(ctx, &mm).await })
+            .await
+    }
+
+    async fn apply_in(self, ctx: &Ctx, mm: &ModelManager) -> Result<Seeded> {
+        let mut seeded = Seeded::default();
+
+        for SeedUser { admin, data } in self.users {
+            data.validate()?;
+            let username = data.username.clone();
+            let id = UserBmc::create(ctx, mm, data).await?;
+            if admin {
+                UserBmc::set_admin(ctx, mm, id, true).await?;
+            }
+            seeded.users.insert(username, id);
+        }
+
+        for SeedWallet { username, key, hrp } in self.wallets {
+            let user_id = match seeded.users.get(&username) {
+                Some(id) => *id,
+                None => UserBmc::first_by_username::<User>(ctx, mm, &username)
+                    .await?
+                    .map(|user| user.id)
+                    .ok_or_else(|| Error::SeedRefNotFound {
+                        kind: "user",
+                        key: username.clone(),
+                    })?,
+            };
+            let hrp = hrp.as_deref().unwrap_or("cosmos");
+            DevWallet::new(&key)
+                .link(&Ctx::new(user_id)?, mm, hrp)
+                .await?;
+        }
+
+        for denom_c in self.denoms {
+            denom_c.validate()?;
+            DenomBmc::create(ctx, mm, denom_c).await?;
+        }
+
+        for SeedProject { key, data } in self.projects {
+            data.validate()?;
+            let key = key.unwrap_or_else(|| data.project_id.clone());
+            let id = ProjectBmc::create(ctx, mm, data).await?;
+            seeded.projects.insert(key, id);
+        }
+
+        for SeedBounty {
+            key,
+            project,
+            mut data,
+        } in self.bounties
+        {
+            data.validate()?;
+            if let Some(project) = project {
+                let project_id =
+                    seeded
+                        .projects
+                        .get(&project)
+                        .ok_or_else(|| Error::SeedRefNotFound {
+                            kind: "project",
+                            key: project.clone(),
+                        })?;
+                data.bounty_project_id = Some(*project_id);
+            }
+            let id = BountyBmc::create(ctx, mm, data).await?;
+            if let Some(key) = key {
+                seeded.bounties.insert(key, id);
+            }
+        }
+
+        info!(
+            "{:<12} - seed - {} users, {} projects, {} bounties",
+            "FOR-DEV-ONLY",
+            seeded.users.len(),
+            seeded.projects.len(),
+            seeded.bounties.len()
+        );
+
+        Ok(seeded)
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::bounty::{BountyBmc, BountyFilter};
+    use crate::model::wallet::WalletBmc;
+    use anyhow::{Context, Result};
+
+    const SEED_TOML: &str = r#"
+[[users]]
+username = "seed_user_01"
+pwd_clear = "seed_user_01 pwd"
+
+[[wallets]]
+username = "seed_user_01"
+key = "seed_user_01"
+
+[[denoms]]
+denom_base = "uatom"
+denom_display = "atom"
+denom_exponent = 6
+denom_symbol = "ATOM"
+
+[[projects]]
+key = "p1"
+project_id = "seed-project-01"
+project_name = "Seed Project 01"
+project_lead_address = "cosmos1udzkhs056fc0f2texd6cv30acg0rjept43xsph"
+project_budget = "1000"
+project_denom = "uatom"
+project_type = "dev"
+project_description = "A seeded project"
+
+[[bounties]]
+key = "b1"
+project = "p1"
+bounty_title = "Seed bounty 01"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "A PR"
+bounty_description = "A seeded bounty"
+"#;
+
+    #[tokio::test]
+    async fn test_seed_apply_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = ModelManager::new_in_memory();
+        let ctx = Ctx::root_ctx();
+        let seed: Seed = toml::from_str(SEED_TOML)?;
+
+        // -- Exec
+        let seeded = seed.apply(&ctx, &mm).await?;
+
+        // -- Check
+        let user_id = *seeded.users.get("seed_user_01").context("seed_user_01")?;
+        let wallets = WalletBmc::list_for_user(&ctx, &mm, user_id).await?;
+        assert_eq!(
+            wallets.first().map(|w| w.wallet_address.clone()),
+            Some(DevWallet::new("seed_user_01").address("cosmos"))
+        );
+        let project_id = *seeded.projects.get("p1").context("project p1")?;
+        let bounty_id = *seeded.bounties.get("b1").context("bounty b1")?;
+        let bounty = BountyBmc::get(&ctx, &mm, bounty_id).await?;
+        assert_eq!(bounty.bounty_project_id, Some(project_id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_seed_apply_err_ref_not_found() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = ModelManager::new_in_memory();
+        let ctx = Ctx::root_ctx();
+        let seed: Seed =
+            toml::from_str(&SEED_TOML.replace(r#"project = "p1""#, r#"project = "nope""#))?;
+
+        // -- Exec
+        let res = seed.apply(&ctx, &mm).await;
+
+        // -- Check
+        assert!(
+            matches!(&res, Err(Error::SeedRefNotFound { kind: "project", key }) if key == "nope"),
+            "SeedRefNotFound not matching"
+        );
+        let filter = BountyFilter::default();
+        assert!(
+            BountyBmc::list(&ctx, &mm, Some(filter), None)
+                .await?
+                .is_empty(),
+            "seed should be all or nothing"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_seed_files_ok() -> Result<()> {
+        for file in ["seed/dev.toml", "seed/demo.toml"] {
+            // -- Setup & Fixtures
+            let mm = ModelManager::new_in_memory();
+            let ctx = Ctx::root_ctx();
+
+            // -- Exec
+            let seed = Seed::from_file(file)?;
+            let seeded = seed.apply(&ctx, &mm).await?;
+
+            // -- Check
+            assert!(
+                seeded.users.contains_key("demo1"),
+                "{file} should have demo1"
+            );
+        }
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
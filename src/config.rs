@@ -2,6 +2,7 @@ use crate::{Error, Result};
 use std::env;
 use std::str::FromStr;
 use std::sync::OnceLock;
+use std::time::Duration;
 
 pub fn config() -> &'static Config {
     static INSTANCE: OnceLock<Config> = OnceLock::new();
@@ -22,6 +23,23 @@ pub struct Config {
 
     // -- Db
     pub DB_URL: String,
+    pub DB_MIGRATIONS_DIR: String,
+
+    // -- Chain
+    /// Allowed bech32 address prefixes (e.g., `cosmos`).
+    pub ADDRESS_HRPS: Vec<String>,
+
+    // -- Scheduler
+    pub SCHEDULER_INTERVAL_SEC: f64,
+
+    // -- Worker
+    pub WORKER_COUNT: usize,
+    pub WORKER_POLL_SEC: f64,
+
+    // -- Webhook
+    /// Allow the http and the not public (e.g., localhost) webhook URLs.
+    /// (for local dev and tests ONLY)
+    pub WEBHOOK_ALLOW_LOCAL: bool,
 
     // -- Web
     pub WEB_FOLDER: String,
@@ -38,6 +56,20 @@ impl Config {
 
             // -- Db
             DB_URL: get_env("SERVICE_DB_URL")?,
+            DB_MIGRATIONS_DIR: get_env("SERVICE_DB_MIGRATIONS_DIR")?,
+
+            // -- Chain
+            ADDRESS_HRPS: get_env_list("SERVICE_ADDRESS_HRPS")?,
+
+            // -- Scheduler
+            SCHEDULER_INTERVAL_SEC: get_env_secs("SERVICE_SCHEDULER_INTERVAL_SEC")?,
+
+            // -- Worker
+            WORKER_COUNT: get_env_parse("SERVICE_WORKER_COUNT")?,
+            WORKER_POLL_SEC: get_env_secs("SERVICE_WORKER_POLL_SEC")?,
+
+            // -- Webhook
+            WEBHOOK_ALLOW_LOCAL: get_env_parse("SERVICE_WEBHOOK_ALLOW_LOCAL")?,
 
             // -- Web
             WEB_FOLDER: get_env("SERVICE_WEB_FOLDER")?,
@@ -54,6 +86,32 @@ fn get_env_parse<T: FromStr>(name: &'static str) -> Result<T> {
     val.parse::<T>().map_err(|_| Error::ConfigWrongFormat(name))
 }
 
+/// A positive number of seconds (zero, negative, NaN, or out of a
+/// `Duration` range being a wrong format).
+fn get_env_secs(name: &'static str) -> Result<f64> {
+    let secs: f64 = get_env_parse(name)?;
+    if secs <= 0. || Duration::try_from_secs_f64(secs).is_err() {
+        return Err(Error::ConfigWrongFormat(name));
+    }
+
+    Ok(secs)
+}
+
+/// Comma separated values (none being a wrong format).
+fn get_env_list(name: &'static str) -> Result<Vec<String>> {
+    let list: Vec<String> = get_env(name)?
+        .split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect();
+
+    if list.is_empty() {
+        return Err(Error::ConfigWrongFormat(name));
+    }
+
+    Ok(list)
+}
+
 fn get_env_b64u_as_u8s(name: &'static str) -> Result<Vec<u8>> {
     base64_url::decode(&get_env(name)?).map_err(|_| Error::ConfigWrongFormat(name))
 }
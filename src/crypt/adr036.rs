@@ -0,0 +1,174 @@
+//! ADR-036 off-chain signatures, of arbitrary data by a cosmos wallet
+//! (e.g., the `signArbitrary` of Keplr or Leap).
+//!
+//! The signed bytes are the amino JSON sign doc of a single `sign/MsgSignData`
+//! message (sorted keys, no whitespace, empty chain id, zero account number
+//! and sequence), hashed with SHA-256 and signed with the secp256k1 key
+//! of the wallet, the signature being the 64 bytes `r || s`.
+//!
+
+use crate::crypt::{Error, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::ec::{EcGroup, EcKey, EcKeyRef, EcPoint, PointConversionForm};
+use openssl::ecdsa::EcdsaSig;
+use openssl::nid::Nid;
+use openssl::pkey::Private;
+use sha2::{Digest, Sha256};
+
+const SCALAR_LEN: usize = 32;
+
+/// The amino JSON sign doc of `data` signed by `signer`.
+pub fn sign_doc(signer: &str, data: &[u8]) -> String {
+    let msg = serde_json::json!({
+        "type": "sign/MsgSignData",
+        "value": {
+            "data": BASE64.encode(data),
+            "signer": signer,
+        }
+    });
+    // (`serde_json` objects are sorted maps, and compact by default)
+    serde_json::json!({
+        "account_number": "0",
+        "chain_id": "",
+        "fee": { "amount": [], "gas": "0" },
+        "memo": "",
+        "msgs": [msg],
+        "sequence": "0",
+    })
+    .to_string()
+}
+
+/// Verify the `signature` (`r || s`) of `data` by `signer`, with the
+/// secp256k1 compressed `pub_key` of the signer.
+/// (`pub_key` being the one of the `signer` address is for the caller to check)
+pub fn verify(signer: &str, data: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+    let group = secp256k1()?;
+    let mut bn_ctx = BigNumContext::new().map_err(|_| Error::SigInvalidPubKey)?;
+    let point =
+        EcPoint::from_bytes(&group, pub_key, &mut bn_ctx).map_err(|_| Error::SigInvalidPubKey)?;
+    let key = EcKey::from_public_key(&group, &point).map_err(|_| Error::SigInvalidPubKey)?;
+
+    if signature.len() != 2 * SCALAR_LEN {
+        return Err(Error::SigInvalidFormat);
+    }
+    let (r, s) = signature.split_at(SCALAR_LEN);
+    let r = BigNum::from_slice(r).map_err(|_| Error::SigInvalidFormat)?;
+    let s = BigNum::from_slice(s).map_err(|_| Error::SigInvalidFormat)?;
+    let sig = EcdsaSig::from_private_components(r, s).map_err(|_| Error::SigInvalidFormat)?;
+
+    let digest = Sha256::digest(sign_doc(signer, data).as_bytes());
+    match sig.verify(&digest, &key) {
+        Ok(true) => Ok(()),
+        _ => Err(Error::SigNotMatching),
+    }
+}
+
+/// Sign `data` as `signer`, with its secp256k1 `key`.
+/// Returns the signature (`r || s`).
+/// (for the dev wallets, the real ones sign in the browser)
+pub fn sign(signer: &str, data: &[u8], key: &EcKeyRef<Private>) -> Result<Vec<u8>> {
+    let digest = Sha256::digest(sign_doc(signer, data).as_bytes());
+    let sig = EcdsaSig::sign(&digest, key).map_err(|_| Error::SigInvalidFormat)?;
+
+    let mut signature = Vec::with_capacity(2 * SCALAR_LEN);
+    for scalar in [sig.r(), sig.s()] {
+        let bytes = scalar
+            .to_vec_padded(SCALAR_LEN as i32)
+            .map_err(|_| Error::SigInvalidFormat)?;
+        signature.extend(bytes);
+    }
+
+    Ok(signature)
+}
+
+/// The secp256k1 key of the 32 bytes `secret`.
+/// (for the dev wallets, see `sign`)
+pub fn key_from_secret(secret: &[u8]) -> Result<EcKey<Private>> {
+    let group = secp256k1()?;
+    let mut bn_ctx = BigNumContext::new().map_err(|_| Error::SigInvalidPubKey)?;
+    let d = BigNum::from_slice(secret).map_err(|_| Error::SigInvalidPubKey)?;
+    let mut point = EcPoint::new(&group).map_err(|_| Error::SigInvalidPubKey)?;
+    point
+        .mul_generator2(&group, &d, &mut bn_ctx)
+        .map_err(|_| Error::SigInvalidPubKey)?;
+
+    EcKey::from_private_components(&group, &d, &point).map_err(|_| Error::SigInvalidPubKey)
+}
+
+/// The secp256k1 compressed public key of `key`.
+pub fn pub_key(key: &EcKeyRef<Private>) -> Result<Vec<u8>> {
+    let group = secp256k1()?;
+    let mut bn_ctx = BigNumContext::new().map_err(|_| Error::SigInvalidPubKey)?;
+    key.public_key()
+        .to_bytes(&group, PointConversionForm::COMPRESSED, &mut bn_ctx)
+        .map_err(|_| Error::SigInvalidPubKey)
+}
+
+pub fn secp256k1() -> Result<EcGroup> {
+    EcGroup::from_curve_name(Nid::SECP256K1).map_err(|_| Error::SigInvalidPubKey)
+}
+
+/// Standard (padded) base64, as the signatures and keys of the wallets.
+pub fn b64_decode(value: &str) -> Result<Vec<u8>> {
+    BASE64.decode(value).map_err(|_| Error::SigInvalidFormat)
+}
+
+pub fn b64_encode(bytes: &[u8]) -> String {
+    BASE64.encode(bytes)
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    fn fx_key() -> Result<EcKey<Private>> {
+        Ok(key_from_secret(&Sha256::digest(b"adr036 test key"))?)
+    }
+
+    #[test]
+    fn test_sign_doc_ok() -> Result<()> {
+        // -- Exec
+        let doc = sign_doc("cosmos1signer", b"hello");
+
+        // -- Check
+        assert_eq!(
+            doc,
+            r#"{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","msgs":[{"type":"sign/MsgSignData","value":{"data":"aGVsbG8=","signer":"cosmos1signer"}}],"sequence":"0"}"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sign_verify_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let key = fx_key()?;
+        let pub_key = pub_key(&key)?;
+
+        // -- Exec
+        let signature = sign("cosmos1signer", b"hello", &key)?;
+
+        // -- Check
+        assert_eq!(signature.len(), 64);
+        verify("cosmos1signer", b"hello", &pub_key, &signature)?;
+        assert!(matches!(
+            verify("cosmos1signer", b"hello!", &pub_key, &signature),
+            Err(Error::SigNotMatching)
+        ));
+        assert!(matches!(
+            verify("cosmos1other", b"hello", &pub_key, &signature),
+            Err(Error::SigNotMatching)
+        ));
+        assert!(matches!(
+            verify("cosmos1signer", b"hello", &pub_key[1..], &signature),
+            Err(Error::SigInvalidPubKey)
+        ));
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
@@ -17,6 +17,11 @@ pub enum Error {
     TokenSignatureNotMatching,
     TokenExpNotIso,
     TokenExpired,
+
+    // -- Wallet Signature (ADR-036)
+    SigInvalidPubKey,
+    SigInvalidFormat,
+    SigNotMatching,
 }
 
 // region:    --- Error Boilerplate
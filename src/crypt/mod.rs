@@ -1,5 +1,6 @@
 // region:    --- Modules
 
+pub mod adr036;
 mod error;
 pub mod pwd;
 pub mod token;
@@ -31,4 +31,9 @@ impl Ctx {
     pub fn user_id(&self) -> i64 {
         self.user_id
     }
+
+    /// If the ctx of the system tasks (e.g., scheduler, worker, seeds).
+    pub fn is_root(&self) -> bool {
+        self.user_id == 0
+    }
 }
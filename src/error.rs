@@ -75,6 +75,9 @@ pub enum Error {
     ConfigMissingEnv(&'static str),
     ConfigWrongFormat(&'static str),
 
+    // -- Command
+    CommandUnknown(String),
+
     // -- Modules
     Model(model::Error),
 }
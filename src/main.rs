@@ -8,8 +8,10 @@ mod ctx;
 mod error;
 mod log;
 mod model;
+mod scheduler;
 mod utils;
 mod web;
+mod worker;
 
 // #[cfg(test)] // Commented during early development.
 pub mod _dev_utils;
@@ -17,14 +19,21 @@ pub mod _dev_utils;
 pub use self::error::{Error, Result};
 pub use config::config;
 
+use crate::model::notification::Notifier;
 use crate::model::ModelManager;
+use crate::scheduler::{Scheduler, SystemClock};
 use crate::web::mw_auth::{mw_ctx_require, mw_ctx_resolve};
 use crate::web::mw_res_map::mw_reponse_map;
-use crate::web::{routes_login, routes_static, rpc};
+use crate::web::{routes_events, routes_login, routes_rest, routes_static, rpc};
+use crate::worker::handlers::{SearchReindexHandler, WebhookDeliverHandler};
+use crate::worker::webhook::WebhookDispatcher;
+use crate::worker::Worker;
 use axum::response::Html;
 use axum::routing::get;
 use axum::{middleware, Router};
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 use tower_cookies::CookieManagerLayer;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
@@ -39,18 +48,73 @@ async fn main() -> Result<()> {
         .with_env_filter(EnvFilter::from_default_env())
         .init();
 
+    // -- Commands
+    // e.g., `cargo run -- migrate`, `cargo run -- rollback`, `cargo run -- status`
+    //       `cargo run -- dev` (recreates and seeds the dev db)
+    //       `cargo run -- demo` (in-memory storage, no db needed)
+    match std::env::args().nth(1).as_deref() {
+        None | Some("serve") => serve().await,
+        Some("dev") => serve_dev().await,
+        Some("demo") => serve_demo().await,
+        Some("migrate") => cmd_migrate().await,
+        Some("rollback") => cmd_rollback().await,
+        Some("status") => cmd_status().await,
+        Some(cmd) => Err(Error::CommandUnknown(cmd.to_string())),
+    }
+}
+
+async fn serve() -> Result<()> {
+    // Initialize ModelManager.
+    // (the notifications and the webhook deliveries generated in the
+    //  transaction of each change)
+    let mm = ModelManager::new()
+        .await?
+        .with_event_handler(Notifier)
+        .with_event_handler(WebhookDispatcher);
+    mm.migrate().await?;
+    model::schema_check::check_schema(&mm).await?;
+
+    // (db only, the in-memory storage has no job queue)
+    let poll = Duration::from_secs_f64(config().WORKER_POLL_SEC);
+    Worker::new(mm.clone(), Arc::new(SystemClock))
+        .handler(SearchReindexHandler)
+        .handler(WebhookDeliverHandler)
+        .spawn(config().WORKER_COUNT, poll);
+
+    run_server(mm).await
+}
+
+async fn serve_dev() -> Result<()> {
     // -- FOR DEV ONLY
+    // (drops and recreates `app_db`, see `sql/dev_initial/`)
     _dev_utils::init_dev().await;
-    /* */
-    // Initialize ModelManager.
-    let mm = ModelManager::new().await?;
+
+    serve().await
+}
+
+async fn serve_demo() -> Result<()> {
+    // -- FOR DEV ONLY
+    let mm = _dev_utils::init_dev_memory()
+        .await
+        .with_event_handler(Notifier);
+
+    run_server(mm).await
+}
+
+async fn run_server(mm: ModelManager) -> Result<()> {
+    // -- Background Tasks
+    let interval = Duration::from_secs_f64(config().SCHEDULER_INTERVAL_SEC);
+    Scheduler::new(mm.clone(), Arc::new(SystemClock)).spawn(interval);
 
     // -- Define Routes
-    let routes_rpc = rpc::routes(mm.clone()).route_layer(middleware::from_fn(mw_ctx_require));
+    let routes_api = rpc::routes(mm.clone())
+        .merge(routes_rest::routes(mm.clone()))
+        .merge(routes_events::routes(mm.clone()))
+        .route_layer(middleware::from_fn(mw_ctx_require));
 
     let routes_all = Router::new()
         .merge(routes_login::routes(mm.clone()))
-        .nest("/api", routes_rpc)
+        .nest("/api", routes_api)
         .layer(middleware::map_response(mw_reponse_map))
         .layer(middleware::from_fn_with_state(mm.clone(), mw_ctx_resolve))
         .layer(CookieManagerLayer::new())
@@ -67,3 +131,33 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+// region:    --- Migration Commands
+async fn cmd_migrate() -> Result<()> {
+    let mm = ModelManager::new().await?;
+    let versions = mm.migrate().await?;
+    info!("{:<12} - applied: {versions:?}", "MIGRATE");
+
+    Ok(())
+}
+
+async fn cmd_rollback() -> Result<()> {
+    let mm = ModelManager::new().await?;
+    let version = mm.rollback().await?;
+    info!("{:<12} - rolled back: {version:?}", "ROLLBACK");
+
+    Ok(())
+}
+
+async fn cmd_status() -> Result<()> {
+    let mm = ModelManager::new().await?;
+    for status in mm.migration_status().await? {
+        println!(
+            "{:04}_{:<32} {:?}",
+            status.version, status.name, status.state
+        );
+    }
+
+    Ok(())
+}
+// endregion: --- Migration Commands
@@ -0,0 +1,292 @@
+//! Bech32 account addresses (e.g., `cosmos1...`), of the project leads,
+//! bounty assignees and user wallets.
+//!
+//! Design:
+//!
+//! - An address is valid if its bech32 checksum is, and its human readable
+//!   part (e.g., `cosmos`) is in the configured allow-list
+//!   (`SERVICE_ADDRESS_HRPS`, the chains the projects pay on).
+//! - Addresses are stored lowercase (bech32 being case insensitive, but
+//!   never mixed case), see `normalize`.
+//! - The `validate_*` functions are the `validator` custom validations of
+//!   the `*ForCreate` / `*ForUpdate` types, so a malformed address is a
+//!   field validation error, before any db access.
+//!
+
+use crate::config::config;
+use openssl::hash::{hash, MessageDigest};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::fmt;
+use validator::ValidationError;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATORS: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+const MAX_LEN: usize = 90;
+const CHECKSUM_LEN: usize = 6;
+
+// region:    --- Address Error
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressError {
+    Length,
+    MixedCase,
+    NoSeparator,
+    InvalidChar(char),
+    InvalidChecksum,
+    InvalidPayload,
+    HrpNotAllowed(String),
+    InvalidPubKey,
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Length => write!(
+                f,
+                "must be a bech32 address of at most {MAX_LEN} characters"
+            ),
+            Self::MixedCase => write!(f, "must not mix upper and lower case"),
+            Self::NoSeparator => write!(f, "must be a bech32 address (e.g., cosmos1...)"),
+            Self::InvalidChar(c) => write!(f, "invalid bech32 character '{c}'"),
+            Self::InvalidChecksum => write!(f, "invalid bech32 checksum"),
+            Self::InvalidPayload => write!(f, "invalid bech32 payload"),
+            Self::HrpNotAllowed(hrp) => write!(f, "address prefix '{hrp}' is not allowed"),
+            Self::InvalidPubKey => write!(f, "invalid secp256k1 public key"),
+        }
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+// endregion: --- Address Error
+
+/// Check the bech32 address, with its human readable part in `hrps`,
+/// returning it normalized (lowercase).
+pub fn parse(value: &str, hrps: &[String]) -> Result<String, AddressError> {
+    if value.len() > MAX_LEN {
+        return Err(AddressError::Length);
+    }
+    if let Some(c) = value.chars().find(|c| !(c.is_ascii_graphic())) {
+        return Err(AddressError::InvalidChar(c));
+    }
+    let has_lower = value.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = value.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper {
+        return Err(AddressError::MixedCase);
+    }
+    let address = value.to_ascii_lowercase();
+
+    let (hrp, data) = address.rsplit_once('1').ok_or(AddressError::NoSeparator)?;
+    if hrp.is_empty() || data.len() <= CHECKSUM_LEN {
+        return Err(AddressError::NoSeparator);
+    }
+
+    let data = data
+        .chars()
+        .map(|c| {
+            CHARSET
+                .iter()
+                .position(|x| *x as char == c)
+                .map(|p| p as u8)
+                .ok_or(AddressError::InvalidChar(c))
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+    if polymod(hrp, &data) != 1 {
+        return Err(AddressError::InvalidChecksum);
+    }
+    check_payload(&data[..data.len() - CHECKSUM_LEN])?;
+
+    if !hrps.iter().any(|h| h == hrp) {
+        return Err(AddressError::HrpNotAllowed(hrp.to_string()));
+    }
+
+    Ok(address)
+}
+
+/// The address of the secp256k1 compressed `pub_key` on the `hrp` chain,
+/// i.e., the bech32 of `ripemd160(sha256(pub_key))`.
+pub fn from_pub_key(hrp: &str, pub_key: &[u8]) -> Result<String, AddressError> {
+    if pub_key.len() != 33 {
+        return Err(AddressError::InvalidPubKey);
+    }
+    let account = hash(MessageDigest::ripemd160(), &Sha256::digest(pub_key))
+        .map_err(|_| AddressError::InvalidPubKey)?;
+
+    Ok(encode(hrp, &account))
+}
+
+/// The human readable part of an (already validated) address.
+pub fn hrp(address: &str) -> &str {
+    address.rsplit_once('1').map_or("", |(hrp, _)| hrp)
+}
+
+/// The stored form of an address (already validated).
+pub fn normalize(address: &str) -> String {
+    address.trim().to_ascii_lowercase()
+}
+
+/// `validator` custom validation of an address, with the configured HRPs.
+pub fn validate_address(value: &str) -> Result<(), ValidationError> {
+    parse(value, &config().ADDRESS_HRPS)
+        .map(|_| ())
+        .map_err(|ex| {
+            let mut err = ValidationError::new("address");
+            err.message = Some(Cow::Owned(ex.to_string()));
+            err
+        })
+}
+
+/// As `validate_address`, an empty value meaning no address
+/// (e.g., a bounty not assigned yet).
+pub fn validate_optional_address(value: &str) -> Result<(), ValidationError> {
+    if value.is_empty() {
+        return Ok(());
+    }
+    validate_address(value)
+}
+
+// region:    --- Bech32
+
+fn polymod(hrp: &str, data: &[u8]) -> u32 {
+    let hrp = hrp.bytes();
+    let values = hrp
+        .clone()
+        .map(|b| b >> 5)
+        .chain([0])
+        .chain(hrp.map(|b| b & 31))
+        .chain(data.iter().copied());
+
+    values.fold(1u32, |chk, v| {
+        let top = chk >> 25;
+        let chk = ((chk & 0x1ff_ffff) << 5) ^ v as u32;
+        GENERATORS
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(chk, |chk, (_, g)| chk ^ g)
+    })
+}
+
+/// The bech32 of the `bytes`, with its checksum.
+fn encode(hrp: &str, bytes: &[u8]) -> String {
+    // -- The 8-bit bytes as 5-bit words (zero padded).
+    let mut words = Vec::with_capacity(bytes.len() * 8 / 5 + 1);
+    let (mut acc, mut bits) = (0u32, 0);
+    for b in bytes {
+        acc = (acc << 8) | *b as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            words.push(((acc >> bits) & 31) as u8);
+        }
+    }
+    if bits > 0 {
+        words.push(((acc << (5 - bits)) & 31) as u8);
+    }
+
+    // -- The checksum, making the polymod of the whole data 1.
+    let with_zeros: Vec<u8> = words.iter().copied().chain([0; CHECKSUM_LEN]).collect();
+    let chk = polymod(hrp, &with_zeros) ^ 1;
+    words.extend((0..CHECKSUM_LEN).map(|i| ((chk >> (5 * (5 - i))) & 31) as u8));
+
+    let data: String = words.iter().map(|w| CHARSET[*w as usize] as char).collect();
+    format!("{hrp}1{data}")
+}
+
+/// The 5-bit words must be whole bytes (at most 4 zero padding bits).
+fn check_payload(words: &[u8]) -> Result<(), AddressError> {
+    let bits = words.len() * 5;
+    let padding = bits % 8;
+    let last_pad_zero = words
+        .last()
+        .map(|w| w & ((1 << padding) - 1) == 0)
+        .unwrap_or(false);
+
+    if bits < 8 || padding > 4 || !last_pad_zero {
+        return Err(AddressError::InvalidPayload);
+    }
+
+    Ok(())
+}
+
+// endregion: --- Bech32
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    fn hrps() -> Vec<String> {
+        vec!["cosmos".to_string(), "osmo".to_string()]
+    }
+
+    #[test]
+    fn test_parse_ok() -> Result<()> {
+        let fx_address = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu";
+
+        assert_eq!(parse(fx_address, &hrps())?, fx_address);
+        assert_eq!(
+            parse(&fx_address.to_ascii_uppercase(), &hrps())?,
+            fx_address,
+            "normalized to lowercase"
+        );
+        assert!(parse("osmo190vqdjtlpcq27xslcveglfmr4ynfwg7ggqt8xp", &hrps()).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_pub_key_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let fx_pub_key = base64_url::decode("A08EGB7ro1ORuFhjOnZcSgwYlpe0DSFjVNUIkNNQxwKQ")?;
+
+        // -- Exec
+        let address = from_pub_key("cosmos", &fx_pub_key)?;
+
+        // -- Check
+        assert_eq!(address, "cosmos1pkptre7fdkl6gfrzlesjjvhxhlc3r4gmmk8rs6");
+        assert_eq!(parse(&address, &hrps())?, address);
+        assert_eq!(hrp(&address), "cosmos");
+        assert_eq!(
+            from_pub_key("cosmos", &fx_pub_key[1..]),
+            Err(AddressError::InvalidPubKey)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_err() -> Result<()> {
+        let hrps = hrps();
+
+        assert_eq!(
+            parse("cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xv", &hrps),
+            Err(AddressError::InvalidChecksum)
+        );
+        assert_eq!(
+            parse("cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7Xu", &hrps),
+            Err(AddressError::MixedCase)
+        );
+        assert_eq!(
+            parse("cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7bu", &hrps),
+            Err(AddressError::InvalidChar('b'))
+        );
+        assert_eq!(parse("alice", &hrps), Err(AddressError::NoSeparator));
+        // (valid bech32, of another chain)
+        assert_eq!(
+            parse("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw", &hrps),
+            Err(AddressError::HrpNotAllowed("abcdef".to_string()))
+        );
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
@@ -0,0 +1,551 @@
+//! Bounty applications, contributors proposing to work on an open bounty.
+//!
+//! Design:
+//!
+//! - A contributor (the `Ctx` user) applies once per bounty, with a message,
+//!   an estimate (in days), and the address to be paid to.
+//! - The project lead (see `ProjectBmc::check_lead`) accepts one pending
+//!   application, which assigns the bounty to its address (bounty `Open` ->
+//!   `Assigned`), and rejects the other pending ones.
+//! - An application may have a delivery deadline. Once accepted, missing it
+//!   (the bounty still assigned, not submitted) lapses the application and
+//!   un-assigns the bounty (see `scheduler`).
+//! - Each decision notifies the applicant in its transaction (see
+//!   `notification::notify_decision`), and publishes a `Transitioned` event
+//!   of the application (e.g., `/api/events`).
+//!
+
+use crate::ctx::Ctx;
+use crate::model::address::{self, validate_address};
+use crate::model::base::{self, DbBmc, ListOptions};
+use crate::model::bounty::{BountyBmc, BountyStatus};
+use crate::model::event::{EventKind, ModelEvent};
+use crate::model::notification;
+use crate::model::store::memory::Row;
+use crate::model::{Error, ModelManager, Result};
+use serde::{Deserialize, Serialize};
+use sqlb::Fields;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use validator::Validate;
+
+// region:    --- Application Types
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct Application {
+    pub id: i64,
+    pub application_bounty_id: i64,
+    pub application_applicant_id: i64,
+    pub application_message: String,
+    pub application_estimated_days: i32,
+    pub application_address: String,
+    pub application_status: ApplicationStatus,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub application_deadline: Option<OffsetDateTime>,
+}
+
+/// The `application_status` db enum.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type, strum_macros::AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "application_status", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ApplicationStatus {
+    Pending,
+    Accepted,
+    Rejected,
+    /// Accepted, its deadline missed.
+    Lapsed,
+}
+sqlb::bindable!(ApplicationStatus);
+
+#[derive(Deserialize, Serialize, Fields, Validate)]
+pub struct ApplicationForCreate {
+    pub application_bounty_id: i64,
+    #[validate(length(min = 1, max = 2048))]
+    pub application_message: String,
+    #[validate(range(min = 1, max = 365))]
+    pub application_estimated_days: i32,
+    #[validate(length(min = 1, max = 256), custom = "validate_address")]
+    pub application_address: String,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub application_deadline: Option<OffsetDateTime>,
+}
+
+/// `ApplicationForCreate` with the applicant (the `Ctx` user).
+#[derive(Serialize, Fields)]
+pub struct ApplicationForInsert {
+    pub application_bounty_id: i64,
+    pub application_applicant_id: i64,
+    pub application_message: String,
+    pub application_estimated_days: i32,
+    pub application_address: String,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub application_deadline: Option<OffsetDateTime>,
+}
+
+#[derive(Serialize, Fields)]
+pub struct ApplicationForDecide {
+    pub application_status: ApplicationStatus,
+}
+
+#[derive(Default, Deserialize, Serialize, Fields)]
+pub struct ApplicationFilter {
+    pub application_bounty_id: Option<i64>,
+    pub application_applicant_id: Option<i64>,
+    pub application_status: Option<ApplicationStatus>,
+}
+
+// endregion: --- Application Types
+
+pub struct ApplicationBmc;
+
+impl DbBmc for ApplicationBmc {
+    const TABLE: &'static str = "bounty_application";
+
+    fn column_defaults() -> Row {
+        Row::from_iter([("application_status".to_string(), "pending".into())])
+    }
+}
+
+impl ApplicationBmc {
+    /// Apply to the open bounty, as the `Ctx` user.
+    pub async fn create(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        application_c: ApplicationForCreate,
+    ) -> Result<i64> {
+        let ApplicationForCreate {
+            application_bounty_id,
+            application_message,
+            application_estimated_days,
+            application_address,
+            application_deadline,
+        } = application_c;
+
+        let application_i = ApplicationForInsert {
+            application_bounty_id,
+            application_applicant_id: ctx.user_id(),
+            application_message,
+            application_estimated_days,
+            application_address: address::normalize(&application_address),
+            application_deadline,
+        };
+
+        mm.transaction(|mm| async move {
+            // (locked, not to apply to a bounty being assigned or cancelled)
+            base::lock::<BountyBmc>(ctx, &mm, application_bounty_id).await?;
+            let bounty = BountyBmc::get(ctx, &mm, application_bounty_id).await?;
+            if bounty.bounty_status != BountyStatus::Open {
+                return Err(Error::BountyNotOpen {
+                    id: bounty.id,
+                    status: bounty.bounty_status.as_ref().to_string(),
+                });
+            }
+
+            base::create::<Self, _>(ctx, &mm, application_i).await
+        })
+        .await
+    }
+
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Application> {
+        base::get::<Self, _>(ctx, mm, id).await
+    }
+
+    pub async fn list(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        filter: Option<ApplicationFilter>,
+        list_options: Option<ListOptions>,
+    ) -> Result<Vec<Application>> {
+        base::list::<Self, _, _>(ctx, mm, filter, list_options).await
+    }
+
+    /// Accept the pending application, assigning its bounty to the applicant
+    /// address, and reject the other pending applications of the bounty.
+    /// (by the project lead only)
+    pub async fn accept(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        mm.transaction(|mm| async move {
+            let application = Self::get_for_lead(ctx, &mm, id).await?;
+            Self::decide(ctx, &mm, &application, ApplicationStatus::Accepted).await?;
+
+            BountyBmc::assign(
+                ctx,
+                &mm,
+                application.application_bounty_id,
+                &application.application_address,
+            )
+            .await?;
+
+            let filter = ApplicationFilter {
+                application_bounty_id: Some(application.application_bounty_id),
+                application_status: Some(ApplicationStatus::Pending),
+                ..Default::default()
+            };
+            for other in Self::list(ctx, &mm, Some(filter), None).await? {
+                Self::decide(ctx, &mm, &other, ApplicationStatus::Rejected).await?;
+            }
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// (by the project lead only)
+    pub async fn reject(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        mm.transaction(|mm| async move {
+            let application = Self::get_for_lead(ctx, &mm, id).await?;
+            Self::decide(ctx, &mm, &application, ApplicationStatus::Rejected).await
+        })
+        .await
+    }
+
+    /// The accepted applications with their deadline before `now`, and
+    /// their bounty still assigned to the applicant (to be lapsed, see
+    /// `scheduler`).
+    pub async fn list_lapsed(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        now: OffsetDateTime,
+    ) -> Result<Vec<Application>> {
+        let filter = ApplicationFilter {
+            application_status: Some(ApplicationStatus::Accepted),
+            ..Default::default()
+        };
+        let overdue: Vec<Application> = base::list_before::<Self, _, _>(
+            ctx,
+            mm,
+            Some(filter),
+            "application_deadline",
+            now,
+            None,
+        )
+        .await?;
+
+        let mut lapsed = Vec::new();
+        for application in overdue {
+            let bounty = BountyBmc::get(ctx, mm, application.application_bounty_id).await?;
+            // (otherwise delivered in time, or no longer assigned)
+            if bounty.bounty_status == BountyStatus::Assigned
+                && bounty.bounty_assignee == application.application_address
+            {
+                lapsed.push(application);
+            }
+        }
+
+        Ok(lapsed)
+    }
+
+    /// Lapse the accepted application, un-assigning its bounty.
+    pub async fn lapse(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        mm.transaction(|mm| async move {
+            let application = Self::get(ctx, &mm, id).await?;
+            Self::set_status(
+                ctx,
+                &mm,
+                &application,
+                ApplicationStatus::Accepted,
+                ApplicationStatus::Lapsed,
+            )
+            .await?;
+
+            BountyBmc::unassign(ctx, &mm, application.application_bounty_id).await
+        })
+        .await
+    }
+
+    /// The application, if the `Ctx` user is the lead of its bounty project
+    /// (see `BountyBmc::check_lead`).
+    async fn get_for_lead(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Application> {
+        let application = Self::get(ctx, mm, id).await?;
+        let bounty = BountyBmc::get(ctx, mm, application.application_bounty_id).await?;
+        BountyBmc::check_lead(ctx, mm, &bounty).await?;
+
+        Ok(application)
+    }
+
+    /// Set the decision of the pending application.
+    async fn decide(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        application: &Application,
+        to: ApplicationStatus,
+    ) -> Result<()> {
+        Self::set_status(ctx, mm, application, ApplicationStatus::Pending, to).await
+    }
+
+    /// Move the application from the `expected` status to `to`.
+    async fn set_status(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        application: &Application,
+        expected: ApplicationStatus,
+        to: ApplicationStatus,
+    ) -> Result<()> {
+        let from = application.application_status;
+        if from != expected {
+            return Err(Error::InvalidTransition {
+                entity: Self::TABLE,
+                id: application.id,
+                from: from.as_ref().to_string(),
+                to: to.as_ref().to_string(),
+            });
+        }
+
+        let application_d = ApplicationForDecide {
+            application_status: to,
+        };
+        mm.in_txn(|mm| async move {
+            base::update::<Self, _>(ctx, &mm, application.id, None, application_d).await?;
+            notification::notify_decision(ctx, &mm, application, to).await?;
+            mm.publish(ModelEvent::new(
+                Self::TABLE,
+                application.id,
+                EventKind::Transitioned {
+                    from: from.as_ref().to_string(),
+                    to: to.as_ref().to_string(),
+                },
+            ));
+
+            Ok(())
+        })
+        .await
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::{Seed, Seeded};
+    use crate::model::ledger::{LedgerBmc, ProjectFunding};
+    use crate::model::notification::NotificationBmc;
+    use anyhow::{Context, Result};
+
+    const SEED_TOML: &str = r#"
+[[users]]
+username = "lead"
+pwd_clear = "lead pwd 01"
+
+[[users]]
+username = "alice"
+pwd_clear = "alice pwd 01"
+
+[[users]]
+username = "bob"
+pwd_clear = "bob pwd 01"
+
+[[wallets]]
+username = "lead"
+key = "lead"
+
+# (lead, the address of the `lead` dev wallet)
+[[projects]]
+key = "p1"
+project_id = "application-project-01"
+project_name = "Application Project 01"
+project_lead_address = "cosmos1gf3c5mgfe6zd0qnmuwy7c5zf6yj5t5ajyn7sth"
+project_budget = "1000"
+project_denom = "uatom"
+project_type = "dev"
+project_description = "A project with a lead"
+
+[[bounties]]
+key = "b1"
+project = "p1"
+bounty_title = "Application bounty 01"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "A PR"
+bounty_description = "A bounty to apply to"
+"#;
+
+    /// The seeded entities, with the project funded for the bounty escrow.
+    async fn seed(mm: &ModelManager) -> Result<Seeded> {
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, mm).await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let funding = ProjectFunding {
+            amount: 1000,
+            memo: "funding".to_string(),
+        };
+        LedgerBmc::fund_project(&ctx, mm, project_id, funding).await?;
+
+        Ok(seeded)
+    }
+
+    fn user_ctx(seeded: &Seeded, username: &str) -> Result<Ctx> {
+        Ok(Ctx::new(*seeded.users.get(username).context("user")?)?)
+    }
+
+    fn application_c(bounty_id: i64, address: &str) -> ApplicationForCreate {
+        ApplicationForCreate {
+            application_bounty_id: bounty_id,
+            application_message: "I can do it".to_string(),
+            application_estimated_days: 3,
+            application_address: address.to_string(),
+            application_deadline: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accept_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let seeded = seed(&mm).await?;
+        let bounty_id = *seeded.bounties.get("b1").context("b1")?;
+        let lead_ctx = user_ctx(&seeded, "lead")?;
+        let alice_ctx = user_ctx(&seeded, "alice")?;
+        let bob_ctx = user_ctx(&seeded, "bob")?;
+        let alice_app_id = ApplicationBmc::create(
+            &alice_ctx,
+            &mm,
+            application_c(bounty_id, "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn"),
+        )
+        .await?;
+        let bob_app_id = ApplicationBmc::create(
+            &bob_ctx,
+            &mm,
+            application_c(bounty_id, "cosmos1sxmr0k8u6trd5c6eu6trzyapzux7090y3u5dan"),
+        )
+        .await?;
+        let mut rx = mm.events().subscribe();
+
+        // -- Exec
+        ApplicationBmc::accept(&lead_ctx, &mm, alice_app_id).await?;
+
+        // -- Check
+        let bounty = BountyBmc::get(&Ctx::root_ctx(), &mm, bounty_id).await?;
+        assert_eq!(bounty.bounty_status, BountyStatus::Assigned);
+        assert_eq!(
+            bounty.bounty_assignee,
+            "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn"
+        );
+
+        let bob_app = ApplicationBmc::get(&bob_ctx, &mm, bob_app_id).await?;
+        assert_eq!(bob_app.application_status, ApplicationStatus::Rejected);
+
+        let mut transitions = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let EventKind::Transitioned { to, .. } = event.kind {
+                transitions.push((event.entity, event.id, to));
+            }
+        }
+        assert_eq!(
+            transitions,
+            [
+                (ApplicationBmc::TABLE, alice_app_id, "accepted".to_string()),
+                (BountyBmc::TABLE, bounty_id, "assigned".to_string()),
+                (ApplicationBmc::TABLE, bob_app_id, "rejected".to_string()),
+            ]
+        );
+
+        // (both applicants notified with the decisions)
+        for (ctx, decision) in [(&alice_ctx, "accepted"), (&bob_ctx, "rejected")] {
+            let notifications = NotificationBmc::list(ctx, &mm, None, None).await?;
+            let messages: Vec<_> = notifications
+                .iter()
+                .map(|n| n.notification_message.as_str())
+                .collect();
+            assert_eq!(
+                messages,
+                [format!(
+                    "Your application to \"Application bounty 01\" was {decision}"
+                )]
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_apply_decide_err() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = seed(&mm).await?;
+        let bounty_id = *seeded.bounties.get("b1").context("b1")?;
+        let alice_ctx = user_ctx(&seeded, "alice")?;
+        let bob_ctx = user_ctx(&seeded, "bob")?;
+        let alice_app_id = ApplicationBmc::create(
+            &alice_ctx,
+            &mm,
+            application_c(bounty_id, "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn"),
+        )
+        .await?;
+
+        // -- Exec & Check - one application per contributor
+        let res = ApplicationBmc::create(
+            &alice_ctx,
+            &mm,
+            application_c(bounty_id, "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn"),
+        )
+        .await;
+        assert!(
+            matches!(res, Err(Error::UniqueViolation { .. })),
+            "UniqueViolation not matching"
+        );
+
+        // -- Exec & Check - rejected, cannot be accepted
+        ApplicationBmc::reject(&ctx, &mm, alice_app_id).await?;
+        let res = ApplicationBmc::accept(&ctx, &mm, alice_app_id).await;
+        assert!(
+            matches!(&res, Err(Error::InvalidTransition { from, .. }) if from == "rejected"),
+            "InvalidTransition not matching"
+        );
+
+        // -- Exec & Check - bounty no longer open
+        BountyBmc::transition(&ctx, &mm, bounty_id, BountyStatus::Cancelled).await?;
+        let res = ApplicationBmc::create(
+            &bob_ctx,
+            &mm,
+            application_c(bounty_id, "cosmos1sxmr0k8u6trd5c6eu6trzyapzux7090y3u5dan"),
+        )
+        .await;
+        assert!(
+            matches!(&res, Err(Error::BountyNotOpen { status, .. }) if status == "cancelled"),
+            "BountyNotOpen not matching"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_decide_err_not_lead() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let seeded = seed(&mm).await?;
+        let bounty_id = *seeded.bounties.get("b1").context("b1")?;
+        let alice_ctx = user_ctx(&seeded, "alice")?;
+        let bob_ctx = user_ctx(&seeded, "bob")?;
+        let alice_app_id = ApplicationBmc::create(
+            &alice_ctx,
+            &mm,
+            application_c(bounty_id, "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn"),
+        )
+        .await?;
+
+        // -- Exec & Check - neither the applicant, nor another user
+        for ctx in [&alice_ctx, &bob_ctx] {
+            let res = ApplicationBmc::accept(ctx, &mm, alice_app_id).await;
+            assert!(
+                matches!(res, Err(Error::NotProjectLead { user_id, .. }) if user_id == ctx.user_id()),
+                "NotProjectLead not matching"
+            );
+            let res = ApplicationBmc::reject(ctx, &mm, alice_app_id).await;
+            assert!(
+                matches!(res, Err(Error::NotProjectLead { .. })),
+                "NotProjectLead not matching"
+            );
+        }
+
+        // -- Check - undecided
+        let application = ApplicationBmc::get(&alice_ctx, &mm, alice_app_id).await?;
+        assert_eq!(application.application_status, ApplicationStatus::Pending);
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
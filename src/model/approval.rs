@@ -0,0 +1,411 @@
+//! Approval policies of the project submissions, and the reviewer votes.
+//!
+//! Design:
+//!
+//! - A project can have an `ApprovalPolicy` (the `project_approval_policy`
+//!   jsonb column), e.g., "2 of 3 maintainers" or "lead + one reviewer".
+//!   The project lead is always a reviewer, and the only one without
+//!   policy (a single approval by the lead, nobody for a bounty without
+//!   project).
+//! - The submitter never reviews its own submission.
+//! - Each approve review of a `model::submission` records a vote, and the
+//!   submission (and its bounty) is only approved once the votes satisfy
+//!   the policy.
+//! - Only the project lead sets the policy of its project.
+//! - Policies are db only (the in-memory storage has the default one).
+//!
+
+use crate::ctx::Ctx;
+use crate::model::base::{self, DbBmc};
+use crate::model::bounty::BountyBmc;
+use crate::model::event::{EventKind, ModelEvent};
+use crate::model::project::ProjectBmc;
+use crate::model::store::Storage;
+use crate::model::{Error, ModelManager, Result};
+use serde::{Deserialize, Serialize};
+use sqlb::Fields;
+use sqlx::types::Json;
+use sqlx::FromRow;
+use validator::{Validate, ValidationError};
+
+// region:    --- Approval Types
+
+/// The votes needed to approve a submission.
+///
+/// e.g.,
+/// - "2 of 3 maintainers": `required: 2, reviewers: [m1, m2, m3]`
+/// - "lead + one reviewer": `required: 2, reviewers: [r1, r2], required_reviewers: [lead]`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Validate)]
+#[validate(schema(function = "validate_policy"))]
+pub struct ApprovalPolicy {
+    /// Number of distinct reviewer approvals.
+    #[validate(range(min = 1, max = 32))]
+    pub required: u32,
+    /// User ids of the reviewers, besides the project lead.
+    #[serde(default)]
+    pub reviewers: Vec<i64>,
+    /// User ids of the reviewers whose approval is mandatory
+    /// (counted in `required`, and allowed to review).
+    #[serde(default)]
+    pub required_reviewers: Vec<i64>,
+}
+
+impl Default for ApprovalPolicy {
+    /// A single approval, by the project lead (see `with_lead`).
+    fn default() -> Self {
+        ApprovalPolicy {
+            required: 1,
+            reviewers: Vec::new(),
+            required_reviewers: Vec::new(),
+        }
+    }
+}
+
+impl ApprovalPolicy {
+    /// The policy with the project lead user (if any) as a reviewer.
+    pub fn with_lead(mut self, lead_id: Option<i64>) -> Self {
+        if let Some(lead_id) = lead_id {
+            if !self.can_review(lead_id) {
+                self.reviewers.push(lead_id);
+            }
+        }
+        self
+    }
+
+    pub fn can_review(&self, user_id: i64) -> bool {
+        self.reviewers.contains(&user_id) || self.required_reviewers.contains(&user_id)
+    }
+
+    /// If the approvals of `approver_ids` satisfy the policy
+    /// (approvals of non reviewers and duplicates not counting).
+    pub fn is_satisfied(&self, approver_ids: &[i64]) -> bool {
+        let mut approvers: Vec<i64> = approver_ids
+            .iter()
+            .copied()
+            .filter(|id| self.can_review(*id))
+            .collect();
+        approvers.sort_unstable();
+        approvers.dedup();
+
+        approvers.len() >= self.required as usize
+            && self
+                .required_reviewers
+                .iter()
+                .all(|id| approvers.contains(id))
+    }
+}
+
+/// The policy must be satisfiable (by the lead alone, without reviewers).
+fn validate_policy(policy: &ApprovalPolicy) -> core::result::Result<(), ValidationError> {
+    let required = policy.required as usize;
+    if policy.required_reviewers.len() > required {
+        return Err(ValidationError::new("required_reviewers_over_required"));
+    }
+
+    let mut reviewers: Vec<i64> = policy
+        .reviewers
+        .iter()
+        .chain(&policy.required_reviewers)
+        .copied()
+        .collect();
+    reviewers.sort_unstable();
+    reviewers.dedup();
+    if reviewers.len().max(1) < required {
+        return Err(ValidationError::new("required_over_reviewers"));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct ApprovalVote {
+    pub id: i64,
+    pub vote_submission_id: i64,
+    pub vote_reviewer_id: i64,
+}
+
+#[derive(Serialize, Fields)]
+pub struct ApprovalVoteForInsert {
+    pub vote_submission_id: i64,
+    pub vote_reviewer_id: i64,
+}
+
+#[derive(Default, Serialize, Fields)]
+pub struct ApprovalVoteFilter {
+    pub vote_submission_id: Option<i64>,
+}
+
+// endregion: --- Approval Types
+
+pub struct ApprovalBmc;
+
+impl DbBmc for ApprovalBmc {
+    const TABLE: &'static str = "approval_vote";
+}
+
+impl ApprovalBmc {
+    /// The policy of the project (the default one if not set).
+    pub async fn get_policy(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        project_id: i64,
+    ) -> Result<ApprovalPolicy> {
+        // (fails with not found for an unknown project)
+        ProjectBmc::get(ctx, mm, project_id).await?;
+
+        let Storage::Db(dbx) = mm.storage() else {
+            return Ok(ApprovalPolicy::default());
+        };
+        let (policy,): (Option<Json<ApprovalPolicy>>,) = dbx
+            .query_fetch_one(
+                sqlx::query_as("SELECT project_approval_policy FROM project WHERE id = $1")
+                    .bind(project_id),
+            )
+            .await?;
+
+        Ok(policy.map(|p| p.0).unwrap_or_default())
+    }
+
+    /// Set the policy of the project (`None` for the default one),
+    /// as its lead (see `ProjectBmc::check_lead`).
+    pub async fn set_policy(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        project_id: i64,
+        policy: Option<ApprovalPolicy>,
+    ) -> Result<()> {
+        ProjectBmc::check_lead(ctx, mm, project_id).await?;
+
+        mm.in_txn(|mm| async move {
+            let count = mm
+                .dbx()?
+                .query_exec(
+                    sqlx::query(
+                        "UPDATE project SET project_approval_policy = $2, version = version + 1 \
+                     WHERE id = $1",
+                    )
+                    .bind(project_id)
+                    .bind(policy.map(Json)),
+                )
+                .await?;
+
+            if count == 0 {
+                return Err(Error::EntityNotFound {
+                    entity: ProjectBmc::TABLE,
+                    id: project_id,
+                });
+            }
+            mm.publish(ModelEvent::new(
+                ProjectBmc::TABLE,
+                project_id,
+                EventKind::Updated,
+            ));
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// The policy of the bounty project, with its lead as a reviewer
+    /// (the default one without project, i.e., no reviewer).
+    pub async fn policy_for_bounty(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        bounty_id: i64,
+    ) -> Result<ApprovalPolicy> {
+        let bounty = BountyBmc::get(ctx, mm, bounty_id).await?;
+
+        match bounty.bounty_project_id {
+            Some(project_id) => {
+                let project = ProjectBmc::get(ctx, mm, project_id).await?;
+                let lead_id = ProjectBmc::lead_user_id(ctx, mm, &project).await?;
+                let policy = Self::get_policy(ctx, mm, project_id).await?;
+                Ok(policy.with_lead(lead_id))
+            }
+            None => Ok(ApprovalPolicy::default()),
+        }
+    }
+
+    /// Record the approval vote of the `Ctx` user.
+    pub async fn vote(ctx: &Ctx, mm: &ModelManager, submission_id: i64) -> Result<i64> {
+        let vote_i = ApprovalVoteForInsert {
+            vote_submission_id: submission_id,
+            vote_reviewer_id: ctx.user_id(),
+        };
+        base::create::<Self, _>(ctx, mm, vote_i).await
+    }
+
+    pub async fn list_votes(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        submission_id: i64,
+    ) -> Result<Vec<ApprovalVote>> {
+        let filter = ApprovalVoteFilter {
+            vote_submission_id: Some(submission_id),
+        };
+        base::list::<Self, _, _>(ctx, mm, Some(filter), None).await
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::{Seed, Seeded};
+    use anyhow::{Context, Result};
+
+    const LEAD: i64 = 1000;
+    const R1: i64 = 1001;
+    const R2: i64 = 1002;
+    const OTHER: i64 = 1099;
+
+    const SEED_TOML: &str = r#"
+[[users]]
+username = "lead"
+pwd_clear = "lead pwd 01"
+
+[[users]]
+username = "alice"
+pwd_clear = "alice pwd 01"
+
+[[wallets]]
+username = "lead"
+key = "lead"
+
+# (lead, the address of the `lead` dev wallet)
+[[projects]]
+key = "p1"
+project_id = "approval-project-01"
+project_name = "Approval Project 01"
+project_lead_address = "cosmos1gf3c5mgfe6zd0qnmuwy7c5zf6yj5t5ajyn7sth"
+project_budget = "1000"
+project_denom = "uatom"
+project_type = "dev"
+project_description = "A project with a policy"
+"#;
+
+    async fn seed(mm: &ModelManager) -> Result<Seeded> {
+        let ctx = Ctx::root_ctx();
+        Ok(toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, mm).await?)
+    }
+
+    fn policy(required: u32, reviewers: &[i64], required_reviewers: &[i64]) -> ApprovalPolicy {
+        ApprovalPolicy {
+            required,
+            reviewers: reviewers.to_vec(),
+            required_reviewers: required_reviewers.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_policy_default() -> Result<()> {
+        let policy = ApprovalPolicy::default();
+        assert!(!policy.can_review(OTHER), "no reviewer without lead");
+
+        let policy = policy.with_lead(Some(LEAD));
+        assert!(policy.can_review(LEAD));
+        assert!(!policy.can_review(OTHER));
+        assert!(!policy.is_satisfied(&[]));
+        assert!(!policy.is_satisfied(&[OTHER]));
+        assert!(policy.is_satisfied(&[LEAD]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_policy_n_of_m() -> Result<()> {
+        // "2 of 3 maintainers"
+        let policy = policy(2, &[LEAD, R1, R2], &[]);
+
+        assert!(!policy.can_review(OTHER));
+        assert!(!policy.is_satisfied(&[R1]));
+        assert!(!policy.is_satisfied(&[R1, R1]), "duplicates do not count");
+        assert!(
+            !policy.is_satisfied(&[R1, OTHER]),
+            "non reviewers do not count"
+        );
+        assert!(policy.is_satisfied(&[R1, R2]));
+        assert!(policy.is_satisfied(&[LEAD, R1, R2]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_policy_required_reviewer() -> Result<()> {
+        // "lead + one reviewer"
+        let policy = policy(2, &[R1, R2], &[LEAD]).with_lead(Some(LEAD));
+        assert_eq!(policy.reviewers, [R1, R2], "lead already a reviewer");
+
+        assert!(policy.can_review(LEAD));
+        assert!(!policy.is_satisfied(&[R1, R2]), "lead is required");
+        assert!(!policy.is_satisfied(&[LEAD]));
+        assert!(policy.is_satisfied(&[R2, LEAD]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_policy_validate() -> Result<()> {
+        assert!(policy(2, &[LEAD, R1, R2], &[]).validate().is_ok());
+        assert!(policy(2, &[R1], &[LEAD]).validate().is_ok());
+        assert!(policy(1, &[], &[]).validate().is_ok(), "the lead alone");
+        assert!(policy(2, &[], &[]).validate().is_err());
+        assert!(policy(0, &[], &[]).validate().is_err());
+        assert!(policy(3, &[R1], &[LEAD]).validate().is_err());
+        assert!(policy(1, &[R1], &[LEAD, R2]).validate().is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_policy_ok_lead() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let seeded = seed(&mm).await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let lead_ctx = Ctx::new(*seeded.users.get("lead").context("lead")?)?;
+        let alice_ctx = Ctx::new(*seeded.users.get("alice").context("alice")?)?;
+        let fx_policy = policy(1, &[alice_ctx.user_id()], &[]);
+
+        // -- Exec & Check - not the lead
+        let res =
+            ApprovalBmc::set_policy(&alice_ctx, &mm, project_id, Some(fx_policy.clone())).await;
+        assert!(
+            matches!(res, Err(Error::NotProjectLead { user_id, .. }) if user_id == alice_ctx.user_id()),
+            "NotProjectLead not matching"
+        );
+        let policy = ApprovalBmc::get_policy(&alice_ctx, &mm, project_id).await?;
+        assert_eq!(policy, ApprovalPolicy::default());
+
+        // -- Exec & Check - the lead
+        ApprovalBmc::set_policy(&lead_ctx, &mm, project_id, Some(fx_policy.clone())).await?;
+        let policy = ApprovalBmc::get_policy(&alice_ctx, &mm, project_id).await?;
+        assert_eq!(policy, fx_policy);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_policy_in_memory_default() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = ModelManager::new_in_memory();
+        // (the denoms of the dev db)
+        Seed::from_file("seed/dev.toml")?
+            .apply(&Ctx::root_ctx(), &mm)
+            .await?;
+        let seeded = seed(&mm).await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let lead_id = *seeded.users.get("lead").context("lead")?;
+
+        // -- Exec
+        let policy = ApprovalBmc::get_policy(&Ctx::root_ctx(), &mm, project_id).await?;
+
+        // -- Check
+        assert_eq!(policy, ApprovalPolicy::default());
+        assert!(policy.with_lead(Some(lead_id)).can_review(lead_id));
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
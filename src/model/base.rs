@@ -1,88 +1,390 @@
 use crate::ctx::Ctx;
+use crate::model::event::{EventKind, ModelEvent};
+use crate::model::store::dbx::Dbx;
+use crate::model::store::memory::Row;
+use crate::model::store::{self, Storage};
 use crate::model::ModelManager;
 use crate::model::{Error, Result};
-use sqlb::HasFields;
-use sqlx::postgres::PgRow;
-use sqlx::FromRow;
+use crate::utils::parse_utc;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlb::{Field, HasFields, Raw, SqlxBindable};
+use sqlx::postgres::{PgArguments, PgRow};
+use sqlx::query::Query;
+use sqlx::{FromRow, Postgres};
+use time::OffsetDateTime;
+
+const LIST_LIMIT_MAX: i64 = 1000;
 
 pub trait DbBmc {
     const TABLE: &'static str;
+
+    /// If the table has a `version` column, incremented on each update
+    /// and checked against the expected version when one is given.
+    const VERSIONED: bool = false;
+
+    /// Columns with a unique constraint in the sql schema.
+    /// (checked by the in-memory storage, the db has its own constraints)
+    const UNIQUE_FIELDS: &'static [&'static str] = &[];
+
+    /// The `(link table, entity id column)` of the `model::tag` links,
+    /// if the entity is taggable.
+    const TAG_LINK: Option<(&'static str, &'static str)> = None;
+
+    /// Generated column defaults of the sql schema (e.g., `gen_random_uuid()`),
+    /// set by the in-memory storage on create.
+    fn column_defaults() -> Row {
+        Row::new()
+    }
 }
 
-pub async fn create<MC, E>(_ctx: &Ctx, mm: &ModelManager, data: E) -> Result<i64>
+/// Pagination for the `list` functions.
+/// (no `limit` lists all, a given one is capped to `LIST_LIMIT_MAX`)
+#[derive(Debug, Default, Deserialize)]
+pub struct ListOptions {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl ListOptions {
+    /// The `(limit, offset)` to apply, `None` limit meaning all.
+    pub(in crate::model) fn limit_offset(list_options: Option<Self>) -> (Option<i64>, i64) {
+        let Self { limit, offset } = list_options.unwrap_or_default();
+        let limit = limit.map(|limit| limit.clamp(0, LIST_LIMIT_MAX));
+        let offset = offset.unwrap_or(0).max(0);
+
+        (limit, offset)
+    }
+}
+
+pub async fn create<MC, E>(ctx: &Ctx, mm: &ModelManager, data: E) -> Result<i64>
 where
     MC: DbBmc,
-    E: HasFields,
+    E: HasFields + Serialize,
 {
-    let db = mm.db();
+    create_for_user::<MC, E>(ctx, mm, None, data).await
+}
 
-    let fields = data.not_none_fields();
-    let (id,) = sqlb::insert()
-        .table(MC::TABLE)
-        .data(fields)
-        .returning(&["id"])
-        .fetch_one::<_, (i64,)>(db)
-        .await?;
+/// `create`, with the user the entity is for on its event
+/// (see `ModelEvent::user_id`).
+pub async fn create_for_user<MC, E>(
+    _ctx: &Ctx,
+    mm: &ModelManager,
+    user_id: Option<i64>,
+    data: E,
+) -> Result<i64>
+where
+    MC: DbBmc,
+    E: HasFields + Serialize,
+{
+    // (in a transaction, to commit with the handling of its event)
+    mm.in_txn(|mm| async move {
+        let id = match mm.storage() {
+            Storage::Db(dbx) => {
+                let fields = data.not_none_fields();
+                let sb = sqlb::insert()
+                    .table(MC::TABLE)
+                    .data(fields)
+                    .returning(&["id"]);
+                let (id,) = dbx.fetch_one::<_, (i64,)>(&sb).await?;
+                id
+            }
+            Storage::Memory(mem) => {
+                let mut row = MC::column_defaults();
+                row.extend(data_to_row(&data)?);
+                mem.insert(MC::TABLE, MC::UNIQUE_FIELDS, MC::VERSIONED, row)?
+            }
+        };
 
-    Ok(id)
+        mm.publish(ModelEvent::new(MC::TABLE, id, EventKind::Created).with_user_id(user_id));
+
+        Ok(id)
+    })
+    .await
 }
 
 pub async fn get<MC, E>(_ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<E>
 where
     MC: DbBmc,
     E: for<'r> FromRow<'r, PgRow> + Unpin + Send,
-    E: HasFields,
+    E: HasFields + DeserializeOwned,
 {
-    let db = mm.db();
-
-    let entity: E = sqlb::select()
-        .table(MC::TABLE)
-        .columns(E::field_names())
-        .and_where("id", "=", id)
-        .fetch_optional(db)
-        .await?
-        .ok_or(Error::EntityNotFound {
+    let entity: Option<E> = match mm.storage() {
+        Storage::Db(dbx) => {
+            let sb = sqlb::select()
+                .table(MC::TABLE)
+                .columns(E::field_names())
+                .and_where("id", "=", id);
+            dbx.fetch_optional(&sb).await?
+        }
+        Storage::Memory(mem) => mem.get(MC::TABLE, id).map(row_to_entity).transpose()?,
+    };
+
+    entity.ok_or(Error::EntityNotFound {
+        entity: MC::TABLE,
+        id,
+    })
+}
+
+/// Lock the entity row until the end of the transaction (`FOR UPDATE`),
+/// to serialize the check-then-write of concurrent transactions.
+/// (no-op on the in-memory storage)
+pub async fn lock<MC>(_ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()>
+where
+    MC: DbBmc,
+{
+    if let Storage::Db(dbx) = mm.storage() {
+        let sql = format!("SELECT id FROM \"{}\" WHERE id = $1 FOR UPDATE", MC::TABLE);
+        let locked: Option<(i64,)> = dbx
+            .query_fetch_optional(sqlx::query_as(&sql).bind(id))
+            .await?;
+        locked.ok_or(Error::EntityNotFound {
             entity: MC::TABLE,
             id,
         })?;
+    }
+
+    Ok(())
+}
+
+pub async fn first_by<MC, E, V>(
+    _ctx: &Ctx,
+    mm: &ModelManager,
+    field: &'static str,
+    value: V,
+) -> Result<Option<E>>
+where
+    MC: DbBmc,
+    E: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+    E: HasFields + DeserializeOwned,
+    V: SqlxBindable + Serialize + Send + Sync,
+{
+    let entity: Option<E> = match mm.storage() {
+        Storage::Db(dbx) => {
+            let sb = sqlb::select()
+                .table(MC::TABLE)
+                .columns(E::field_names())
+                .and_where(field, "=", value);
+            dbx.fetch_optional(&sb).await?
+        }
+        Storage::Memory(mem) => {
+            let filter = Row::from_iter([(field.to_string(), serde_json::to_value(value)?)]);
+            mem.select(MC::TABLE, &filter)
+                .into_iter()
+                .next()
+                .map(row_to_entity)
+                .transpose()?
+        }
+    };
 
     Ok(entity)
 }
 
-pub async fn list<MC, E>(_ctx: &Ctx, mm: &ModelManager) -> Result<Vec<E>>
+pub async fn list<MC, E, F>(
+    ctx: &Ctx,
+    mm: &ModelManager,
+    filter: Option<F>,
+    list_options: Option<ListOptions>,
+) -> Result<Vec<E>>
 where
     MC: DbBmc,
     E: for<'r> FromRow<'r, PgRow> + Unpin + Send,
-    E: HasFields,
+    E: HasFields + DeserializeOwned,
+    F: HasFields + Serialize,
 {
-    let db = mm.db();
+    list_tagged::<MC, E, F>(ctx, mm, filter, &[], list_options).await
+}
 
-    let entities: Vec<E> = sqlb::select()
-        .table(MC::TABLE)
-        .columns(E::field_names())
-        .order_by("id")
-        .fetch_all(db)
-        .await?;
+/// Same as `list`, restricted to the entities having all the `tags`
+/// (normalized names, see `model::tag`).
+pub async fn list_tagged<MC, E, F>(
+    _ctx: &Ctx,
+    mm: &ModelManager,
+    filter: Option<F>,
+    tags: &[String],
+    list_options: Option<ListOptions>,
+) -> Result<Vec<E>>
+where
+    MC: DbBmc,
+    E: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+    E: HasFields + DeserializeOwned,
+    F: HasFields + Serialize,
+{
+    let (limit, offset) = ListOptions::limit_offset(list_options);
+
+    let entities: Vec<E> = match mm.storage() {
+        Storage::Db(dbx) => {
+            let mut sb = sqlb::select()
+                .table(MC::TABLE)
+                .columns(E::field_names())
+                .order_by("id")
+                .offset(offset);
+            if let Some(limit) = limit {
+                sb = sb.limit(limit);
+            }
+
+            // Each not none filter field is an equality condition.
+            for Field { name, value } in filter.map(|f| f.not_none_fields()).unwrap_or_default() {
+                sb = sb.and_where(&name, "=", FieldValue(value));
+            }
+
+            if !tags.is_empty() {
+                let (link_table, link_column) =
+                    MC::TAG_LINK.ok_or(Error::EntityNotTaggable { entity: MC::TABLE })?;
+                // (sqlb takes a column name with parentheses as is)
+                let entity_tags = format!(
+                    "ARRAY(SELECT t.name::text FROM {link_table} l JOIN tag t ON t.id = l.tag_id \
+                     WHERE l.{link_column} = {}.id)",
+                    MC::TABLE
+                );
+                sb = sb.and_where(&entity_tags, "@>", TextArray(tags.to_vec()));
+            }
+
+            dbx.fetch_all(&sb).await?
+        }
+        Storage::Memory(_) if !tags.is_empty() => {
+            return Err(store::Error::NoDbInMemoryStorage.into());
+        }
+        Storage::Memory(mem) => {
+            let filter = filter.as_ref().map(data_to_row).transpose()?;
+            mem.select(MC::TABLE, &filter.unwrap_or_default())
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit.map_or(usize::MAX, |limit| limit as usize))
+                .map(row_to_entity)
+                .collect::<Result<_>>()?
+        }
+    };
 
     Ok(entities)
 }
 
-pub async fn update<MC, E>(_ctx: &Ctx, mm: &ModelManager, id: i64, data: E) -> Result<()>
+/// Same as `list`, restricted to the entities with their `column` time
+/// before `moment` (e.g., the overdue ones, a null time never being).
+pub async fn list_before<MC, E, F>(
+    _ctx: &Ctx,
+    mm: &ModelManager,
+    filter: Option<F>,
+    column: &'static str,
+    moment: OffsetDateTime,
+    list_options: Option<ListOptions>,
+) -> Result<Vec<E>>
+where
+    MC: DbBmc,
+    E: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+    E: HasFields + DeserializeOwned,
+    F: HasFields + Serialize,
+{
+    let (limit, offset) = ListOptions::limit_offset(list_options);
+
+    let entities: Vec<E> = match mm.storage() {
+        Storage::Db(dbx) => {
+            let mut sb = sqlb::select()
+                .table(MC::TABLE)
+                .columns(E::field_names())
+                .and_where(column, "<", moment)
+                .order_by("id")
+                .offset(offset);
+            if let Some(limit) = limit {
+                sb = sb.limit(limit);
+            }
+
+            for Field { name, value } in filter.map(|f| f.not_none_fields()).unwrap_or_default() {
+                sb = sb.and_where(&name, "=", FieldValue(value));
+            }
+
+            dbx.fetch_all(&sb).await?
+        }
+        Storage::Memory(mem) => {
+            let filter = filter.as_ref().map(data_to_row).transpose()?;
+            // (the times are stored as RFC3339 strings, see `utils::parse_utc`)
+            let is_before = |row: &Row| {
+                row.get(column)
+                    .and_then(Value::as_str)
+                    .and_then(|time| parse_utc(time).ok())
+                    .is_some_and(|time| time < moment)
+            };
+            mem.select(MC::TABLE, &filter.unwrap_or_default())
+                .into_iter()
+                .filter(is_before)
+                .skip(offset as usize)
+                .take(limit.map_or(usize::MAX, |limit| limit as usize))
+                .map(row_to_entity)
+                .collect::<Result<_>>()?
+        }
+    };
+
+    Ok(entities)
+}
+
+pub async fn update<MC, E>(
+    _ctx: &Ctx,
+    mm: &ModelManager,
+    id: i64,
+    expected_version: Option<i64>,
+    data: E,
+) -> Result<()>
+where
+    MC: DbBmc,
+    E: HasFields + Serialize,
+{
+    let expected_version = expected_version.filter(|_| MC::VERSIONED);
+
+    mm.in_txn(|mm| async move {
+        match mm.storage() {
+            Storage::Db(dbx) => update_db::<MC, E>(dbx, id, expected_version, data).await?,
+            Storage::Memory(mem) => mem.update(
+                MC::TABLE,
+                MC::UNIQUE_FIELDS,
+                MC::VERSIONED,
+                id,
+                expected_version,
+                update_to_row(data)?,
+            )?,
+        }
+
+        mm.publish(ModelEvent::new(MC::TABLE, id, EventKind::Updated));
+
+        Ok(())
+    })
+    .await
+}
+
+async fn update_db<MC, E>(dbx: &Dbx, id: i64, expected_version: Option<i64>, data: E) -> Result<()>
 where
     MC: DbBmc,
     E: HasFields,
 {
-    let db = mm.db();
+    let mut fields = data.not_none_fields();
+    if MC::VERSIONED {
+        fields.push(("version", Raw("version + 1")).into());
+    }
 
-    let fields = data.not_none_fields();
-    let count = sqlb::update()
-        .table(MC::TABLE)
-        .and_where("id", "=", id)
-        .data(fields)
-        .exec(db)
-        .await?;
+    let mut sb = sqlb::update().table(MC::TABLE).and_where("id", "=", id);
+    if let Some(expected_version) = expected_version {
+        sb = sb.and_where("version", "=", expected_version);
+    }
+    let sb = sb.data(fields);
+    let count = dbx.exec(&sb).await?;
 
     if count == 0 {
+        // -- Distinguish a stale version from a missing entity.
+        if let Some(expected) = expected_version {
+            let sb = sqlb::select()
+                .table(MC::TABLE)
+                .columns(&["version"])
+                .and_where("id", "=", id);
+            if let Some((current,)) = dbx.fetch_optional::<_, (i64,)>(&sb).await? {
+                return Err(Error::VersionConflict {
+                    entity: MC::TABLE,
+                    id,
+                    expected,
+                    current,
+                });
+            }
+        }
+
         Err(Error::EntityNotFound {
             entity: MC::TABLE,
             id,
@@ -96,20 +398,111 @@ pub async fn delete<MC>(_ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()>
 where
     MC: DbBmc,
 {
-    let db = mm.db();
+    mm.in_txn(|mm| async move {
+        let deleted = match mm.storage() {
+            Storage::Db(dbx) => {
+                let sb = sqlb::delete().table(MC::TABLE).and_where("id", "=", id);
+                dbx.exec(&sb).await? > 0
+            }
+            Storage::Memory(mem) => mem.delete(MC::TABLE, id),
+        };
 
-    let count = sqlb::delete()
-        .table(MC::TABLE)
-        .and_where("id", "=", id)
-        .exec(db)
-        .await?;
+        if !deleted {
+            Err(Error::EntityNotFound {
+                entity: MC::TABLE,
+                id,
+            })
+        } else {
+            mm.publish(ModelEvent::new(MC::TABLE, id, EventKind::Deleted));
+            Ok(())
+        }
+    })
+    .await
+}
 
-    if count == 0 {
-        Err(Error::EntityNotFound {
-            entity: MC::TABLE,
-            id,
+// region:    --- Memory Rows
+
+/// The not none fields of `data` as a row.
+fn data_to_row<E>(data: &E) -> Result<Row>
+where
+    E: HasFields + Serialize,
+{
+    let Value::Object(mut row) = serde_json::to_value(data)? else {
+        return Err(Error::MemoryRowNotAnObject);
+    };
+    let names = E::field_names();
+    row.retain(|name, val| !val.is_null() && names.contains(&name.as_str()));
+
+    Ok(row)
+}
+
+/// The not none fields of the update `data` as a row, the cleared ones
+/// (e.g., a `Some(None)`) being null.
+fn update_to_row<E>(data: E) -> Result<Row>
+where
+    E: HasFields + Serialize,
+{
+    let Value::Object(mut row) = serde_json::to_value(&data)? else {
+        return Err(Error::MemoryRowNotAnObject);
+    };
+    let names: Vec<String> = data.not_none_fields().into_iter().map(|f| f.name).collect();
+    row.retain(|name, _| names.contains(name));
+
+    Ok(row)
+}
+
+/// The entity from its fields of the row (missing columns being null).
+fn row_to_entity<E>(row: Row) -> Result<E>
+where
+    E: HasFields + DeserializeOwned,
+{
+    let fields: Row = E::field_names()
+        .iter()
+        .map(|name| {
+            let val = row.get(*name).cloned().unwrap_or(Value::Null);
+            (name.to_string(), val)
         })
-    } else {
-        Ok(())
+        .collect();
+
+    Ok(serde_json::from_value(Value::Object(fields))?)
+}
+
+// endregion: --- Memory Rows
+
+// region:    --- FieldValue
+
+/// Makes a `sqlb::Field` value usable as a where clause value.
+#[derive(Debug)]
+struct FieldValue<'a>(Box<dyn SqlxBindable + 'a + Send + Sync>);
+
+impl SqlxBindable for FieldValue<'_> {
+    fn bind_query<'q>(
+        &'q self,
+        query: Query<'q, Postgres, PgArguments>,
+    ) -> Query<'q, Postgres, PgArguments> {
+        self.0.bind_query(query)
+    }
+
+    fn raw(&self) -> Option<&str> {
+        self.0.raw()
+    }
+}
+
+// endregion: --- FieldValue
+
+// region:    --- TextArray
+
+/// A `text[]` value.
+#[derive(Debug)]
+struct TextArray(Vec<String>);
+
+impl SqlxBindable for TextArray {
+    fn bind_query<'q>(
+        &'q self,
+        query: Query<'q, Postgres, PgArguments>,
+    ) -> Query<'q, Postgres, PgArguments> {
+        query.bind(self.0.clone())
     }
 }
+
+// endregion: --- TextArray
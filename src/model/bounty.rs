@@ -1,14 +1,24 @@
 use crate::ctx::Ctx;
+use crate::model::address::{self, validate_optional_address};
+use crate::model::denom::{Denom, DenomBmc};
+use crate::model::event::{EventKind, ModelEvent};
+use crate::model::ledger::LedgerBmc;
+use crate::model::project::ProjectBmc;
+use crate::model::store::memory::Row;
+use crate::model::tag;
 use crate::model::ModelManager;
 use crate::model::{Error, Result};
 use serde::{Deserialize, Serialize};
 use sqlb::Fields;
 use sqlx::FromRow;
+use std::collections::HashMap;
+use time::OffsetDateTime;
+use validator::Validate;
 
-use super::base::{self, DbBmc};
+use super::base::{self, DbBmc, ListOptions};
 
 // region:    --- Bounty Types
-#[derive(Debug, Clone, Fields, FromRow, Serialize)]
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
 pub struct Bounty {
     pub id: i64,
     pub bounty_title: String,
@@ -16,22 +26,113 @@ pub struct Bounty {
     pub bounty_reward: String,
     pub bounty_deliverables: String,
     pub bounty_description: String,
+    pub bounty_project_id: Option<i64>,
+    pub bounty_status: BountyStatus,
+    /// Expired past this time, if not assigned or assigned only
+    /// (see `scheduler`).
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub bounty_deadline: Option<OffsetDateTime>,
+    pub version: i64,
+
+    /// The reward with the display formatting of the project denom
+    /// (see `model::denom`, none for a bounty without project).
+    #[field(skip)]
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub bounty_reward_display: Option<String>,
+}
+
+/// Bounty lifecycle status (the `bounty_status` db enum).
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type, strum_macros::AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "bounty_status", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum BountyStatus {
+    Open,
+    Assigned,
+    Submitted,
+    Approved,
+    Paid,
+    Expired,
+    Cancelled,
+}
+sqlb::bindable!(BountyStatus);
+
+impl BountyStatus {
+    /// The allowed lifecycle transitions.
+    pub fn can_transition_to(self, to: BountyStatus) -> bool {
+        use BountyStatus::*;
+
+        matches!(
+            (self, to),
+            (Open, Assigned)
+                | (Assigned, Submitted)
+                // (changes requested on the submission)
+                | (Submitted, Assigned)
+                | (Submitted, Approved)
+                // (the assignee missed its deadline, see `ApplicationBmc::lapse`)
+                | (Assigned, Open)
+                | (Approved, Paid)
+                | (Open | Assigned, Expired)
+                | (Open | Assigned | Submitted, Cancelled)
+        )
+    }
 }
-#[derive(Deserialize, Fields)]
+
+#[derive(Deserialize, Serialize, Fields, Validate)]
 pub struct BountyForCreate {
+    #[validate(length(min = 1, max = 256))]
     pub bounty_title: String,
+    #[validate(length(max = 256), custom = "validate_optional_address")]
     pub bounty_assignee: String,
+    #[validate(length(min = 1, max = 256))]
     pub bounty_reward: String,
+    #[validate(length(max = 256))]
     pub bounty_deliverables: String,
+    #[validate(length(max = 256))]
     pub bounty_description: String,
+    pub bounty_project_id: Option<i64>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub bounty_deadline: Option<OffsetDateTime>,
 }
-#[derive(Deserialize, Fields)]
+#[derive(Default, Deserialize, Serialize, Fields, Validate)]
 pub struct BountyForUpdate {
+    #[validate(length(min = 1, max = 256))]
     pub bounty_title: Option<String>,
-    pub bounty_assignee: Option<String>,
+    #[validate(length(min = 1, max = 256))]
     pub bounty_reward: Option<String>,
+    #[validate(length(max = 256))]
     pub bounty_deliverables: Option<String>,
+    #[validate(length(max = 256))]
     pub bounty_description: Option<String>,
+    /// The new deadline, or `Some(None)` (a json null) to clear it.
+    #[serde(default, with = "crate::utils::rfc3339_update")]
+    pub bounty_deadline: Option<Option<OffsetDateTime>>,
+
+    /// Expected row version, the update fails with a conflict if stale.
+    #[field(skip)]
+    pub version: Option<i64>,
+}
+
+/// Status transition, with the new assignee when assigned.
+#[derive(Serialize, Fields)]
+pub struct BountyForTransition {
+    pub bounty_status: BountyStatus,
+    pub bounty_assignee: Option<String>,
+}
+
+#[derive(Default, Deserialize, Serialize, Fields)]
+pub struct BountyFilter {
+    pub bounty_title: Option<String>,
+    pub bounty_assignee: Option<String>,
+    pub bounty_project_id: Option<i64>,
+    pub bounty_status: Option<BountyStatus>,
+
+    /// Tag name (see `model::tag`).
+    #[field(skip)]
+    pub tag: Option<String>,
 }
 // endregion: --- Bounty Types
 
@@ -40,32 +141,249 @@ pub struct BountyBmc;
 
 impl DbBmc for BountyBmc {
     const TABLE: &'static str = "bounty";
+    const VERSIONED: bool = true;
+    const TAG_LINK: Option<(&'static str, &'static str)> = Some(("bounty_tag", "bounty_id"));
+
+    fn column_defaults() -> Row {
+        Row::from_iter([("bounty_status".to_string(), "open".into())])
+    }
 }
 
 impl BountyBmc {
-    pub async fn create(ctx: &Ctx, mm: &ModelManager, bounty_c: BountyForCreate) -> Result<i64> {
+    pub async fn create(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        mut bounty_c: BountyForCreate,
+    ) -> Result<i64> {
+        bounty_c.bounty_assignee = address::normalize(&bounty_c.bounty_assignee);
+        if let Some(project_id) = bounty_c.bounty_project_id {
+            bounty_c.bounty_reward =
+                Self::reward_in_base_units(ctx, mm, project_id, &bounty_c.bounty_reward).await?;
+        }
         base::create::<Self, _>(ctx, mm, bounty_c).await
     }
 
     pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Bounty> {
-        base::get::<Self, _>(ctx, mm, id).await
+        let mut bounty = base::get::<Self, _>(ctx, mm, id).await?;
+        Self::set_reward_display(ctx, mm, std::slice::from_mut(&mut bounty)).await?;
+
+        Ok(bounty)
     }
 
-    pub async fn list(ctx: &Ctx, mm: &ModelManager) -> Result<Vec<Bounty>> {
-        base::list::<Self, _>(ctx, mm).await
+    pub async fn list(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        filter: Option<BountyFilter>,
+        list_options: Option<ListOptions>,
+    ) -> Result<Vec<Bounty>> {
+        let tags: Vec<String> = filter
+            .as_ref()
+            .and_then(|f| f.tag.as_deref())
+            .map(tag::normalize)
+            .into_iter()
+            .collect();
+
+        let mut bounties =
+            base::list_tagged::<Self, _, _>(ctx, mm, filter, &tags, list_options).await?;
+        Self::set_reward_display(ctx, mm, &mut bounties).await?;
+
+        Ok(bounties)
     }
+    /// Update the bounty, as the lead of its project.
+    /// (the assignee only changes with its status, see `assign` and
+    /// `unassign`)
     pub async fn update(
         ctx: &Ctx,
         mm: &ModelManager,
         id: i64,
-        bounty_u: BountyForUpdate,
+        mut bounty_u: BountyForUpdate,
     ) -> Result<()> {
-        base::update::<Self, _>(ctx, mm, id, bounty_u).await
+        let bounty = base::get::<Self, Bounty>(ctx, mm, id).await?;
+        Self::check_lead(ctx, mm, &bounty).await?;
+
+        if let Some(reward) = &bounty_u.bounty_reward {
+            // (frozen once assigned, being the escrowed and paid amount)
+            if bounty.bounty_status != BountyStatus::Open {
+                return Err(Error::BountyRewardFrozen {
+                    id,
+                    status: bounty.bounty_status.as_ref().to_string(),
+                });
+            }
+            // (the read version guards against a concurrent assignment)
+            bounty_u.version.get_or_insert(bounty.version);
+            if let Some(project_id) = bounty.bounty_project_id {
+                bounty_u.bounty_reward =
+                    Some(Self::reward_in_base_units(ctx, mm, project_id, reward).await?);
+            }
+        }
+        base::update::<Self, _>(ctx, mm, id, bounty_u.version, bounty_u).await
     }
 
+    /// Delete the bounty, as the lead of its project.
     pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        let bounty = base::get::<Self, Bounty>(ctx, mm, id).await?;
+        Self::check_lead(ctx, mm, &bounty).await?;
+
         base::delete::<Self>(ctx, mm, id).await
     }
+
+    /// Check the `Ctx` user is the lead of the bounty project
+    /// (`NotProjectLead` otherwise, and for a bounty without project).
+    /// (the root ctx, of the system tasks, always is)
+    pub async fn check_lead(ctx: &Ctx, mm: &ModelManager, bounty: &Bounty) -> Result<()> {
+        match bounty.bounty_project_id {
+            Some(project_id) => ProjectBmc::check_lead(ctx, mm, project_id)
+                .await
+                .map(|_| ()),
+            None if ctx.is_root() => Ok(()),
+            None => Err(Error::NotProjectLead {
+                project_id: None,
+                user_id: ctx.user_id(),
+            }),
+        }
+    }
+
+    /// Move the bounty to the `to` status, if allowed from its current one
+    /// (see `BountyStatus::can_transition_to`), posting its ledger entry
+    /// (see `model::ledger`).
+    pub async fn transition(ctx: &Ctx, mm: &ModelManager, id: i64, to: BountyStatus) -> Result<()> {
+        let bounty_t = BountyForTransition {
+            bounty_status: to,
+            bounty_assignee: None,
+        };
+        Self::transition_with(ctx, mm, id, bounty_t).await
+    }
+
+    /// Move the assigned bounty back to open, without assignee
+    /// (releasing its escrow, see `model::ledger`).
+    pub async fn unassign(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        let bounty_t = BountyForTransition {
+            bounty_status: BountyStatus::Open,
+            bounty_assignee: Some(String::new()),
+        };
+        Self::transition_with(ctx, mm, id, bounty_t).await
+    }
+
+    /// The open or assigned bounties with their deadline before `now`
+    /// (to be expired, see `scheduler`).
+    pub async fn list_overdue(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        now: OffsetDateTime,
+    ) -> Result<Vec<Bounty>> {
+        let mut overdue = Vec::new();
+        for status in [BountyStatus::Open, BountyStatus::Assigned] {
+            let filter = BountyFilter {
+                bounty_status: Some(status),
+                ..Default::default()
+            };
+            let bounties: Vec<Bounty> = base::list_before::<Self, _, _>(
+                ctx,
+                mm,
+                Some(filter),
+                "bounty_deadline",
+                now,
+                None,
+            )
+            .await?;
+            overdue.extend(bounties);
+        }
+
+        Ok(overdue)
+    }
+
+    /// Assign the open bounty to `assignee`.
+    pub async fn assign(ctx: &Ctx, mm: &ModelManager, id: i64, assignee: &str) -> Result<()> {
+        let bounty_t = BountyForTransition {
+            bounty_status: BountyStatus::Assigned,
+            bounty_assignee: Some(address::normalize(assignee)),
+        };
+        Self::transition_with(ctx, mm, id, bounty_t).await
+    }
+
+    /// The reward in base units of the project denom
+    /// (e.g., "1.5 ATOM" -> "1500000", see `DenomBmc::amount_in`).
+    async fn reward_in_base_units(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        project_id: i64,
+        reward: &str,
+    ) -> Result<String> {
+        let project = ProjectBmc::get(ctx, mm, project_id).await?;
+        let denom = DenomBmc::resolve(ctx, mm, &project.project_denom).await?;
+        let amount = DenomBmc::amount_in(ctx, mm, reward, &denom).await?;
+
+        Ok(amount.to_string())
+    }
+
+    /// Set the `bounty_reward_display` of the project bounties.
+    /// (left none if the reward or the project denom is not a valid one,
+    /// e.g., for the bounties created before the denom registry)
+    async fn set_reward_display(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        bounties: &mut [Bounty],
+    ) -> Result<()> {
+        let mut denoms: HashMap<i64, Option<Denom>> = HashMap::new();
+
+        for bounty in bounties {
+            let Some(project_id) = bounty.bounty_project_id else {
+                continue;
+            };
+            let denom = match denoms.get(&project_id) {
+                Some(denom) => denom.clone(),
+                None => {
+                    let project = ProjectBmc::get(ctx, mm, project_id).await?;
+                    let denom = DenomBmc::resolve(ctx, mm, &project.project_denom)
+                        .await
+                        .ok();
+                    denoms.insert(project_id, denom.clone());
+                    denom
+                }
+            };
+
+            if let (Some(denom), Ok(amount)) = (denom, bounty.bounty_reward.parse::<i64>()) {
+                bounty.bounty_reward_display = Some(denom.format(amount));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn transition_with(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        id: i64,
+        bounty_t: BountyForTransition,
+    ) -> Result<()> {
+        mm.transaction(|mm| async move {
+            let bounty = Self::get(ctx, &mm, id).await?;
+            let (from, to) = (bounty.bounty_status, bounty_t.bounty_status);
+            if !from.can_transition_to(to) {
+                return Err(Error::InvalidTransition {
+                    entity: Self::TABLE,
+                    id,
+                    from: from.as_ref().to_string(),
+                    to: to.as_ref().to_string(),
+                });
+            }
+
+            // (the read version guards against a concurrent transition)
+            base::update::<Self, _>(ctx, &mm, id, Some(bounty.version), bounty_t).await?;
+            LedgerBmc::post_for_bounty(ctx, &mm, &bounty, to).await?;
+            mm.publish(ModelEvent::new(
+                Self::TABLE,
+                id,
+                EventKind::Transitioned {
+                    from: from.as_ref().to_string(),
+                    to: to.as_ref().to_string(),
+                },
+            ));
+
+            Ok(())
+        })
+        .await
+    }
 }
 // endregion: --- BountyBmc
 
@@ -76,10 +394,9 @@ mod tests {
     use crate::_dev_utils;
 
     use super::*;
+    use crate::model::project::ProjectForCreate;
     use anyhow::Result;
-    use serial_test::serial;
 
-    #[serial]
     #[tokio::test]
     async fn test_create_ok() -> Result<()> {
         // -- Setup & Fixture
@@ -93,20 +410,22 @@ mod tests {
             bounty_deliverables: fx_title.to_string(),
             bounty_description: fx_title.to_string(),
             bounty_reward: fx_title.to_string(),
+            bounty_project_id: None,
+            bounty_deadline: None,
         };
         let id = BountyBmc::create(&ctx, &mm, bounty_c).await?;
 
         // -- Check title using SQL query
-        let (title,): (String,) = sqlx::query_as("SELECT title from bounty where id = $1")
+        let (title,): (String,) = sqlx::query_as("SELECT bounty_title from bounty where id = $1")
             .bind(id)
-            .fetch_one(mm.db())
+            .fetch_one(mm.db()?)
             .await?;
 
         assert_eq!(title, fx_title);
         // -- Clean Bounty using SQL query
         let count = sqlx::query("DELETE FROM bounty WHERE id = $1")
             .bind(id)
-            .execute(mm.db())
+            .execute(mm.db()?)
             .await?
             .rows_affected();
         assert_eq!(count, 1, "Did not delete 1 row?");
@@ -116,6 +435,8 @@ mod tests {
             bounty_deliverables: fx_title.to_string(),
             bounty_description: fx_title.to_string(),
             bounty_reward: fx_title.to_string(),
+            bounty_project_id: None,
+            bounty_deadline: None,
         };
         let id = BountyBmc::create(&ctx, &mm, bounty_c).await?;
         // -- Check Bounty using model get method
@@ -127,7 +448,6 @@ mod tests {
         Ok(())
     }
 
-    #[serial]
     #[tokio::test]
     async fn test_get_err_not_found() -> Result<()> {
         // -- Setup & Fixture
@@ -148,7 +468,6 @@ mod tests {
         ),);
         Ok(())
     }
-    #[serial]
     #[tokio::test]
     async fn test_delete_bounty_ok() -> Result<()> {
         // -- Setup & Fixture
@@ -169,7 +488,6 @@ mod tests {
         );
         Ok(())
     }
-    #[serial]
     #[tokio::test]
     async fn test_list_ok() -> Result<()> {
         // -- Setup & Fixture
@@ -179,7 +497,7 @@ mod tests {
         let fx_titles = &["test_list_ok-bounty 01", "test_list_ok-bounty 02"];
         _dev_utils::seed_tasks(&ctx, &mm, fx_titles).await?;
         // -- Exec
-        let bounties = BountyBmc::list(&ctx, &mm).await?;
+        let bounties = BountyBmc::list(&ctx, &mm, None, None).await?;
         // -- Check
         let bounties: Vec<Bounty> = bounties
             .into_iter()
@@ -192,7 +510,6 @@ mod tests {
         }
         Ok(())
     }
-    #[serial]
     #[tokio::test]
     async fn test_update_ok() -> Result<()> {
         // -- Setup & Fixture
@@ -210,10 +527,11 @@ mod tests {
             fx_bounty.id,
             BountyForUpdate {
                 bounty_title: Some(fx_new_title.to_string()),
-                bounty_assignee: Some(fx_new_title.to_string()),
                 bounty_deliverables: Some(fx_new_title.to_string()),
                 bounty_description: Some(fx_new_title.to_string()),
                 bounty_reward: Some(fx_new_title.to_string()),
+                bounty_deadline: None,
+                version: None,
             },
         )
         .await?;
@@ -223,5 +541,270 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_update_deadline_clear_ok() -> Result<()> {
+        // -- Setup & Fixture
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let fx_bounty =
+            _dev_utils::seed_tasks(&ctx, &mm, &["test_update_deadline_clear_ok - bounty 01"])
+                .await?
+                .remove(0);
+        let fx_set: BountyForUpdate =
+            serde_json::from_str(r#"{"bounty_deadline": "2030-01-01T00:00:00Z"}"#)?;
+        let fx_clear: BountyForUpdate = serde_json::from_str(r#"{"bounty_deadline": null}"#)?;
+        let fx_none: BountyForUpdate = serde_json::from_str(r#"{"bounty_title": "renamed"}"#)?;
+        assert_eq!(fx_clear.bounty_deadline, Some(None), "null clears");
+        assert_eq!(fx_none.bounty_deadline, None, "absent unchanged");
+
+        // -- Exec & Check
+        BountyBmc::update(&ctx, &mm, fx_bounty.id, fx_set).await?;
+        BountyBmc::update(&ctx, &mm, fx_bounty.id, fx_none).await?;
+        let bounty = BountyBmc::get(&ctx, &mm, fx_bounty.id).await?;
+        assert!(bounty.bounty_deadline.is_some(), "deadline set");
+
+        BountyBmc::update(&ctx, &mm, fx_bounty.id, fx_clear).await?;
+        let bounty = BountyBmc::get(&ctx, &mm, fx_bounty.id).await?;
+        assert_eq!(bounty.bounty_deadline, None, "deadline cleared");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_err_version_conflict() -> Result<()> {
+        // -- Setup & Fixture
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let fx_title = "test_update_err_version_conflict - bounty 01";
+        let fx_bounty = _dev_utils::seed_tasks(&ctx, &mm, &[fx_title])
+            .await?
+            .remove(0);
+        let fx_update = |version| BountyForUpdate {
+            bounty_title: Some(format!("{fx_title} - v{version}")),
+            bounty_deliverables: None,
+            bounty_description: None,
+            bounty_reward: None,
+            bounty_deadline: None,
+            version: Some(version),
+        };
+
+        // -- Exec
+        // First writer, with the current version, wins.
+        BountyBmc::update(&ctx, &mm, fx_bounty.id, fx_update(fx_bounty.version)).await?;
+        // Second writer, with the same (now stale) version, conflicts.
+        let res = BountyBmc::update(&ctx, &mm, fx_bounty.id, fx_update(fx_bounty.version)).await;
+
+        // -- Check
+        let current = fx_bounty.version + 1;
+        assert!(
+            matches!(
+                res,
+                Err(Error::VersionConflict { entity: "bounty", id, expected, current: c })
+                    if id == fx_bounty.id && expected == fx_bounty.version && c == current
+            ),
+            "VersionConflict not matching"
+        );
+        let bounty = BountyBmc::get(&ctx, &mm, fx_bounty.id).await?;
+        assert_eq!(bounty.version, current);
+
+        // -- Clean
+        BountyBmc::delete(&ctx, &mm, fx_bounty.id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transition_ok() -> Result<()> {
+        // -- Setup & Fixture
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let fx_bounty = _dev_utils::seed_tasks(&ctx, &mm, &["test_transition_ok - bounty 01"])
+            .await?
+            .remove(0);
+
+        // -- Exec
+        BountyBmc::assign(
+            &ctx,
+            &mm,
+            fx_bounty.id,
+            "cosmos12w5avhfkylduwqts47x406zdqfr5d3nj04g9g0",
+        )
+        .await?;
+        let res = BountyBmc::transition(&ctx, &mm, fx_bounty.id, BountyStatus::Paid).await;
+
+        // -- Check
+        let bounty = BountyBmc::get(&ctx, &mm, fx_bounty.id).await?;
+        assert_eq!(bounty.bounty_status, BountyStatus::Assigned);
+        assert_eq!(
+            bounty.bounty_assignee,
+            "cosmos12w5avhfkylduwqts47x406zdqfr5d3nj04g9g0"
+        );
+        assert!(
+            matches!(
+                &res,
+                Err(Error::InvalidTransition { entity: "bounty", from, to, .. })
+                    if from == "assigned" && to == "paid"
+            ),
+            "InvalidTransition not matching"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_err_reward_frozen() -> Result<()> {
+        // -- Setup & Fixture
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let fx_bounty =
+            _dev_utils::seed_tasks(&ctx, &mm, &["test_update_err_reward_frozen - bounty 01"])
+                .await?
+                .remove(0);
+        BountyBmc::assign(
+            &ctx,
+            &mm,
+            fx_bounty.id,
+            "cosmos12w5avhfkylduwqts47x406zdqfr5d3nj04g9g0",
+        )
+        .await?;
+
+        // -- Exec
+        let res = BountyBmc::update(
+            &ctx,
+            &mm,
+            fx_bounty.id,
+            BountyForUpdate {
+                bounty_reward: Some("999".to_string()),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        // -- Check
+        assert!(
+            matches!(&res, Err(Error::BountyRewardFrozen { status, .. }) if status == "assigned"),
+            "BountyRewardFrozen not matching"
+        );
+        let bounty = BountyBmc::get(&ctx, &mm, fx_bounty.id).await?;
+        assert_eq!(bounty.bounty_reward, fx_bounty.bounty_reward);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_for_create_err() -> Result<()> {
+        // -- Setup & Fixture
+        let fx_long = "x".repeat(257);
+        let bounty_c = BountyForCreate {
+            bounty_title: "".to_string(),
+            bounty_assignee: "".to_string(),
+            bounty_deliverables: "".to_string(),
+            bounty_description: fx_long,
+            bounty_reward: "10".to_string(),
+            bounty_project_id: None,
+            bounty_deadline: None,
+        };
+
+        // -- Exec
+        let errors = bounty_c.validate().expect_err("Should fail validation");
+
+        // -- Check
+        let mut fields: Vec<&str> = errors.field_errors().into_keys().collect();
+        fields.sort();
+        assert_eq!(fields, ["bounty_description", "bounty_title"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_reward_denom_ok() -> Result<()> {
+        // -- Setup & Fixture
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let fx_title = "test_create_reward_denom_ok";
+        let project_id = project_create(&ctx, &mm, fx_title).await?;
+
+        // -- Exec
+        let mut ids = Vec::new();
+        for fx_reward in ["1.5 ATOM", "1500000", "1500000 uatom"] {
+            let bounty_c = bounty_c(fx_title, fx_reward, Some(project_id));
+            ids.push(BountyBmc::create(&ctx, &mm, bounty_c).await?);
+        }
+
+        // -- Check
+        for id in ids {
+            let bounty = BountyBmc::get(&ctx, &mm, id).await?;
+            assert_eq!(bounty.bounty_reward, "1500000", "base units");
+            assert_eq!(bounty.bounty_reward_display.as_deref(), Some("1.5 ATOM"));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_reward_denom_err() -> Result<()> {
+        // -- Setup & Fixture
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let fx_title = "test_create_reward_denom_err";
+        let project_id = project_create(&ctx, &mm, fx_title).await?;
+
+        // -- Exec & Check
+        let res =
+            BountyBmc::create(&ctx, &mm, bounty_c(fx_title, "2 OSMO", Some(project_id))).await;
+        assert!(
+            matches!(res, Err(Error::DenomMismatch { .. })),
+            "DenomMismatch not matching"
+        );
+        let res = BountyBmc::create(&ctx, &mm, bounty_c(fx_title, "2 XMR", Some(project_id))).await;
+        assert!(
+            matches!(res, Err(Error::DenomUnknown { .. })),
+            "DenomUnknown not matching"
+        );
+
+        let mut project_c = project_c(fx_title);
+        project_c.project_id = format!("{fx_title}-xmr");
+        project_c.project_denom = "xmr".to_string();
+        let res = ProjectBmc::create(&ctx, &mm, project_c).await;
+        assert!(
+            matches!(res, Err(Error::DenomUnknown { .. })),
+            "DenomUnknown not matching"
+        );
+
+        Ok(())
+    }
+
+    fn bounty_c(title: &str, reward: &str, project_id: Option<i64>) -> BountyForCreate {
+        BountyForCreate {
+            bounty_title: title.to_string(),
+            bounty_assignee: String::new(),
+            bounty_deliverables: title.to_string(),
+            bounty_description: title.to_string(),
+            bounty_reward: reward.to_string(),
+            bounty_project_id: project_id,
+            bounty_deadline: None,
+        }
+    }
+
+    fn project_c(project_id: &str) -> ProjectForCreate {
+        ProjectForCreate {
+            project_id: project_id.to_string(),
+            project_name: project_id.to_string(),
+            project_lead_address: "cosmos1udzkhs056fc0f2texd6cv30acg0rjept43xsph".to_string(),
+            project_budget: "100".to_string(),
+            project_denom: "ATOM".to_string(),
+            project_type: project_id.to_string(),
+            project_description: project_id.to_string(),
+        }
+    }
+
+    async fn project_create(ctx: &Ctx, mm: &ModelManager, project_id: &str) -> Result<i64> {
+        let id = ProjectBmc::create(ctx, mm, project_c(project_id)).await?;
+        let project = ProjectBmc::get(ctx, mm, id).await?;
+        assert_eq!(project.project_denom, "uatom", "resolved to the base denom");
+
+        Ok(id)
+    }
 }
 // Endregion:    --- UnitTest
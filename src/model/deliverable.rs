@@ -0,0 +1,106 @@
+//! Deliverables checklist of a bounty, each one being covered by the
+//! items of a `model::submission`.
+//!
+//! (`bounty.bounty_deliverables` stays as the free text summary)
+//!
+//! The project lead sets the checklist while the bounty is open only, it
+//! being the acceptance criteria of the assignee and of the reviewers.
+//!
+
+use crate::ctx::Ctx;
+use crate::model::base::{self, DbBmc};
+use crate::model::bounty::{BountyBmc, BountyStatus};
+use crate::model::{Error, ModelManager, Result};
+use serde::{Deserialize, Serialize};
+use sqlb::Fields;
+use sqlx::FromRow;
+use validator::Validate;
+
+// region:    --- Deliverable Types
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct Deliverable {
+    pub id: i64,
+    pub deliverable_bounty_id: i64,
+    pub deliverable_title: String,
+    pub deliverable_description: String,
+}
+
+#[derive(Deserialize, Serialize, Fields, Validate)]
+pub struct DeliverableForCreate {
+    pub deliverable_bounty_id: i64,
+    #[validate(length(min = 1, max = 256))]
+    pub deliverable_title: String,
+    #[validate(length(max = 2048))]
+    pub deliverable_description: String,
+}
+
+#[derive(Default, Deserialize, Serialize, Fields)]
+pub struct DeliverableFilter {
+    pub deliverable_bounty_id: Option<i64>,
+}
+
+// endregion: --- Deliverable Types
+
+pub struct DeliverableBmc;
+
+impl DbBmc for DeliverableBmc {
+    const TABLE: &'static str = "deliverable";
+}
+
+impl DeliverableBmc {
+    pub async fn create(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        deliverable_c: DeliverableForCreate,
+    ) -> Result<i64> {
+        mm.transaction(|mm| async move {
+            Self::check_open(ctx, &mm, deliverable_c.deliverable_bounty_id).await?;
+            base::create::<Self, _>(ctx, &mm, deliverable_c).await
+        })
+        .await
+    }
+
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Deliverable> {
+        base::get::<Self, _>(ctx, mm, id).await
+    }
+
+    /// The checklist of the bounty, in creation order.
+    pub async fn list_for_bounty(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        bounty_id: i64,
+    ) -> Result<Vec<Deliverable>> {
+        let filter = DeliverableFilter {
+            deliverable_bounty_id: Some(bounty_id),
+        };
+        base::list::<Self, _, _>(ctx, mm, Some(filter), None).await
+    }
+
+    pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        mm.transaction(|mm| async move {
+            let deliverable = Self::get(ctx, &mm, id).await?;
+            Self::check_open(ctx, &mm, deliverable.deliverable_bounty_id).await?;
+            base::delete::<Self>(ctx, &mm, id).await
+        })
+        .await
+    }
+
+    /// The `Ctx` user must be the lead of the bounty project
+    /// (`NotProjectLead`), and the bounty open (`BountyChecklistFrozen`).
+    async fn check_open(ctx: &Ctx, mm: &ModelManager, bounty_id: i64) -> Result<()> {
+        // (until the commit, not to be assigned meanwhile)
+        base::lock::<BountyBmc>(ctx, mm, bounty_id).await?;
+        let bounty = BountyBmc::get(ctx, mm, bounty_id).await?;
+        BountyBmc::check_lead(ctx, mm, &bounty).await?;
+
+        if bounty.bounty_status == BountyStatus::Open {
+            Ok(())
+        } else {
+            Err(Error::BountyChecklistFrozen {
+                id: bounty_id,
+                status: bounty.bounty_status.as_ref().to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,408 @@
+//! Registry of the denoms the projects pay in.
+//!
+//! Design:
+//!
+//! - A denom has a base denom (e.g., `uatom`, the on-chain unit), and a
+//!   display denom and symbol (e.g., `atom` / `ATOM`) worth 10^exponent
+//!   base units.
+//! - Amounts are stored in base units (see `model::ledger`), and formatted
+//!   with `Denom::format` when returned.
+//! - `DenomBmc::resolve` accepts the base denom, or (case insensitive) the
+//!   display denom or symbol, so "uatom", "ATOM" and "atom" are the same
+//!   denom. Unknown and disabled denoms are rejected on project create or
+//!   update, and in the project bounty rewards.
+//! - Only an admin registers or updates the denoms (see
+//!   `UserBmc::check_admin`).
+//!
+
+use crate::ctx::Ctx;
+use crate::model::base::{self, DbBmc, ListOptions};
+use crate::model::store::memory::Row;
+use crate::model::user::UserBmc;
+use crate::model::{Error, ModelManager, Result};
+use lazy_regex::regex_is_match;
+use serde::{Deserialize, Serialize};
+use sqlb::Fields;
+use sqlx::FromRow;
+use validator::{Validate, ValidationError};
+
+// region:    --- Denom Types
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct Denom {
+    pub id: i64,
+    pub denom_base: String,
+    pub denom_display: String,
+    pub denom_exponent: i32,
+    pub denom_symbol: String,
+    pub denom_enabled: bool,
+}
+
+#[derive(Deserialize, Serialize, Fields, Validate)]
+pub struct DenomForCreate {
+    #[validate(custom = "validate_denom_base")]
+    pub denom_base: String,
+    #[validate(length(min = 1, max = 128))]
+    pub denom_display: String,
+    #[validate(range(min = 0, max = 18))]
+    pub denom_exponent: i32,
+    #[validate(length(max = 32))]
+    pub denom_symbol: String,
+    pub denom_enabled: Option<bool>,
+}
+
+/// (the base denom cannot change, amounts being stored in it)
+#[derive(Deserialize, Serialize, Fields, Validate)]
+pub struct DenomForUpdate {
+    #[validate(length(min = 1, max = 128))]
+    pub denom_display: Option<String>,
+    #[validate(range(min = 0, max = 18))]
+    pub denom_exponent: Option<i32>,
+    #[validate(length(max = 32))]
+    pub denom_symbol: Option<String>,
+    pub denom_enabled: Option<bool>,
+}
+
+#[derive(Default, Deserialize, Serialize, Fields)]
+pub struct DenomFilter {
+    pub denom_enabled: Option<bool>,
+}
+
+/// An amount, in base units and as displayed.
+#[derive(Debug, Clone, Serialize)]
+pub struct DenomAmount {
+    pub amount: String,
+    pub denom: String,
+    pub display: String,
+}
+
+/// The Cosmos SDK base denom format (e.g., `uatom`, `ibc/27394F...`).
+fn validate_denom_base(value: &str) -> core::result::Result<(), ValidationError> {
+    if regex_is_match!(r"^[a-zA-Z][a-zA-Z0-9/:._-]{2,127}$", value) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("denom"))
+    }
+}
+
+impl Denom {
+    /// The unit shown with the display amounts (the symbol if any).
+    pub fn label(&self) -> &str {
+        if self.denom_symbol.is_empty() {
+            &self.denom_display
+        } else {
+            &self.denom_symbol
+        }
+    }
+
+    /// The base units of a display amount (e.g., "1.5" atom -> 1500000).
+    pub fn to_base_units(&self, value: &str) -> Result<i64> {
+        let invalid = || Error::DenomInvalidAmount {
+            value: value.to_string(),
+            denom: self.denom_base.clone(),
+        };
+        let exponent = self.denom_exponent as usize;
+
+        let (int, frac) = value.trim().split_once('.').unwrap_or((value.trim(), ""));
+        let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if int.is_empty() || !digits(int) || !digits(frac) || frac.len() > exponent {
+            return Err(invalid());
+        }
+
+        let units = format!("{int}{frac:0<exponent$}");
+        units
+            .parse::<i64>()
+            .ok()
+            .filter(|amount| *amount > 0)
+            .ok_or_else(invalid)
+    }
+
+    /// The display amount of base units (e.g., 1500000 -> "1.5 ATOM").
+    pub fn format(&self, amount: i64) -> String {
+        let exponent = self.denom_exponent as u32;
+        let unit = 10u64.pow(exponent);
+        let sign = if amount < 0 { "-" } else { "" };
+        let (int, frac) = (amount.unsigned_abs() / unit, amount.unsigned_abs() % unit);
+
+        if frac == 0 {
+            format!("{sign}{int} {}", self.label())
+        } else {
+            let frac = format!("{frac:0>width$}", width = exponent as usize);
+            format!(
+                "{sign}{int}.{} {}",
+                frac.trim_end_matches('0'),
+                self.label()
+            )
+        }
+    }
+}
+
+// endregion: --- Denom Types
+
+pub struct DenomBmc;
+
+impl DbBmc for DenomBmc {
+    const TABLE: &'static str = "denom";
+    const UNIQUE_FIELDS: &'static [&'static str] = &["denom_base"];
+
+    fn column_defaults() -> Row {
+        Row::from_iter([("denom_enabled".to_string(), true.into())])
+    }
+}
+
+impl DenomBmc {
+    /// Register the denom, as an admin.
+    pub async fn create(ctx: &Ctx, mm: &ModelManager, denom_c: DenomForCreate) -> Result<i64> {
+        UserBmc::check_admin(ctx, mm).await?;
+
+        base::create::<Self, _>(ctx, mm, denom_c).await
+    }
+
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Denom> {
+        base::get::<Self, _>(ctx, mm, id).await
+    }
+
+    pub async fn list(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        filter: Option<DenomFilter>,
+        list_options: Option<ListOptions>,
+    ) -> Result<Vec<Denom>> {
+        base::list::<Self, _, _>(ctx, mm, filter, list_options).await
+    }
+
+    /// Update the denom, as an admin.
+    pub async fn update(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        id: i64,
+        denom_u: DenomForUpdate,
+    ) -> Result<()> {
+        UserBmc::check_admin(ctx, mm).await?;
+
+        base::update::<Self, _>(ctx, mm, id, None, denom_u).await
+    }
+
+    /// The enabled denom of `value`, its base denom, or its display denom
+    /// or symbol (case insensitive).
+    pub async fn resolve(ctx: &Ctx, mm: &ModelManager, value: &str) -> Result<Denom> {
+        let value = value.trim();
+        let denoms = Self::list(ctx, mm, None, None).await?;
+        let denom = denoms
+            .iter()
+            .find(|d| d.denom_base == value)
+            .or_else(|| {
+                denoms.iter().find(|d| {
+                    d.denom_display.eq_ignore_ascii_case(value)
+                        || (!d.denom_symbol.is_empty()
+                            && d.denom_symbol.eq_ignore_ascii_case(value))
+                })
+            })
+            .cloned()
+            .ok_or_else(|| Error::DenomUnknown {
+                denom: value.to_string(),
+            })?;
+
+        if !denom.denom_enabled {
+            return Err(Error::DenomDisabled {
+                denom: denom.denom_base,
+            });
+        }
+
+        Ok(denom)
+    }
+
+    /// The base units of an amount in `denom`, e.g., "1500000" or
+    /// "1500000 uatom" (base units), or "1.5 ATOM" (display units).
+    pub async fn amount_in(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        value: &str,
+        denom: &Denom,
+    ) -> Result<i64> {
+        let invalid = || Error::DenomInvalidAmount {
+            value: value.to_string(),
+            denom: denom.denom_base.clone(),
+        };
+
+        let mut parts = value.split_whitespace();
+        let (amount, unit) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(amount), unit, None) => (amount, unit),
+            _ => return Err(invalid()),
+        };
+
+        match unit {
+            // (base units)
+            None => base_units(amount).ok_or_else(invalid),
+            Some(unit) => {
+                let unit_denom = Self::resolve(ctx, mm, unit).await?;
+                if unit_denom.denom_base != denom.denom_base {
+                    return Err(Error::DenomMismatch {
+                        expected: denom.denom_base.clone(),
+                        actual: unit_denom.denom_base,
+                    });
+                }
+                if unit == denom.denom_base {
+                    base_units(amount).ok_or_else(invalid)
+                } else {
+                    denom.to_base_units(amount)
+                }
+            }
+        }
+    }
+
+    /// The amount of `value` (see `amount_in`), with its display formatting.
+    pub async fn convert(ctx: &Ctx, mm: &ModelManager, value: &str) -> Result<DenomAmount> {
+        // (the denom is required, e.g., "1.5 ATOM")
+        let unit = value
+            .split_whitespace()
+            .nth(1)
+            .ok_or_else(|| Error::DenomInvalidAmount {
+                value: value.to_string(),
+                denom: String::new(),
+            })?;
+        let denom = Self::resolve(ctx, mm, unit).await?;
+        let amount = Self::amount_in(ctx, mm, value, &denom).await?;
+
+        Ok(DenomAmount {
+            amount: amount.to_string(),
+            display: denom.format(amount),
+            denom: denom.denom_base,
+        })
+    }
+}
+
+fn base_units(value: &str) -> Option<i64> {
+    value.parse::<i64>().ok().filter(|amount| *amount > 0)
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::Seed;
+    use anyhow::{Context, Result};
+
+    fn atom() -> Denom {
+        Denom {
+            id: 0,
+            denom_base: "uatom".to_string(),
+            denom_display: "atom".to_string(),
+            denom_exponent: 6,
+            denom_symbol: "ATOM".to_string(),
+            denom_enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_conversions() -> Result<()> {
+        let atom = atom();
+
+        assert_eq!(atom.to_base_units("1.5")?, 1_500_000);
+        assert_eq!(atom.to_base_units("2")?, 2_000_000);
+        assert_eq!(atom.to_base_units("0.000001")?, 1);
+        assert!(atom.to_base_units("0.0000001").is_err(), "below base unit");
+        assert!(atom.to_base_units("1,5").is_err());
+        assert!(atom.to_base_units("0").is_err());
+
+        assert_eq!(atom.format(1_500_000), "1.5 ATOM");
+        assert_eq!(atom.format(2_000_000), "2 ATOM");
+        assert_eq!(atom.format(1), "0.000001 ATOM");
+        assert_eq!(atom.format(-250_000), "-0.25 ATOM");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_resolve_convert_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        // (uatom and uosmo from the dev seed)
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+
+        // -- Exec & Check
+        for value in ["uatom", "ATOM", "atom", " Atom "] {
+            let denom = DenomBmc::resolve(&ctx, &mm, value).await?;
+            assert_eq!(denom.denom_base, "uatom", "{value}");
+        }
+
+        let amount = DenomBmc::convert(&ctx, &mm, "1.25 ATOM").await?;
+        assert_eq!(amount.amount, "1250000");
+        assert_eq!(amount.denom, "uatom");
+        assert_eq!(amount.display, "1.25 ATOM");
+        let amount = DenomBmc::convert(&ctx, &mm, "1250000 uatom").await?;
+        assert_eq!(amount.display, "1.25 ATOM");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_resolve_err() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let atom = DenomBmc::resolve(&ctx, &mm, "uatom").await?;
+
+        // -- Exec & Check
+        let res = DenomBmc::resolve(&ctx, &mm, "XMR").await;
+        assert!(
+            matches!(&res, Err(Error::DenomUnknown { denom }) if denom == "XMR"),
+            "DenomUnknown not matching"
+        );
+
+        let res = DenomBmc::amount_in(&ctx, &mm, "1 OSMO", &atom).await;
+        assert!(
+            matches!(&res, Err(Error::DenomMismatch { actual, .. }) if actual == "uosmo"),
+            "DenomMismatch not matching"
+        );
+
+        let denom_u = DenomForUpdate {
+            denom_display: None,
+            denom_exponent: None,
+            denom_symbol: None,
+            denom_enabled: Some(false),
+        };
+        DenomBmc::update(&ctx, &mm, atom.id, denom_u).await?;
+        let res = DenomBmc::resolve(&ctx, &mm, "ATOM").await;
+        assert!(
+            matches!(res, Err(Error::DenomDisabled { .. })),
+            "DenomDisabled not matching"
+        );
+
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_update_err_not_admin() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let root_ctx = Ctx::root_ctx();
+        let seed = r#"
+[[users]]
+username = "alice"
+pwd_clear = "alice pwd 01"
+"#;
+        let seeded = toml::from_str::<Seed>(seed)?.apply(&root_ctx, &mm).await?;
+        let ctx = Ctx::new(*seeded.users.get("alice").context("alice")?)?;
+        let atom = DenomBmc::resolve(&ctx, &mm, "uatom").await?;
+        let denom_u = DenomForUpdate {
+            denom_display: None,
+            denom_exponent: None,
+            denom_symbol: None,
+            denom_enabled: Some(false),
+        };
+
+        // -- Exec
+        let res = DenomBmc::update(&ctx, &mm, atom.id, denom_u).await;
+
+        // -- Check
+        assert!(
+            matches!(res, Err(Error::NotAdmin { .. })),
+            "NotAdmin not matching"
+        );
+        assert!(DenomBmc::get(&ctx, &mm, atom.id).await?.denom_enabled);
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
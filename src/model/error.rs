@@ -1,14 +1,182 @@
 use crate::crypt;
+use crate::model::schema_check::SchemaMismatch;
 use crate::model::store;
 use serde::Serialize;
 use serde_with::{serde_as, DisplayFromStr};
+use sqlx::error::ErrorKind;
+use sqlx::postgres::PgDatabaseError;
 
 pub type Result<T> = core::result::Result<T, Error>;
 
 #[serde_as]
 #[derive(Debug, Serialize)]
 pub enum Error {
-    EntityNotFound { entity: &'static str, id: i64 },
+    EntityNotFound {
+        entity: &'static str,
+        id: i64,
+    },
+    EntityNotFoundBy {
+        entity: &'static str,
+        field: &'static str,
+        value: String,
+    },
+    EntityNotTaggable {
+        entity: &'static str,
+    },
+    VersionConflict {
+        entity: &'static str,
+        id: i64,
+        expected: i64,
+        current: i64,
+    },
+    InvalidTransition {
+        entity: &'static str,
+        id: i64,
+        from: String,
+        to: String,
+    },
+
+    // -- User
+    NotAdmin {
+        user_id: i64,
+    },
+
+    // -- Project
+    /// Not the lead of the project (`None`, a bounty without project).
+    NotProjectLead {
+        project_id: Option<i64>,
+        user_id: i64,
+    },
+    /// The denom of a project with a ledger (its accounts being in it).
+    ProjectDenomFrozen {
+        id: i64,
+        denom: String,
+    },
+
+    // -- Bounty Application
+    BountyNotOpen {
+        id: i64,
+        status: String,
+    },
+    BountyNotApproved {
+        id: i64,
+        status: String,
+    },
+    BountyRewardFrozen {
+        id: i64,
+        status: String,
+    },
+    BountyChecklistFrozen {
+        id: i64,
+        status: String,
+    },
+
+    // -- Submission
+    NotBountyAssignee {
+        bounty_id: i64,
+        user_id: i64,
+    },
+    SubmissionChecklistMismatch {
+        missing: Vec<i64>,
+        unknown: Vec<i64>,
+    },
+    ReviewCommentRequired {
+        id: i64,
+    },
+    NotPolicyReviewer {
+        id: i64,
+        user_id: i64,
+    },
+    SubmissionSelfReview {
+        id: i64,
+        user_id: i64,
+    },
+
+    // -- Ledger
+    LedgerInvalidAmount {
+        value: String,
+    },
+    LedgerUnbalanced {
+        sum: i64,
+    },
+    LedgerInsufficientFunds {
+        account: String,
+        balance: i64,
+        amount: i64,
+    },
+
+    // -- Payout
+    PayoutBountyWithoutProject {
+        id: i64,
+    },
+    PayoutLeadMismatch {
+        id: i64,
+        expected: String,
+        actual: String,
+    },
+    PayoutInvalidTxHash {
+        value: String,
+    },
+    PayoutBountyPending {
+        id: i64,
+        payout_id: i64,
+    },
+    PayoutNotSigner {
+        id: i64,
+        user_id: i64,
+    },
+
+    // -- Wallet
+    WalletProofInvalid {
+        address: String,
+        reason: &'static str,
+    },
+
+    // -- Denom
+    DenomUnknown {
+        denom: String,
+    },
+    DenomDisabled {
+        denom: String,
+    },
+    DenomMismatch {
+        expected: String,
+        actual: String,
+    },
+    DenomInvalidAmount {
+        value: String,
+        denom: String,
+    },
+
+    // -- Webhook
+    WebhookUrlNotAllowed {
+        host: String,
+        reason: &'static str,
+    },
+
+    // -- Memory Storage
+    MemoryRowNotAnObject,
+
+    // -- Schema
+    SchemaMismatch(Vec<SchemaMismatch>),
+
+    // -- Db Constraints
+    UniqueViolation {
+        entity: String,
+        field: String,
+    },
+    ForeignKeyViolation {
+        entity: String,
+        constraint: String,
+    },
+    CheckViolation {
+        entity: String,
+        constraint: String,
+    },
+    NotNullViolation {
+        entity: String,
+        field: String,
+    },
 
     // -- Modules
     Crypt(crypt::Error),
@@ -16,6 +184,7 @@ pub enum Error {
 
     // -- Externals
     Sqlx(#[serde_as(as = "DisplayFromStr")] sqlx::Error),
+    SerdeJson(#[serde_as(as = "DisplayFromStr")] serde_json::Error),
 }
 
 // region:    --- Froms
@@ -31,13 +200,61 @@ impl From<store::Error> for Error {
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(val: serde_json::Error) -> Self {
+        Self::SerdeJson(val)
+    }
+}
+
 impl From<sqlx::Error> for Error {
     fn from(val: sqlx::Error) -> Self {
-        Self::Sqlx(val)
+        constraint_violation(&val).unwrap_or(Self::Sqlx(val))
     }
 }
 // endregion: --- Froms
 
+// region:    --- Constraint Violations
+
+/// Decode a Postgres constraint violation into its typed model `Error`.
+/// Returns `None` for any other sqlx error.
+fn constraint_violation(err: &sqlx::Error) -> Option<Error> {
+    let db_err = err.as_database_error()?;
+    let pg_err = db_err.try_downcast_ref::<PgDatabaseError>()?;
+
+    let entity = pg_err.table().unwrap_or_default().to_string();
+    let constraint = pg_err.constraint().unwrap_or_default().to_string();
+
+    let error = match db_err.kind() {
+        ErrorKind::UniqueViolation => Error::UniqueViolation {
+            field: pg_err
+                .detail()
+                .and_then(key_columns_from_detail)
+                .unwrap_or(constraint),
+            entity,
+        },
+        ErrorKind::ForeignKeyViolation => Error::ForeignKeyViolation { entity, constraint },
+        ErrorKind::CheckViolation => Error::CheckViolation { entity, constraint },
+        ErrorKind::NotNullViolation => Error::NotNullViolation {
+            field: pg_err.column().unwrap_or_default().to_string(),
+            entity,
+        },
+        _ => return None,
+    };
+
+    Some(error)
+}
+
+/// Extract the column list from a Postgres violation detail.
+/// e.g., `Key (username)=(demo1) already exists.` -> `username`
+fn key_columns_from_detail(detail: &str) -> Option<String> {
+    let start = detail.strip_prefix("Key (")?;
+    let end = start.find(")=")?;
+
+    Some(start[..end].to_string())
+}
+
+// endregion: --- Constraint Violations
+
 // region:    --- Error Boilerplate
 impl core::fmt::Display for Error {
     fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::result::Result<(), core::fmt::Error> {
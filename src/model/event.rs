@@ -0,0 +1,155 @@
+//! Model Events
+//!
+//! The `EventBus` lives in the `ModelManager` and broadcasts a `ModelEvent`
+//! for every entity change done through the `base` functions
+//! (and for domain state transitions).
+//!
+//! Subscribers (e.g., the `/api/events` SSE route) get their own receiver
+//! and can narrow the stream with an `EventFilter`. The broadcast is lossy
+//! (a lagging subscriber misses events), so the writes that must follow
+//! an event (e.g., the webhook deliveries) are done by an `EventHandler`
+//! instead, in the transaction of the change.
+//!
+//! (the events published outside a transaction, i.e., of the job queue,
+//! are not handled)
+//!
+
+use crate::ctx::Ctx;
+use crate::model::{ModelManager, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+// region:    --- Event Types
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelEvent {
+    pub entity: &'static str,
+    pub id: i64,
+    pub kind: EventKind,
+    /// The user the entity is for (e.g., the notified user), for the
+    /// subscribers to filter on without fetching it.
+    /// (not pushed to the clients)
+    #[serde(skip)]
+    pub user_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, strum_macros::AsRefStr)]
+#[serde(tag = "type", content = "data")]
+pub enum EventKind {
+    Created,
+    Updated,
+    Deleted,
+    Transitioned { from: String, to: String },
+}
+
+impl ModelEvent {
+    pub fn new(entity: &'static str, id: i64, kind: EventKind) -> Self {
+        ModelEvent {
+            entity,
+            id,
+            kind,
+            user_id: None,
+        }
+    }
+
+    pub fn with_user_id(mut self, user_id: Option<i64>) -> Self {
+        self.user_id = user_id;
+        self
+    }
+}
+
+/// Subscription filter, all set properties must match.
+#[derive(Debug, Default, Deserialize)]
+pub struct EventFilter {
+    pub entity: Option<String>,
+    pub id: Option<i64>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, event: &ModelEvent) -> bool {
+        self.entity.as_deref().is_none_or(|e| e == event.entity)
+            && self.id.is_none_or(|id| id == event.id)
+    }
+}
+
+/// A handler of the model events, run before the commit of the transaction
+/// of the change, an error rolling it back (see
+/// `ModelManager::with_event_handler`).
+#[async_trait]
+pub trait EventHandler: Send + Sync {
+    async fn handle(&self, ctx: &Ctx, mm: &ModelManager, event: &ModelEvent) -> Result<()>;
+}
+
+// endregion: --- Event Types
+
+// region:    --- EventBus
+
+#[derive(Clone)]
+pub struct EventBus {
+    tx: broadcast::Sender<ModelEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+
+        EventBus { tx }
+    }
+
+    /// Publish the event to all current subscribers.
+    /// (Having no subscriber is not an error)
+    pub fn publish(&self, event: ModelEvent) {
+        let _ = self.tx.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ModelEvent> {
+        self.tx.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// endregion: --- EventBus
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[tokio::test]
+    async fn test_publish_subscribe_filter_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+        let fx_filter = EventFilter {
+            entity: Some("bounty".to_string()),
+            id: Some(1000),
+        };
+
+        // -- Exec
+        bus.publish(ModelEvent::new("project", 1000, EventKind::Created));
+        bus.publish(ModelEvent::new("bounty", 1001, EventKind::Updated));
+        bus.publish(ModelEvent::new("bounty", 1000, EventKind::Deleted));
+
+        // -- Check
+        let mut matched = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if fx_filter.matches(&event) {
+                matched.push(event);
+            }
+        }
+        assert_eq!(matched.len(), 1, "number of matched events");
+        assert!(matches!(matched[0].kind, EventKind::Deleted));
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
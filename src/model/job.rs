@@ -0,0 +1,579 @@
+//! Durable background job queue.
+//!
+//! Design:
+//!
+//! - A job has a kind (e.g., `search_reindex`) and a JSON payload, typed by
+//!   a `JobPayload` (see the `worker` handlers).
+//! - The workers claim the due queued jobs with `FOR UPDATE SKIP LOCKED`,
+//!   so concurrent workers (or service instances) never run the same job.
+//!   A claim increments the job attempts, and locks it to the worker.
+//! - A failed job is queued again with an exponential backoff, until its
+//!   max attempts where it is `dead` (kept for inspection, and a manual
+//!   `retry`).
+//! - A running job with an expired lock (e.g., its worker crashed) is
+//!   claimable again.
+//! - Only an admin reads (`get`, `list`), retries, or cancels the jobs,
+//!   their payloads and errors being of any project (see
+//!   `UserBmc::check_admin`).
+//! - Times are given by the caller (the worker clock), for the tests.
+//! - Db only (not supported by the in-memory storage).
+//!
+
+use crate::ctx::Ctx;
+use crate::model::base::{DbBmc, ListOptions};
+use crate::model::event::{EventKind, ModelEvent};
+use crate::model::user::UserBmc;
+use crate::model::{Error, ModelManager, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::types::Json;
+use sqlx::FromRow;
+use time::{Duration, OffsetDateTime};
+
+const JOB_MAX_ATTEMPTS_DEFAULT: i32 = 5;
+const BACKOFF_BASE_SEC: i64 = 10;
+const BACKOFF_MAX_SEC: i64 = 3600;
+
+const SQL_JOB_COLUMNS: &str = "id, job_kind, job_payload, job_status, job_attempts, \
+     job_max_attempts, job_run_at, job_locked_at, job_locked_by, job_last_error";
+
+// region:    --- Job Types
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct Job {
+    pub id: i64,
+    pub job_kind: String,
+    pub job_payload: Json<Value>,
+    pub job_status: JobStatus,
+    pub job_attempts: i32,
+    pub job_max_attempts: i32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub job_run_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub job_locked_at: Option<OffsetDateTime>,
+    pub job_locked_by: Option<String>,
+    pub job_last_error: Option<String>,
+}
+
+/// The `job_status` db enum.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type, strum_macros::AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "job_status", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    /// Failed its max attempts.
+    Dead,
+    Cancelled,
+}
+
+/// The payload of a job kind.
+pub trait JobPayload: Serialize + DeserializeOwned + Send {
+    const KIND: &'static str;
+
+    /// Attempts before the job is dead.
+    fn max_attempts() -> i32 {
+        JOB_MAX_ATTEMPTS_DEFAULT
+    }
+}
+
+#[derive(Default, Deserialize)]
+pub struct JobFilter {
+    pub job_kind: Option<String>,
+    pub job_status: Option<JobStatus>,
+}
+
+/// The exponential delay before the next attempt, after `attempts` ones.
+pub fn backoff(attempts: i32) -> Duration {
+    let exp = attempts.clamp(1, 20) as u32 - 1;
+    let secs = BACKOFF_BASE_SEC
+        .saturating_mul(2i64.saturating_pow(exp))
+        .min(BACKOFF_MAX_SEC);
+
+    Duration::seconds(secs)
+}
+
+// endregion: --- Job Types
+
+pub struct JobBmc;
+
+impl DbBmc for JobBmc {
+    const TABLE: &'static str = "job";
+}
+
+impl JobBmc {
+    /// Queue the job of the payload, to run as soon as a worker is free.
+    pub async fn enqueue<P: JobPayload>(ctx: &Ctx, mm: &ModelManager, payload: &P) -> Result<i64> {
+        Self::enqueue_at(ctx, mm, payload, None).await
+    }
+
+    /// Queue the job of the payload, to run not before `run_at`
+    /// (now if none).
+    pub async fn enqueue_at<P: JobPayload>(
+        _ctx: &Ctx,
+        mm: &ModelManager,
+        payload: &P,
+        run_at: Option<OffsetDateTime>,
+    ) -> Result<i64> {
+        let (id,): (i64,) = mm
+            .dbx()?
+            .query_fetch_one(
+                sqlx::query_as(
+                    "INSERT INTO job (job_kind, job_payload, job_max_attempts, job_run_at) \
+                     VALUES ($1, $2, $3, COALESCE($4, now())) RETURNING id",
+                )
+                .bind(P::KIND)
+                .bind(Json(serde_json::to_value(payload)?))
+                .bind(P::max_attempts())
+                .bind(run_at),
+            )
+            .await?;
+        mm.publish(ModelEvent::new(Self::TABLE, id, EventKind::Created));
+
+        Ok(id)
+    }
+
+    /// The job, as an admin.
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Job> {
+        UserBmc::check_admin(ctx, mm).await?;
+
+        let sql = format!("SELECT {SQL_JOB_COLUMNS} FROM job WHERE id = $1");
+        mm.dbx()?
+            .query_fetch_optional(sqlx::query_as(&sql).bind(id))
+            .await?
+            .ok_or(Error::EntityNotFound {
+                entity: Self::TABLE,
+                id,
+            })
+    }
+
+    /// The jobs, latest first, as an admin.
+    pub async fn list(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        filter: Option<JobFilter>,
+        list_options: Option<ListOptions>,
+    ) -> Result<Vec<Job>> {
+        UserBmc::check_admin(ctx, mm).await?;
+
+        let JobFilter {
+            job_kind,
+            job_status,
+        } = filter.unwrap_or_default();
+        let (limit, offset) = ListOptions::limit_offset(list_options);
+
+        let sql = format!(
+            "SELECT {SQL_JOB_COLUMNS} FROM job \
+             WHERE ($1::text IS NULL OR job_kind = $1) \
+               AND ($2::job_status IS NULL OR job_status = $2) \
+             ORDER BY id DESC LIMIT $3 OFFSET $4"
+        );
+        let jobs = mm
+            .dbx()?
+            .query_fetch_all(
+                sqlx::query_as(&sql)
+                    .bind(job_kind)
+                    .bind(job_status)
+                    .bind(limit)
+                    .bind(offset),
+            )
+            .await?;
+
+        Ok(jobs)
+    }
+
+    /// Claim the next due job of the `kinds`, for the `worker`
+    /// (or a running one with its lock before `lock_expired`, dead instead
+    /// if that was its last attempt).
+    pub async fn claim(
+        _ctx: &Ctx,
+        mm: &ModelManager,
+        worker: &str,
+        kinds: &[&str],
+        now: OffsetDateTime,
+        lock_expired: OffsetDateTime,
+    ) -> Result<Option<Job>> {
+        let kinds: Vec<String> = kinds.iter().map(|k| k.to_string()).collect();
+
+        // -- The expired ones of their last attempt are dead.
+        let dead: Vec<(i64,)> = mm
+            .dbx()?
+            .query_fetch_all(
+                sqlx::query_as(
+                    "UPDATE job SET job_status = 'dead', job_locked_at = NULL, job_locked_by = NULL, \
+                                    job_last_error = 'lock expired on the last attempt' \
+                     WHERE job_kind = ANY($1) AND job_status = 'running' AND job_locked_at < $2 \
+                       AND job_attempts >= job_max_attempts \
+                     RETURNING id",
+                )
+                .bind(&kinds)
+                .bind(lock_expired),
+            )
+            .await?;
+        for (id,) in dead {
+            Self::publish_transitioned(mm, id, JobStatus::Running, JobStatus::Dead);
+        }
+
+        let sql = format!(
+            "UPDATE job SET job_status = 'running', job_attempts = job_attempts + 1, \
+                            job_locked_at = $1, job_locked_by = $2 \
+             WHERE id = ( \
+               SELECT id FROM job \
+                WHERE job_kind = ANY($3) \
+                  AND ((job_status = 'queued' AND job_run_at <= $1) \
+                    OR (job_status = 'running' AND job_locked_at < $4 \
+                        AND job_attempts < job_max_attempts)) \
+                ORDER BY job_run_at, id \
+                LIMIT 1 \
+                FOR UPDATE SKIP LOCKED) \
+             RETURNING {SQL_JOB_COLUMNS}"
+        );
+        let job = mm
+            .dbx()?
+            .query_fetch_optional(
+                sqlx::query_as(&sql)
+                    .bind(now)
+                    .bind(worker)
+                    .bind(kinds)
+                    .bind(lock_expired),
+            )
+            .await?;
+
+        Ok(job)
+    }
+
+    /// Mark the job claimed by the `worker` as succeeded.
+    pub async fn complete(ctx: &Ctx, mm: &ModelManager, id: i64, worker: &str) -> Result<()> {
+        Self::release(ctx, mm, id, worker, JobStatus::Succeeded, None, None).await
+    }
+
+    /// Record the failed attempt of the job claimed by the `worker`, queuing
+    /// it again after its backoff, or dead if it was its last attempt.
+    /// Returns the new job status.
+    pub async fn fail(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        job: &Job,
+        worker: &str,
+        error: &str,
+        now: OffsetDateTime,
+    ) -> Result<JobStatus> {
+        let (status, run_at) = if job.job_attempts >= job.job_max_attempts {
+            (JobStatus::Dead, None)
+        } else {
+            (JobStatus::Queued, Some(now + backoff(job.job_attempts)))
+        };
+        Self::release(ctx, mm, job.id, worker, status, Some(error), run_at).await?;
+
+        Ok(status)
+    }
+
+    /// Queue the dead or cancelled job again, with new attempts, as an admin
+    /// (see `get`).
+    pub async fn retry(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        Self::set_status(
+            ctx,
+            mm,
+            id,
+            &[JobStatus::Dead, JobStatus::Cancelled],
+            JobStatus::Queued,
+        )
+        .await
+    }
+
+    /// Cancel the queued job, as an admin (see `get`).
+    pub async fn cancel(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        Self::set_status(ctx, mm, id, &[JobStatus::Queued], JobStatus::Cancelled).await
+    }
+
+    /// Unlock the running job of the `worker`, to the `to` status.
+    /// (a no-op if the lock was lost, e.g., expired and claimed again)
+    async fn release(
+        _ctx: &Ctx,
+        mm: &ModelManager,
+        id: i64,
+        worker: &str,
+        to: JobStatus,
+        error: Option<&str>,
+        run_at: Option<OffsetDateTime>,
+    ) -> Result<()> {
+        let count = mm
+            .dbx()?
+            .query_exec(
+                sqlx::query(
+                    "UPDATE job SET job_status = $3, job_locked_at = NULL, job_locked_by = NULL, \
+                                    job_last_error = COALESCE($4, job_last_error), \
+                                    job_run_at = COALESCE($5, job_run_at) \
+                     WHERE id = $1 AND job_status = 'running' AND job_locked_by = $2",
+                )
+                .bind(id)
+                .bind(worker)
+                .bind(to)
+                .bind(error)
+                .bind(run_at),
+            )
+            .await?;
+
+        if count > 0 {
+            Self::publish_transitioned(mm, id, JobStatus::Running, to);
+        }
+
+        Ok(())
+    }
+
+    async fn set_status(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        id: i64,
+        allowed_from: &[JobStatus],
+        to: JobStatus,
+    ) -> Result<()> {
+        let job = Self::get(ctx, mm, id).await?;
+        let from = job.job_status;
+        if !allowed_from.contains(&from) {
+            return Err(Error::InvalidTransition {
+                entity: Self::TABLE,
+                id,
+                from: from.as_ref().to_string(),
+                to: to.as_ref().to_string(),
+            });
+        }
+
+        // (the status guard against a concurrent change)
+        let count = mm
+            .dbx()?
+            .query_exec(
+                sqlx::query(
+                    "UPDATE job SET job_status = $3, \
+                                    job_attempts = CASE WHEN $3 = 'queued' THEN 0 ELSE job_attempts END, \
+                                    job_run_at = CASE WHEN $3 = 'queued' THEN now() ELSE job_run_at END \
+                     WHERE id = $1 AND job_status = $2",
+                )
+                .bind(id)
+                .bind(from)
+                .bind(to),
+            )
+            .await?;
+        if count == 0 {
+            return Err(Error::InvalidTransition {
+                entity: Self::TABLE,
+                id,
+                from: from.as_ref().to_string(),
+                to: to.as_ref().to_string(),
+            });
+        }
+        Self::publish_transitioned(mm, id, from, to);
+
+        Ok(())
+    }
+
+    fn publish_transitioned(mm: &ModelManager, id: i64, from: JobStatus, to: JobStatus) {
+        mm.publish(ModelEvent::new(
+            Self::TABLE,
+            id,
+            EventKind::Transitioned {
+                from: from.as_ref().to_string(),
+                to: to.as_ref().to_string(),
+            },
+        ));
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use anyhow::Result;
+    use time::macros::datetime;
+
+    #[derive(Serialize, Deserialize)]
+    struct TestPayload {
+        n: i64,
+    }
+
+    impl JobPayload for TestPayload {
+        const KIND: &'static str = "test";
+
+        fn max_attempts() -> i32 {
+            2
+        }
+    }
+
+    const FX_NOW: OffsetDateTime = datetime!(2030-01-01 0:00 UTC);
+
+    async fn claim(mm: &ModelManager, worker: &str, now: OffsetDateTime) -> Result<Option<Job>> {
+        let lock_expired = now - Duration::minutes(5);
+        let job = JobBmc::claim(&Ctx::root_ctx(), mm, worker, &["test"], now, lock_expired).await?;
+
+        Ok(job)
+    }
+
+    #[test]
+    fn test_backoff() {
+        assert_eq!(backoff(1), Duration::seconds(10));
+        assert_eq!(backoff(2), Duration::seconds(20));
+        assert_eq!(backoff(4), Duration::seconds(80));
+        assert_eq!(backoff(30), Duration::seconds(3600), "capped");
+    }
+
+    #[tokio::test]
+    async fn test_claim_complete_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let id = JobBmc::enqueue_at(&ctx, &mm, &TestPayload { n: 1 }, Some(FX_NOW)).await?;
+
+        // -- Exec & Check
+        assert!(
+            claim(&mm, "w1", FX_NOW - Duration::seconds(1))
+                .await?
+                .is_none(),
+            "not due yet"
+        );
+        let job = claim(&mm, "w1", FX_NOW).await?.expect("claimed");
+        assert_eq!(job.id, id);
+        assert_eq!(job.job_status, JobStatus::Running);
+        assert_eq!(job.job_attempts, 1);
+        assert_eq!(job.job_payload.0["n"], 1);
+        assert!(claim(&mm, "w2", FX_NOW).await?.is_none(), "locked");
+
+        JobBmc::complete(&ctx, &mm, id, "w1").await?;
+        let job = JobBmc::get(&ctx, &mm, id).await?;
+        assert_eq!(job.job_status, JobStatus::Succeeded);
+        assert_eq!(job.job_locked_by, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_claim_concurrent_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        for n in 0..5 {
+            JobBmc::enqueue_at(&ctx, &mm, &TestPayload { n }, Some(FX_NOW)).await?;
+        }
+
+        // -- Exec
+        let claims = (0..10).map(|i| {
+            let mm = mm.clone();
+            tokio::spawn(async move { claim(&mm, &format!("w{i}"), FX_NOW).await })
+        });
+        let mut claimed = Vec::new();
+        for claim in claims {
+            claimed.extend(claim.await??.map(|job| job.id));
+        }
+
+        // -- Check
+        assert_eq!(claimed.len(), 5, "all jobs claimed");
+        let count = claimed.len();
+        claimed.sort();
+        claimed.dedup();
+        assert_eq!(claimed.len(), count, "each job claimed once");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fail_retry_dead_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let id = JobBmc::enqueue_at(&ctx, &mm, &TestPayload { n: 1 }, Some(FX_NOW)).await?;
+
+        // -- Exec & Check
+        let job = claim(&mm, "w1", FX_NOW).await?.expect("claimed");
+        let status = JobBmc::fail(&ctx, &mm, &job, "w1", "boom 1", FX_NOW).await?;
+        assert_eq!(status, JobStatus::Queued);
+        let job = JobBmc::get(&ctx, &mm, id).await?;
+        assert_eq!(job.job_run_at, FX_NOW + Duration::seconds(10), "backoff");
+        assert_eq!(job.job_last_error.as_deref(), Some("boom 1"));
+
+        assert!(claim(&mm, "w1", FX_NOW).await?.is_none(), "in backoff");
+        let job = claim(&mm, "w1", FX_NOW + Duration::minutes(1))
+            .await?
+            .expect("claimed");
+        let status = JobBmc::fail(&ctx, &mm, &job, "w1", "boom 2", FX_NOW).await?;
+        assert_eq!(status, JobStatus::Dead, "max attempts");
+
+        JobBmc::retry(&ctx, &mm, id).await?;
+        let job = JobBmc::get(&ctx, &mm, id).await?;
+        assert_eq!(job.job_status, JobStatus::Queued);
+        assert_eq!(job.job_attempts, 0);
+
+        JobBmc::cancel(&ctx, &mm, id).await?;
+        let res = JobBmc::cancel(&ctx, &mm, id).await;
+        assert!(
+            matches!(res, Err(Error::InvalidTransition { .. })),
+            "InvalidTransition not matching"
+        );
+        let jobs = JobBmc::list(
+            &ctx,
+            &mm,
+            Some(JobFilter {
+                job_status: Some(JobStatus::Cancelled),
+                ..Default::default()
+            }),
+            None,
+        )
+        .await?;
+        assert_eq!(jobs.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_claim_lock_expired_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let id = JobBmc::enqueue_at(&ctx, &mm, &TestPayload { n: 1 }, Some(FX_NOW)).await?;
+        claim(&mm, "w1", FX_NOW).await?.expect("claimed");
+
+        // -- Exec
+        let job = claim(&mm, "w2", FX_NOW + Duration::minutes(10))
+            .await?
+            .expect("claimed again");
+
+        // -- Check
+        assert_eq!(job.id, id);
+        assert_eq!(job.job_attempts, 2);
+        assert_eq!(job.job_locked_by.as_deref(), Some("w2"));
+        JobBmc::complete(&ctx, &mm, id, "w1").await?;
+        let job = JobBmc::get(&ctx, &mm, id).await?;
+        assert_eq!(job.job_status, JobStatus::Running, "lock lost by w1");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_claim_lock_expired_dead() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let id = JobBmc::enqueue_at(&ctx, &mm, &TestPayload { n: 1 }, Some(FX_NOW)).await?;
+        claim(&mm, "w1", FX_NOW).await?.expect("claimed");
+        claim(&mm, "w2", FX_NOW + Duration::minutes(10))
+            .await?
+            .expect("claimed again");
+
+        // -- Exec
+        let res = claim(&mm, "w3", FX_NOW + Duration::minutes(20)).await?;
+
+        // -- Check
+        assert!(res.is_none(), "not claimed past max attempts");
+        let job = JobBmc::get(&ctx, &mm, id).await?;
+        assert_eq!(job.job_status, JobStatus::Dead);
+        assert_eq!(job.job_attempts, 2);
+        assert_eq!(job.job_locked_by, None);
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
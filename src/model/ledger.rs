@@ -0,0 +1,878 @@
+//! Double-entry ledger of the project budgets, escrows and payouts.
+//!
+//! Design:
+//!
+//! - Accounts are per project and denom (`project_budget`, `project_escrow`,
+//!   `project_paid`), per contributor address and denom (`contributor`),
+//!   and per denom for the money coming from outside (`external`).
+//! - Each journal entry has lines (positive to debit, negative to credit)
+//!   summing to zero, and is immutable (checked by db triggers as well).
+//!   A balance is the sum of the account lines.
+//! - Entries are posted on:
+//!   - funding: `external` -> `project_budget`,
+//!   - commitment (bounty `Assigned`): `project_budget` -> `project_escrow`,
+//!     failing if the budget is insufficient,
+//!   - approval (bounty `Approved`): `project_escrow` -> `contributor`,
+//!   - payout (bounty `Paid`): `contributor` -> `project_paid`,
+//!   - release (bounty `Cancelled` / `Expired`, or un-assigned back to
+//!     `Open`): `project_escrow` -> `project_budget`.
+//! - Amounts are integers in the denom base unit (e.g., "1500" uatom),
+//!   parsed from the `bounty_reward` string (see `model::denom`), and the
+//!   balances are returned with their display formatting as well.
+//! - Only the projects with a ledger (funded at least once) are accounted,
+//!   so the bounties without a project, of a project never funded, or with
+//!   a legacy free-text reward (e.g., "1500 ATOM", before `model::denom`)
+//!   move without entries.
+//! - The project lead, or an admin, funds the project.
+//! - The denom of a project with a ledger is frozen, its accounts being in
+//!   it (see `ProjectBmc::update`).
+//! - The commitment locks the project row, so concurrent assignments
+//!   cannot overdraw the budget.
+//! - Db only (the in-memory storage has no ledger, its bounties not being
+//!   accounted).
+//!
+
+use crate::ctx::Ctx;
+use crate::model::address;
+use crate::model::base::{self, DbBmc};
+use crate::model::bounty::{Bounty, BountyStatus};
+use crate::model::denom::DenomBmc;
+use crate::model::project::ProjectBmc;
+use crate::model::store::Storage;
+use crate::model::user::UserBmc;
+use crate::model::{Error, ModelManager, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+// region:    --- Ledger Types
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type, strum_macros::AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "ledger_account_kind", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AccountKind {
+    External,
+    ProjectBudget,
+    ProjectEscrow,
+    ProjectPaid,
+    Contributor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "ledger_entry_kind", rename_all = "snake_case")]
+pub enum EntryKind {
+    Funding,
+    Commitment,
+    Approval,
+    Payout,
+    Release,
+}
+
+/// A ledger account reference, created on its first use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Account {
+    External {
+        denom: String,
+    },
+    Project {
+        project_id: i64,
+        kind: AccountKind,
+        denom: String,
+    },
+    Contributor {
+        owner: String,
+        denom: String,
+    },
+}
+
+impl Account {
+    fn key(&self) -> String {
+        match self {
+            Account::External { denom } => format!("external:{denom}"),
+            Account::Project {
+                project_id,
+                kind,
+                denom,
+            } => format!("project:{project_id}:{}:{denom}", kind.as_ref()),
+            Account::Contributor { owner, denom } => format!("contributor:{owner}:{denom}"),
+        }
+    }
+
+    fn kind(&self) -> AccountKind {
+        match self {
+            Account::External { .. } => AccountKind::External,
+            Account::Project { kind, .. } => *kind,
+            Account::Contributor { .. } => AccountKind::Contributor,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct LedgerEntry {
+    pub id: i64,
+    pub entry_kind: EntryKind,
+    pub entry_project_id: i64,
+    pub entry_bounty_id: Option<i64>,
+    pub entry_memo: String,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct AccountBalance {
+    pub account_id: i64,
+    pub account_key: String,
+    pub account_kind: AccountKind,
+    pub account_project_id: Option<i64>,
+    pub account_owner: Option<String>,
+    pub account_denom: String,
+    pub balance: i64,
+    /// e.g., "1.5 ATOM" (none if the denom is not registered)
+    #[sqlx(skip)]
+    pub balance_display: Option<String>,
+}
+
+/// Money added to a project budget, in the project denom.
+#[derive(Deserialize, Validate)]
+pub struct ProjectFunding {
+    #[validate(range(min = 1))]
+    pub amount: i64,
+    #[validate(length(max = 512))]
+    #[serde(default)]
+    pub memo: String,
+}
+
+/// A new entry, its lines summing to zero.
+struct EntryForPost {
+    kind: EntryKind,
+    project_id: i64,
+    bounty_id: Option<i64>,
+    memo: String,
+    lines: Vec<(Account, i64)>,
+}
+
+/// The amount in the denom base unit (a positive integer).
+pub fn parse_amount(value: &str) -> Result<i64> {
+    value
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|amount| *amount > 0)
+        .ok_or_else(|| Error::LedgerInvalidAmount {
+            value: value.to_string(),
+        })
+}
+
+// endregion: --- Ledger Types
+
+pub struct LedgerBmc;
+
+impl DbBmc for LedgerBmc {
+    const TABLE: &'static str = "ledger_entry";
+}
+
+impl LedgerBmc {
+    /// Add the funding amount to the project budget, as the project lead
+    /// or an admin.
+    pub async fn fund_project(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        project_id: i64,
+        funding: ProjectFunding,
+    ) -> Result<i64> {
+        let ProjectFunding { amount, memo } = funding;
+        if amount <= 0 {
+            return Err(Error::LedgerInvalidAmount {
+                value: amount.to_string(),
+            });
+        }
+        let project = if UserBmc::is_admin(ctx, mm).await? {
+            ProjectBmc::get(ctx, mm, project_id).await?
+        } else {
+            ProjectBmc::check_lead(ctx, mm, project_id).await?
+        };
+        let denom = project.project_denom;
+
+        let entry = EntryForPost {
+            kind: EntryKind::Funding,
+            project_id,
+            bounty_id: None,
+            memo,
+            lines: vec![
+                (
+                    Account::Project {
+                        project_id,
+                        kind: AccountKind::ProjectBudget,
+                        denom: denom.clone(),
+                    },
+                    amount,
+                ),
+                (Account::External { denom }, -amount),
+            ],
+        };
+
+        Self::post(ctx, mm, entry).await
+    }
+
+    /// Post the entry of the bounty transition to `to`, if any.
+    /// (called by `BountyBmc` with the bounty state before the transition)
+    pub async fn post_for_bounty(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        bounty: &Bounty,
+        to: BountyStatus,
+    ) -> Result<()> {
+        let Some(project_id) = bounty.bounty_project_id else {
+            return Ok(());
+        };
+        if !Self::has_ledger(ctx, mm, project_id).await? {
+            return Ok(());
+        }
+        let denom = ProjectBmc::get(ctx, mm, project_id).await?.project_denom;
+        let project_account = |kind| Account::Project {
+            project_id,
+            kind,
+            denom: denom.clone(),
+        };
+        let contributor_account = Account::Contributor {
+            owner: bounty.bounty_assignee.clone(),
+            denom: denom.clone(),
+        };
+
+        let (kind, from, to_account, amount) = match to {
+            BountyStatus::Assigned if bounty.bounty_status == BountyStatus::Open => {
+                // (a legacy free-text reward, not accounted)
+                let Ok(amount) = parse_amount(&bounty.bounty_reward) else {
+                    return Ok(());
+                };
+                // (until the commit, not to commit the same budget twice)
+                base::lock::<ProjectBmc>(ctx, mm, project_id).await?;
+                let budget = project_account(AccountKind::ProjectBudget);
+                let balance = Self::balance(ctx, mm, &budget).await?;
+                if balance < amount {
+                    return Err(Error::LedgerInsufficientFunds {
+                        account: budget.key(),
+                        balance,
+                        amount,
+                    });
+                }
+                let escrow = project_account(AccountKind::ProjectEscrow);
+                (EntryKind::Commitment, budget, escrow, amount)
+            }
+            BountyStatus::Approved => {
+                let escrow = project_account(AccountKind::ProjectEscrow);
+                let amount = Self::bounty_balance(ctx, mm, bounty.id, &escrow).await?;
+                (EntryKind::Approval, escrow, contributor_account, amount)
+            }
+            BountyStatus::Paid => {
+                let amount = Self::bounty_balance(ctx, mm, bounty.id, &contributor_account).await?;
+                let paid = project_account(AccountKind::ProjectPaid);
+                (EntryKind::Payout, contributor_account, paid, amount)
+            }
+            BountyStatus::Open | BountyStatus::Cancelled | BountyStatus::Expired => {
+                let escrow = project_account(AccountKind::ProjectEscrow);
+                let amount = Self::bounty_balance(ctx, mm, bounty.id, &escrow).await?;
+                let budget = project_account(AccountKind::ProjectBudget);
+                (EntryKind::Release, escrow, budget, amount)
+            }
+            _ => return Ok(()),
+        };
+
+        // (e.g., nothing in escrow for an open bounty being cancelled)
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let entry = EntryForPost {
+            kind,
+            project_id,
+            bounty_id: Some(bounty.id),
+            memo: format!("bounty {} {}", bounty.id, to.as_ref()),
+            lines: vec![(to_account, amount), (from, -amount)],
+        };
+        Self::post(ctx, mm, entry).await?;
+
+        Ok(())
+    }
+
+    /// The balances of the project accounts.
+    pub async fn project_balances(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        project_id: i64,
+    ) -> Result<Vec<AccountBalance>> {
+        let sql = format!("{SQL_BALANCES} WHERE a.account_project_id = $1 {SQL_BALANCES_END}");
+        let balances = mm
+            .dbx()?
+            .query_fetch_all(sqlx::query_as(&sql).bind(project_id))
+            .await?;
+
+        Self::with_display(ctx, mm, balances).await
+    }
+
+    /// The balances of the contributor (address) accounts.
+    pub async fn contributor_balances(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        owner: &str,
+    ) -> Result<Vec<AccountBalance>> {
+        let sql = format!(
+            "{SQL_BALANCES} WHERE a.account_kind = 'contributor' AND a.account_owner = $1 \
+             {SQL_BALANCES_END}"
+        );
+        let balances = mm
+            .dbx()?
+            .query_fetch_all(sqlx::query_as(&sql).bind(address::normalize(owner)))
+            .await?;
+
+        Self::with_display(ctx, mm, balances).await
+    }
+
+    /// Set the `balance_display` of the balances in a registered denom.
+    async fn with_display(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        mut balances: Vec<AccountBalance>,
+    ) -> Result<Vec<AccountBalance>> {
+        let denoms = DenomBmc::list(ctx, mm, None, None).await?;
+        for balance in balances.iter_mut() {
+            balance.balance_display = denoms
+                .iter()
+                .find(|d| d.denom_base == balance.account_denom)
+                .map(|d| d.format(balance.balance));
+        }
+
+        Ok(balances)
+    }
+
+    /// The journal entries of the project, oldest first.
+    pub async fn list_entries(
+        _ctx: &Ctx,
+        mm: &ModelManager,
+        project_id: i64,
+    ) -> Result<Vec<LedgerEntry>> {
+        let entries = mm
+            .dbx()?
+            .query_fetch_all(
+                sqlx::query_as(
+                    "SELECT id, entry_kind, entry_project_id, entry_bounty_id, entry_memo \
+                     FROM ledger_entry WHERE entry_project_id = $1 ORDER BY id",
+                )
+                .bind(project_id),
+            )
+            .await?;
+
+        Ok(entries)
+    }
+
+    /// Insert the entry and its lines, in a transaction
+    /// (the db checks the balance at commit).
+    async fn post(ctx: &Ctx, mm: &ModelManager, entry: EntryForPost) -> Result<i64> {
+        let sum: i64 = entry.lines.iter().map(|(_, amount)| amount).sum();
+        if sum != 0 {
+            return Err(Error::LedgerUnbalanced { sum });
+        }
+
+        mm.transaction(|mm| async move {
+            let dbx = mm.dbx()?;
+            let (entry_id,): (i64,) = dbx
+                .query_fetch_one(
+                    sqlx::query_as(
+                        "INSERT INTO ledger_entry \
+                         (entry_kind, entry_project_id, entry_bounty_id, entry_memo) \
+                         VALUES ($1, $2, $3, $4) RETURNING id",
+                    )
+                    .bind(entry.kind)
+                    .bind(entry.project_id)
+                    .bind(entry.bounty_id)
+                    .bind(&entry.memo),
+                )
+                .await?;
+
+            for (account, amount) in &entry.lines {
+                let account_id = Self::account_id(ctx, &mm, account).await?;
+                dbx.query_exec(
+                    sqlx::query(
+                        "INSERT INTO ledger_line (line_entry_id, line_account_id, line_amount) \
+                         VALUES ($1, $2, $3)",
+                    )
+                    .bind(entry_id)
+                    .bind(account_id)
+                    .bind(amount),
+                )
+                .await?;
+            }
+
+            Ok(entry_id)
+        })
+        .await
+    }
+
+    /// The id of the account, created if new.
+    async fn account_id(_ctx: &Ctx, mm: &ModelManager, account: &Account) -> Result<i64> {
+        let (project_id, owner, denom) = match account {
+            Account::External { denom } => (None, None, denom),
+            Account::Project {
+                project_id, denom, ..
+            } => (Some(*project_id), None, denom),
+            Account::Contributor { owner, denom } => (None, Some(owner), denom),
+        };
+
+        // (the no-op update makes RETURNING work for an existing account)
+        let (id,): (i64,) = mm
+            .dbx()?
+            .query_fetch_one(
+                sqlx::query_as(
+                    "INSERT INTO ledger_account \
+                     (account_key, account_kind, account_project_id, account_owner, account_denom) \
+                     VALUES ($1, $2, $3, $4, $5) \
+                     ON CONFLICT (account_key) DO UPDATE SET account_key = EXCLUDED.account_key \
+                     RETURNING id",
+                )
+                .bind(account.key())
+                .bind(account.kind())
+                .bind(project_id)
+                .bind(owner)
+                .bind(denom),
+            )
+            .await?;
+
+        Ok(id)
+    }
+
+    /// If the project was funded (see the module design).
+    /// (never on the in-memory storage)
+    pub async fn has_ledger(_ctx: &Ctx, mm: &ModelManager, project_id: i64) -> Result<bool> {
+        if matches!(mm.storage(), Storage::Memory(_)) {
+            return Ok(false);
+        }
+        let (funded,): (bool,) = mm
+            .dbx()?
+            .query_fetch_one(
+                sqlx::query_as(
+                    "SELECT EXISTS (SELECT 1 FROM ledger_entry \
+                     WHERE entry_project_id = $1 AND entry_kind = 'funding')",
+                )
+                .bind(project_id),
+            )
+            .await?;
+
+        Ok(funded)
+    }
+
+    async fn balance(_ctx: &Ctx, mm: &ModelManager, account: &Account) -> Result<i64> {
+        let (balance,): (i64,) = mm
+            .dbx()?
+            .query_fetch_one(
+                sqlx::query_as(
+                    "SELECT COALESCE(sum(l.line_amount), 0)::bigint FROM ledger_line l \
+                     JOIN ledger_account a ON a.id = l.line_account_id WHERE a.account_key = $1",
+                )
+                .bind(account.key()),
+            )
+            .await?;
+
+        Ok(balance)
+    }
+
+    /// The balance of the account, for the entries of the bounty only.
+    async fn bounty_balance(
+        _ctx: &Ctx,
+        mm: &ModelManager,
+        bounty_id: i64,
+        account: &Account,
+    ) -> Result<i64> {
+        let (balance,): (i64,) = mm
+            .dbx()?
+            .query_fetch_one(
+                sqlx::query_as(
+                    "SELECT COALESCE(sum(l.line_amount), 0)::bigint FROM ledger_line l \
+                     JOIN ledger_account a ON a.id = l.line_account_id \
+                     JOIN ledger_entry e ON e.id = l.line_entry_id \
+                     WHERE a.account_key = $1 AND e.entry_bounty_id = $2",
+                )
+                .bind(account.key())
+                .bind(bounty_id),
+            )
+            .await?;
+
+        Ok(balance)
+    }
+}
+
+const SQL_BALANCES: &str = r#"
+SELECT a.id AS account_id, a.account_key, a.account_kind, a.account_project_id,
+       a.account_owner, a.account_denom,
+       COALESCE(sum(l.line_amount), 0)::bigint AS balance
+  FROM ledger_account a LEFT JOIN ledger_line l ON l.line_account_id = a.id"#;
+
+const SQL_BALANCES_END: &str = "GROUP BY a.id ORDER BY a.id";
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::Seed;
+    use crate::model::bounty::BountyBmc;
+    use crate::model::project::ProjectForUpdate;
+    use anyhow::{Context, Result};
+
+    const SEED_TOML: &str = r#"
+[[users]]
+username = "lead"
+pwd_clear = "lead pwd 01"
+
+[[users]]
+username = "alice"
+pwd_clear = "alice pwd 01"
+
+[[users]]
+username = "admin"
+pwd_clear = "admin pwd 01"
+admin = true
+
+[[wallets]]
+username = "lead"
+key = "lead"
+
+# (lead, the address of the `lead` dev wallet)
+[[projects]]
+key = "p1"
+project_id = "ledger-project-01"
+project_name = "Ledger Project 01"
+project_lead_address = "cosmos1gf3c5mgfe6zd0qnmuwy7c5zf6yj5t5ajyn7sth"
+project_budget = "1000"
+project_denom = "uatom"
+project_type = "dev"
+project_description = "An accounted project"
+
+[[bounties]]
+key = "b1"
+project = "p1"
+bounty_title = "Ledger bounty 01"
+bounty_assignee = ""
+bounty_reward = "300"
+bounty_deliverables = "A PR"
+bounty_description = "A paid bounty"
+
+[[bounties]]
+key = "b2"
+project = "p1"
+bounty_title = "Ledger bounty 02"
+bounty_assignee = ""
+bounty_reward = "200"
+bounty_deliverables = "A PR"
+bounty_description = "A cancelled bounty"
+"#;
+
+    fn funding(amount: i64) -> ProjectFunding {
+        ProjectFunding {
+            amount,
+            memo: "funding".to_string(),
+        }
+    }
+
+    /// The balance of the project account of `kind`.
+    fn balance_of(balances: &[AccountBalance], kind: AccountKind) -> i64 {
+        balances
+            .iter()
+            .find(|b| b.account_kind == kind)
+            .map(|b| b.balance)
+            .unwrap_or(0)
+    }
+
+    /// The ledger invariants: each entry, and so the whole ledger, sums to zero.
+    async fn check_zero_sum(mm: &ModelManager) -> Result<()> {
+        let unbalanced: Vec<(i64, i64)> = sqlx::query_as(
+            "SELECT line_entry_id, sum(line_amount)::bigint FROM ledger_line \
+             GROUP BY line_entry_id HAVING sum(line_amount) <> 0",
+        )
+        .fetch_all(mm.db()?)
+        .await?;
+        assert!(unbalanced.is_empty(), "unbalanced entries: {unbalanced:?}");
+
+        let (total,): (i64,) =
+            sqlx::query_as("SELECT COALESCE(sum(line_amount), 0)::bigint FROM ledger_line")
+                .fetch_one(mm.db()?)
+                .await?;
+        assert_eq!(total, 0, "ledger total");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_amount() -> Result<()> {
+        assert_eq!(parse_amount(" 1500 ")?, 1500);
+        assert!(parse_amount("0").is_err());
+        assert!(parse_amount("-5").is_err());
+        assert!(parse_amount("10 ATOM").is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bounty_lifecycle_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let b1 = *seeded.bounties.get("b1").context("b1")?;
+        let b2 = *seeded.bounties.get("b2").context("b2")?;
+        LedgerBmc::fund_project(&ctx, &mm, project_id, funding(1000)).await?;
+
+        // -- Exec
+        BountyBmc::assign(
+            &ctx,
+            &mm,
+            b1,
+            "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn",
+        )
+        .await?;
+        BountyBmc::assign(
+            &ctx,
+            &mm,
+            b2,
+            "cosmos1sxmr0k8u6trd5c6eu6trzyapzux7090y3u5dan",
+        )
+        .await?;
+        for to in [
+            BountyStatus::Submitted,
+            BountyStatus::Approved,
+            BountyStatus::Paid,
+        ] {
+            BountyBmc::transition(&ctx, &mm, b1, to).await?;
+        }
+        BountyBmc::transition(&ctx, &mm, b2, BountyStatus::Cancelled).await?;
+
+        // -- Check
+        let balances = LedgerBmc::project_balances(&ctx, &mm, project_id).await?;
+        assert_eq!(balance_of(&balances, AccountKind::ProjectBudget), 700);
+        assert_eq!(balance_of(&balances, AccountKind::ProjectEscrow), 0);
+        assert_eq!(balance_of(&balances, AccountKind::ProjectPaid), 300);
+        let paid = balances
+            .iter()
+            .find(|b| b.account_kind == AccountKind::ProjectPaid)
+            .context("project_paid")?;
+        assert_eq!(paid.balance_display.as_deref(), Some("0.0003 ATOM"));
+
+        let balances = LedgerBmc::contributor_balances(
+            &ctx,
+            &mm,
+            "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn",
+        )
+        .await?;
+        assert_eq!(balance_of(&balances, AccountKind::Contributor), 0);
+
+        let kinds: Vec<EntryKind> = LedgerBmc::list_entries(&ctx, &mm, project_id)
+            .await?
+            .into_iter()
+            .map(|e| e.entry_kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                EntryKind::Funding,
+                EntryKind::Commitment,
+                EntryKind::Commitment,
+                EntryKind::Approval,
+                EntryKind::Payout,
+                EntryKind::Release,
+            ]
+        );
+
+        check_zero_sum(&mm).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_commitment_err_insufficient_funds() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let b1 = *seeded.bounties.get("b1").context("b1")?;
+        LedgerBmc::fund_project(&ctx, &mm, project_id, funding(100)).await?;
+
+        // -- Exec
+        let res = BountyBmc::assign(
+            &ctx,
+            &mm,
+            b1,
+            "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn",
+        )
+        .await;
+
+        // -- Check
+        assert!(
+            matches!(
+                res,
+                Err(Error::LedgerInsufficientFunds {
+                    balance: 100,
+                    amount: 300,
+                    ..
+                })
+            ),
+            "LedgerInsufficientFunds not matching"
+        );
+        let bounty = BountyBmc::get(&ctx, &mm, b1).await?;
+        assert_eq!(bounty.bounty_status, BountyStatus::Open, "rolled back");
+
+        check_zero_sum(&mm).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_commitment_concurrent_no_overdraw() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let b1 = *seeded.bounties.get("b1").context("b1")?;
+        let b2 = *seeded.bounties.get("b2").context("b2")?;
+        // (for one of the b1 (300) and b2 (200) commitments only)
+        LedgerBmc::fund_project(&ctx, &mm, project_id, funding(300)).await?;
+
+        // -- Exec
+        let (res1, res2) = tokio::join!(
+            BountyBmc::assign(
+                &ctx,
+                &mm,
+                b1,
+                "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn"
+            ),
+            BountyBmc::assign(
+                &ctx,
+                &mm,
+                b2,
+                "cosmos1sxmr0k8u6trd5c6eu6trzyapzux7090y3u5dan"
+            ),
+        );
+
+        // -- Check
+        assert!(
+            res1.is_ok() != res2.is_ok(),
+            "a single commitment: {res1:?} {res2:?}"
+        );
+        let balances = LedgerBmc::project_balances(&ctx, &mm, project_id).await?;
+        assert!(balance_of(&balances, AccountKind::ProjectBudget) >= 0);
+        check_zero_sum(&mm).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unfunded_project_not_accounted() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let b1 = *seeded.bounties.get("b1").context("b1")?;
+
+        // -- Exec
+        BountyBmc::assign(
+            &ctx,
+            &mm,
+            b1,
+            "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn",
+        )
+        .await?;
+        BountyBmc::transition(&ctx, &mm, b1, BountyStatus::Cancelled).await?;
+
+        // -- Check
+        let entries = LedgerBmc::list_entries(&ctx, &mm, project_id).await?;
+        assert!(entries.is_empty(), "no ledger, no entries");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fund_project_lead_or_admin() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?
+            .apply(&Ctx::root_ctx(), &mm)
+            .await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let user_ctx = |username| -> Result<Ctx> {
+            Ok(Ctx::new(*seeded.users.get(username).context("user")?)?)
+        };
+
+        // -- Exec & Check - not the lead
+        let alice_ctx = user_ctx("alice")?;
+        let res = LedgerBmc::fund_project(&alice_ctx, &mm, project_id, funding(100)).await;
+        assert!(
+            matches!(res, Err(Error::NotProjectLead { .. })),
+            "NotProjectLead not matching"
+        );
+
+        // -- Exec & Check - the lead, and an admin
+        LedgerBmc::fund_project(&user_ctx("lead")?, &mm, project_id, funding(100)).await?;
+        LedgerBmc::fund_project(&user_ctx("admin")?, &mm, project_id, funding(50)).await?;
+        let balances = LedgerBmc::project_balances(&alice_ctx, &mm, project_id).await?;
+        assert_eq!(balance_of(&balances, AccountKind::ProjectBudget), 150);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_project_denom_frozen() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let denom_u = |denom: &str| ProjectForUpdate {
+            project_denom: Some(denom.to_string()),
+            ..Default::default()
+        };
+
+        // -- Exec & Check - no ledger yet
+        ProjectBmc::update(&ctx, &mm, project_id, denom_u("uosmo")).await?;
+        ProjectBmc::update(&ctx, &mm, project_id, denom_u("uatom")).await?;
+
+        // -- Exec & Check - funded
+        LedgerBmc::fund_project(&ctx, &mm, project_id, funding(100)).await?;
+        let res = ProjectBmc::update(&ctx, &mm, project_id, denom_u("uosmo")).await;
+        assert!(
+            matches!(&res, Err(Error::ProjectDenomFrozen { denom, .. }) if denom == "uatom"),
+            "ProjectDenomFrozen not matching"
+        );
+        // (the same denom is not a change)
+        ProjectBmc::update(&ctx, &mm, project_id, denom_u("uatom")).await?;
+        let project = ProjectBmc::get(&ctx, &mm, project_id).await?;
+        assert_eq!(project.project_denom, "uatom");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_entries_immutable() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let entry_id = LedgerBmc::fund_project(&ctx, &mm, project_id, funding(100)).await?;
+
+        // -- Exec
+        let res = sqlx::query("UPDATE ledger_line SET line_amount = 1 WHERE line_entry_id = $1")
+            .bind(entry_id)
+            .execute(mm.db()?)
+            .await;
+
+        // -- Check
+        assert!(res.is_err(), "ledger lines should be immutable");
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
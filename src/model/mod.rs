@@ -19,35 +19,370 @@
 
 // region:    --- Modules
 
+pub mod address;
+pub mod application;
+pub mod approval;
 mod base;
 pub mod bounty;
+pub mod deliverable;
+pub mod denom;
 mod error;
+pub mod event;
+pub mod job;
+pub mod ledger;
+pub mod notification;
+pub mod payout;
 pub mod project;
-mod store;
+pub mod schema_check;
+pub mod search;
+pub mod store;
+pub mod submission;
+pub mod tag;
 pub mod user;
+pub mod wallet;
+pub mod webhook;
 
+pub use self::base::ListOptions;
 pub use self::error::{Error, Result};
 
-use crate::model::store::{new_db_pool, Db};
+use crate::config;
+use crate::ctx::Ctx;
+use crate::model::event::{EventBus, EventHandler, ModelEvent};
+use crate::model::store::dbx::Dbx;
+use crate::model::store::memory::MemStore;
+use crate::model::store::migration::{self, MigrationStatus};
+use crate::model::store::{new_db_pool, Db, Storage};
+use std::any::Any;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
 
 // endregion: --- Modules
 
 #[derive(Clone)]
 pub struct ModelManager {
-    db: Db,
+    storage: Storage,
+    events: EventBus,
+    /// If the Bmc functions run in a transaction (see `transaction`).
+    with_txn: bool,
+    /// Events emitted inside the open (nested) transactions,
+    /// only published once the outermost one commits.
+    txn_events: Arc<Mutex<Vec<Vec<ModelEvent>>>>,
+    /// Run on the events of a transaction, before its commit
+    /// (see `with_event_handler`).
+    event_handlers: Arc<Vec<Arc<dyn EventHandler>>>,
+    /// Dropped with the last clone (e.g., the test db cleanup of `_dev_utils`).
+    drop_guard: Option<Arc<dyn Any + Send + Sync>>,
 }
 
 impl ModelManager {
     /// Constructor
     pub async fn new() -> Result<Self> {
-        let db = new_db_pool().await?;
+        Self::new_with_db_url(&config().DB_URL).await
+    }
+
+    /// Constructor on another db than the `DB_URL` one (e.g., a test db).
+    pub async fn new_with_db_url(db_url: &str) -> Result<Self> {
+        let db = new_db_pool(db_url).await?;
 
-        Ok(ModelManager { db })
+        Ok(Self::with_storage(Storage::Db(Dbx::new(db, false))))
     }
 
-    /// Returns the sqlx db pool reference.
+    /// A ModelManager on the in-memory storage (empty, without schema),
+    /// for tests and demos without a db.
+    /// (migrations and raw sql functions are not available)
+    pub fn new_in_memory() -> Self {
+        Self::with_storage(Storage::Memory(MemStore::default()))
+    }
+
+    fn with_storage(storage: Storage) -> Self {
+        ModelManager {
+            storage,
+            events: EventBus::new(),
+            with_txn: false,
+            txn_events: Arc::default(),
+            event_handlers: Arc::default(),
+            drop_guard: None,
+        }
+    }
+
+    /// Keep `guard` alive as long as this ModelManager (or a clone) is.
+    pub fn with_drop_guard(mut self, guard: impl Any + Send + Sync) -> Self {
+        self.drop_guard = Some(Arc::new(guard));
+        self
+    }
+
+    /// Run `handler` on each event, in the transaction of the change
+    /// (as an outbox, its writes commit or roll back with the change).
+    pub fn with_event_handler(mut self, handler: impl EventHandler + 'static) -> Self {
+        Arc::make_mut(&mut self.event_handlers).push(Arc::new(handler));
+        self
+    }
+
+    /// A ModelManager on which all the Bmc functions run in a transaction.
+    /// (if already in a transaction, shares it)
+    pub fn new_with_txn(&self) -> ModelManager {
+        if self.with_txn {
+            return self.clone();
+        }
+
+        let storage = match &self.storage {
+            Storage::Db(dbx) => Storage::Db(Dbx::new(dbx.db().clone(), true)),
+            Storage::Memory(mem) => Storage::Memory(mem.new_with_txn()),
+        };
+
+        ModelManager {
+            storage,
+            events: self.events.clone(),
+            with_txn: true,
+            txn_events: Arc::default(),
+            event_handlers: self.event_handlers.clone(),
+            drop_guard: self.drop_guard.clone(),
+        }
+    }
+
+    /// Run `f` in a transaction, committed if `f` returns `Ok`,
+    /// rolled back otherwise.
+    ///
+    /// Calls can be nested (e.g., a Bmc function using a transaction called from
+    /// an RPC handler transaction), the inner ones being savepoints.
+    pub async fn transaction<F, Fut, T, E>(&self, f: F) -> core::result::Result<T, E>
+    where
+        F: FnOnce(ModelManager) -> Fut,
+        Fut: Future<Output = core::result::Result<T, E>>,
+        E: From<Error>,
+    {
+        let mm = self.new_with_txn();
+        mm.begin_txn().await?;
+
+        match f(mm.clone()).await {
+            Ok(val) => {
+                mm.commit_txn().await?;
+                Ok(val)
+            }
+            Err(ex) => {
+                mm.rollback_txn().await?;
+                Err(ex)
+            }
+        }
+    }
+
+    /// Run `f` in the open transaction, or in a new one if there are event
+    /// handlers (e.g., for a single write, to commit with the handling of
+    /// its event).
+    pub(in crate::model) async fn in_txn<F, Fut, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(ModelManager) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        if self.with_txn || self.event_handlers.is_empty() {
+            f(self.clone()).await
+        } else {
+            self.transaction(f).await
+        }
+    }
+
+    async fn begin_txn(&self) -> Result<()> {
+        match &self.storage {
+            Storage::Db(dbx) => dbx.begin_txn().await?,
+            Storage::Memory(mem) => mem.begin_txn(),
+        }
+        self.txn_events().push(Vec::new());
+
+        Ok(())
+    }
+
+    async fn commit_txn(&self) -> Result<()> {
+        if self.txn_events().len() == 1 {
+            self.handle_txn_events().await?;
+        }
+
+        match &self.storage {
+            Storage::Db(dbx) => dbx.commit_txn().await?,
+            Storage::Memory(mem) => mem.commit_txn()?,
+        }
+
+        let mut txn_events = self.txn_events();
+        let events = txn_events.pop().unwrap_or_default();
+        match txn_events.last_mut() {
+            Some(parent) => parent.extend(events),
+            None => events.into_iter().for_each(|e| self.events.publish(e)),
+        }
+
+        Ok(())
+    }
+
+    async fn rollback_txn(&self) -> Result<()> {
+        match &self.storage {
+            Storage::Db(dbx) => dbx.rollback_txn().await?,
+            Storage::Memory(mem) => mem.rollback_txn()?,
+        }
+        self.txn_events().pop();
+
+        Ok(())
+    }
+
+    /// Run the event handlers on the events of the outermost transaction,
+    /// including the ones of their own writes.
+    async fn handle_txn_events(&self) -> Result<()> {
+        let ctx = Ctx::root_ctx();
+        let mut handled = 0;
+        loop {
+            let event = match self
+                .txn_events()
+                .first()
+                .and_then(|events| events.get(handled))
+            {
+                Some(event) => event.clone(),
+                None => break,
+            };
+            for handler in self.event_handlers.iter() {
+                handler.handle(&ctx, self, &event).await?;
+            }
+            handled += 1;
+        }
+
+        Ok(())
+    }
+
+    fn txn_events(&self) -> std::sync::MutexGuard<'_, Vec<Vec<ModelEvent>>> {
+        self.txn_events.lock().unwrap_or_else(|ex| ex.into_inner())
+    }
+
+    /// Apply the pending migrations of `DB_MIGRATIONS_DIR`.
+    pub async fn migrate(&self) -> Result<Vec<i64>> {
+        Ok(migration::migrate(self.db()?, &config().DB_MIGRATIONS_DIR).await?)
+    }
+
+    /// Roll back the last applied migration.
+    pub async fn rollback(&self) -> Result<Option<i64>> {
+        Ok(migration::rollback(self.db()?, &config().DB_MIGRATIONS_DIR).await?)
+    }
+
+    pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>> {
+        Ok(migration::status(self.db()?, &config().DB_MIGRATIONS_DIR).await?)
+    }
+
+    /// Returns the event bus, to subscribe to the model events.
+    pub fn events(&self) -> &EventBus {
+        &self.events
+    }
+
+    /// Publish a model event to the event bus subscribers.
+    /// (In a transaction, deferred until the commit)
+    pub fn publish(&self, event: ModelEvent) {
+        if self.with_txn {
+            if let Some(events) = self.txn_events().last_mut() {
+                events.push(event);
+                return;
+            }
+        }
+
+        self.events.publish(event);
+    }
+
+    /// Returns the storage (db or in-memory).
     /// (Only for the model layer)
-    pub(in crate::model) fn db(&self) -> &Db {
-        &self.db
+    pub(in crate::model) fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    /// Returns the db executor (pool or transaction).
+    /// (Only for the model layer, for the db only functions)
+    pub(in crate::model) fn dbx(&self) -> Result<&Dbx> {
+        match &self.storage {
+            Storage::Db(dbx) => Ok(dbx),
+            Storage::Memory(_) => Err(store::Error::NoDbInMemoryStorage.into()),
+        }
+    }
+
+    /// Returns the sqlx db pool reference.
+    /// (Only for the model layer, does not see the open transaction)
+    pub(in crate::model) fn db(&self) -> Result<&Db> {
+        Ok(self.dbx()?.db())
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::ctx::Ctx;
+    use crate::model::bounty::{BountyBmc, BountyFilter};
+    use anyhow::{anyhow, Result};
+
+    async fn count_titled(mm: &ModelManager, title: &str) -> Result<usize> {
+        let filter = BountyFilter {
+            bounty_title: Some(title.to_string()),
+            ..Default::default()
+        };
+        let bounties = BountyBmc::list(&Ctx::root_ctx(), mm, Some(filter), None).await?;
+
+        Ok(bounties.len())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_rollback_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = &Ctx::root_ctx();
+        let fx_title = "test_transaction_rollback_ok";
+        let mut events = mm.events().subscribe();
+
+        // -- Exec
+        let res: Result<()> = mm
+            .transaction(|mm| async move {
+                _dev_utils::seed_tasks(ctx, &mm, &[fx_title]).await?;
+                Err(anyhow!("fail after create"))
+            })
+            .await;
+
+        // -- Check
+        assert!(res.is_err(), "transaction should fail");
+        assert_eq!(count_titled(&mm, fx_title).await?, 0);
+        assert!(
+            events.try_recv().is_err(),
+            "no event for rolled back create"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_nested_savepoint_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = &Ctx::root_ctx();
+        let fx_outer_title = "test_transaction_nested_savepoint_ok - outer";
+        let fx_inner_title = "test_transaction_nested_savepoint_ok - inner";
+
+        // -- Exec
+        let outer_bounty = mm
+            .transaction(|mm| async move {
+                let outer = _dev_utils::seed_tasks(ctx, &mm, &[fx_outer_title])
+                    .await?
+                    .remove(0);
+
+                let inner_res: Result<()> = mm
+                    .transaction(|mm| async move {
+                        _dev_utils::seed_tasks(ctx, &mm, &[fx_inner_title]).await?;
+                        Err(anyhow!("fail inner"))
+                    })
+                    .await;
+                assert!(inner_res.is_err(), "inner transaction should fail");
+
+                Ok::<_, anyhow::Error>(outer)
+            })
+            .await?;
+
+        // -- Check
+        assert_eq!(count_titled(&mm, fx_outer_title).await?, 1);
+        assert_eq!(count_titled(&mm, fx_inner_title).await?, 0);
+
+        // -- Clean
+        BountyBmc::delete(ctx, &mm, outer_bounty.id).await?;
+
+        Ok(())
     }
 }
+// endregion: --- Tests
@@ -0,0 +1,768 @@
+//! In-app notification inbox of the users.
+//!
+//! Design:
+//!
+//! - Notifications are generated from the model events by the `Notifier`
+//!   event handler (see `main`), in the transaction of the change (so none
+//!   is lost, nor generated for a rolled back change):
+//!   - `assignment`: an application of the user accepted, rejected or
+//!     lapsed (generated in the transaction of the decision instead, see
+//!     `notify_decision`),
+//!   - `review`: a submission of the user approved, sent back for changes,
+//!     or rejected,
+//!   - `comment`: the review comment on a submission of the user,
+//!   - `payout`: a bounty assigned to the user paid (the user of the
+//!     accepted application, or of the linked wallet of the assignee),
+//!   - `mention`: `@username` in an application message, submission notes,
+//!     or review comment (not notifying its author).
+//! - A user disables kinds in its preferences (all enabled by default),
+//!   the disabled ones not being generated.
+//! - The users only see, and mark read, their own notifications (the
+//!   `Ctx` user), also pushed on `/api/events/notifications` (their events
+//!   carrying the notified user, see `ModelEvent::user_id`).
+//!
+
+use crate::ctx::Ctx;
+use crate::model::application::{
+    Application, ApplicationBmc, ApplicationFilter, ApplicationStatus,
+};
+use crate::model::base::{self, DbBmc, ListOptions};
+use crate::model::bounty::{BountyBmc, BountyStatus};
+use crate::model::event::{EventHandler, EventKind, ModelEvent};
+use crate::model::store::memory::Row;
+use crate::model::store::Storage;
+use crate::model::submission::SubmissionBmc;
+use crate::model::user::{User, UserBmc};
+use crate::model::wallet::WalletBmc;
+use crate::model::{Error, ModelManager, Result};
+use crate::utils::now_utc;
+use async_trait::async_trait;
+use lazy_regex::regex;
+use serde::{Deserialize, Serialize};
+use sqlb::Fields;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+/// Max mentions notified per text.
+const MENTIONS_MAX: usize = 20;
+
+// region:    --- Notification Types
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct Notification {
+    pub id: i64,
+    pub notification_user_id: i64,
+    pub notification_kind: NotificationKind,
+    pub notification_entity: String,
+    pub notification_entity_id: i64,
+    pub notification_message: String,
+    pub notification_read: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub notification_ctime: OffsetDateTime,
+}
+
+/// The `notification_kind` db enum.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type, strum_macros::AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "notification_kind", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum NotificationKind {
+    Assignment,
+    Review,
+    Payout,
+    Comment,
+    Mention,
+}
+sqlb::bindable!(NotificationKind);
+
+#[derive(Serialize, Fields)]
+pub struct NotificationForInsert {
+    pub notification_user_id: i64,
+    pub notification_kind: NotificationKind,
+    pub notification_entity: String,
+    pub notification_entity_id: i64,
+    pub notification_message: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub notification_ctime: OffsetDateTime,
+}
+
+#[derive(Serialize, Fields)]
+pub struct NotificationForRead {
+    pub notification_read: bool,
+}
+
+#[derive(Default, Deserialize, Serialize, Fields)]
+pub struct NotificationFilter {
+    pub notification_kind: Option<NotificationKind>,
+    pub notification_read: Option<bool>,
+
+    /// (always the `Ctx` user)
+    #[serde(skip_deserializing)]
+    pub notification_user_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationCount {
+    pub unread: i64,
+}
+
+/// The notification preferences of a user.
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct NotificationPrefs {
+    pub pref_assignment: bool,
+    pub pref_review: bool,
+    pub pref_payout: bool,
+    pub pref_comment: bool,
+    pub pref_mention: bool,
+}
+
+/// The preferences row, to update it.
+#[derive(Debug, Fields, FromRow, Deserialize)]
+pub struct NotificationPrefsRow {
+    pub id: i64,
+    pub pref_user_id: i64,
+}
+
+#[derive(Default, Deserialize, Serialize, Fields)]
+pub struct NotificationPrefsForUpdate {
+    pub pref_assignment: Option<bool>,
+    pub pref_review: Option<bool>,
+    pub pref_payout: Option<bool>,
+    pub pref_comment: Option<bool>,
+    pub pref_mention: Option<bool>,
+}
+
+/// `NotificationPrefsForUpdate` with the user (the `Ctx` user).
+#[derive(Serialize, Fields)]
+pub struct NotificationPrefsForInsert {
+    pub pref_user_id: i64,
+    pub pref_assignment: Option<bool>,
+    pub pref_review: Option<bool>,
+    pub pref_payout: Option<bool>,
+    pub pref_comment: Option<bool>,
+    pub pref_mention: Option<bool>,
+}
+
+impl Default for NotificationPrefs {
+    fn default() -> Self {
+        NotificationPrefs {
+            pref_assignment: true,
+            pref_review: true,
+            pref_payout: true,
+            pref_comment: true,
+            pref_mention: true,
+        }
+    }
+}
+
+impl NotificationPrefs {
+    pub fn enabled(&self, kind: NotificationKind) -> bool {
+        match kind {
+            NotificationKind::Assignment => self.pref_assignment,
+            NotificationKind::Review => self.pref_review,
+            NotificationKind::Payout => self.pref_payout,
+            NotificationKind::Comment => self.pref_comment,
+            NotificationKind::Mention => self.pref_mention,
+        }
+    }
+}
+
+// endregion: --- Notification Types
+
+pub struct NotificationBmc;
+
+impl DbBmc for NotificationBmc {
+    const TABLE: &'static str = "notification";
+
+    fn column_defaults() -> Row {
+        Row::from_iter([("notification_read".to_string(), false.into())])
+    }
+}
+
+impl NotificationBmc {
+    /// Notify the user, unless the kind is disabled in its preferences.
+    /// Returns the notification id, if notified.
+    pub async fn notify(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        user_id: i64,
+        kind: NotificationKind,
+        (entity, entity_id): (&str, i64),
+        message: String,
+    ) -> Result<Option<i64>> {
+        let prefs = NotificationPrefsBmc::get_for_user(ctx, mm, user_id).await?;
+        if !prefs.enabled(kind) {
+            return Ok(None);
+        }
+
+        let notification_i = NotificationForInsert {
+            notification_user_id: user_id,
+            notification_kind: kind,
+            notification_entity: entity.to_string(),
+            notification_entity_id: entity_id,
+            notification_message: message,
+            notification_ctime: now_utc(),
+        };
+        let id = base::create_for_user::<Self, _>(ctx, mm, Some(user_id), notification_i).await?;
+
+        Ok(Some(id))
+    }
+
+    /// A notification of the `Ctx` user.
+    /// (not found for the notifications of the other users)
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Notification> {
+        let notification: Notification = base::get::<Self, _>(ctx, mm, id).await?;
+        if notification.notification_user_id != ctx.user_id() {
+            return Err(Error::EntityNotFound {
+                entity: Self::TABLE,
+                id,
+            });
+        }
+
+        Ok(notification)
+    }
+
+    /// The notifications of the `Ctx` user.
+    pub async fn list(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        filter: Option<NotificationFilter>,
+        list_options: Option<ListOptions>,
+    ) -> Result<Vec<Notification>> {
+        let filter = NotificationFilter {
+            notification_user_id: Some(ctx.user_id()),
+            ..filter.unwrap_or_default()
+        };
+        base::list::<Self, _, _>(ctx, mm, Some(filter), list_options).await
+    }
+
+    pub async fn unread_count(ctx: &Ctx, mm: &ModelManager) -> Result<NotificationCount> {
+        let unread = match mm.storage() {
+            Storage::Db(dbx) => {
+                let (unread,): (i64,) = dbx
+                    .query_fetch_one(
+                        sqlx::query_as(
+                            "SELECT count(*) FROM notification \
+                             WHERE notification_user_id = $1 AND NOT notification_read",
+                        )
+                        .bind(ctx.user_id()),
+                    )
+                    .await?;
+                unread
+            }
+            Storage::Memory(_) => {
+                let filter = NotificationFilter {
+                    notification_read: Some(false),
+                    ..Default::default()
+                };
+                Self::list(ctx, mm, Some(filter), None).await?.len() as i64
+            }
+        };
+
+        Ok(NotificationCount { unread })
+    }
+
+    /// Mark the notifications of the `Ctx` user read.
+    pub async fn mark_read(ctx: &Ctx, mm: &ModelManager, ids: &[i64]) -> Result<()> {
+        mm.transaction(|mm| async move {
+            for &id in ids {
+                let notification = Self::get(ctx, &mm, id).await?;
+                if !notification.notification_read {
+                    Self::set_read(ctx, &mm, id).await?;
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Mark all the notifications of the `Ctx` user read.
+    /// Returns the number of notifications marked.
+    pub async fn mark_all_read(ctx: &Ctx, mm: &ModelManager) -> Result<usize> {
+        mm.transaction(|mm| async move {
+            let ids: Vec<(i64,)> = match mm.storage() {
+                Storage::Db(dbx) => {
+                    dbx.query_fetch_all(
+                        sqlx::query_as(
+                            "UPDATE notification SET notification_read = true \
+                             WHERE notification_user_id = $1 AND NOT notification_read \
+                             RETURNING id",
+                        )
+                        .bind(ctx.user_id()),
+                    )
+                    .await?
+                }
+                Storage::Memory(_) => {
+                    let filter = NotificationFilter {
+                        notification_read: Some(false),
+                        ..Default::default()
+                    };
+                    let unread = Self::list(ctx, &mm, Some(filter), None).await?;
+                    for notification in &unread {
+                        Self::set_read(ctx, &mm, notification.id).await?;
+                    }
+                    return Ok(unread.len());
+                }
+            };
+            for &(id,) in &ids {
+                let event = ModelEvent::new(Self::TABLE, id, EventKind::Updated);
+                mm.publish(event.with_user_id(Some(ctx.user_id())));
+            }
+
+            Ok(ids.len())
+        })
+        .await
+    }
+
+    async fn set_read(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        let notification_r = NotificationForRead {
+            notification_read: true,
+        };
+        base::update::<Self, _>(ctx, mm, id, None, notification_r).await
+    }
+}
+
+pub struct NotificationPrefsBmc;
+
+impl DbBmc for NotificationPrefsBmc {
+    const TABLE: &'static str = "notification_pref";
+    const UNIQUE_FIELDS: &'static [&'static str] = &["pref_user_id"];
+
+    fn column_defaults() -> Row {
+        let NotificationPrefs {
+            pref_assignment,
+            pref_review,
+            pref_payout,
+            pref_comment,
+            pref_mention,
+        } = NotificationPrefs::default();
+        Row::from_iter([
+            ("pref_assignment".to_string(), pref_assignment.into()),
+            ("pref_review".to_string(), pref_review.into()),
+            ("pref_payout".to_string(), pref_payout.into()),
+            ("pref_comment".to_string(), pref_comment.into()),
+            ("pref_mention".to_string(), pref_mention.into()),
+        ])
+    }
+}
+
+impl NotificationPrefsBmc {
+    /// The preferences of the user (the defaults if never set).
+    pub async fn get_for_user(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        user_id: i64,
+    ) -> Result<NotificationPrefs> {
+        let prefs = base::first_by::<Self, _, _>(ctx, mm, "pref_user_id", user_id).await?;
+
+        Ok(prefs.unwrap_or_default())
+    }
+
+    /// Set the preferences of the `Ctx` user (the unset ones unchanged).
+    pub async fn set(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        prefs_u: NotificationPrefsForUpdate,
+    ) -> Result<()> {
+        let user_id = ctx.user_id();
+        let row: Option<NotificationPrefsRow> =
+            base::first_by::<Self, _, _>(ctx, mm, "pref_user_id", user_id).await?;
+
+        match row {
+            Some(row) => base::update::<Self, _>(ctx, mm, row.id, None, prefs_u).await,
+            None => {
+                let prefs_i = NotificationPrefsForInsert {
+                    pref_user_id: user_id,
+                    pref_assignment: prefs_u.pref_assignment,
+                    pref_review: prefs_u.pref_review,
+                    pref_payout: prefs_u.pref_payout,
+                    pref_comment: prefs_u.pref_comment,
+                    pref_mention: prefs_u.pref_mention,
+                };
+                base::create::<Self, _>(ctx, mm, prefs_i).await.map(|_| ())
+            }
+        }
+    }
+}
+
+// region:    --- Notifier
+
+/// Generates the notifications of the model events, in their transaction
+/// (see `ModelManager::with_event_handler`).
+pub struct Notifier;
+
+#[async_trait]
+impl EventHandler for Notifier {
+    async fn handle(&self, ctx: &Ctx, mm: &ModelManager, event: &ModelEvent) -> Result<()> {
+        notify_event(ctx, mm, event).await?;
+
+        Ok(())
+    }
+}
+
+/// Generate the notifications of the event.
+/// Returns the notification ids.
+pub async fn notify_event(ctx: &Ctx, mm: &ModelManager, event: &ModelEvent) -> Result<Vec<i64>> {
+    let mut notifications: Vec<(i64, NotificationKind, String)> = Vec::new();
+
+    match (event.entity, &event.kind) {
+        ("bounty_application", EventKind::Created) => {
+            let application = ApplicationBmc::get(ctx, mm, event.id).await?;
+            let title = bounty_title(ctx, mm, application.application_bounty_id).await?;
+            let message = format!("You were mentioned in an application to \"{title}\"");
+            for user_id in mentions(ctx, mm, &application.application_message).await? {
+                if user_id != application.application_applicant_id {
+                    notifications.push((user_id, NotificationKind::Mention, message.clone()));
+                }
+            }
+        }
+        ("submission", EventKind::Created) => {
+            let submission = SubmissionBmc::get(ctx, mm, event.id).await?;
+            let title = bounty_title(ctx, mm, submission.submission_bounty_id).await?;
+            let message = format!("You were mentioned in a submission to \"{title}\"");
+            for user_id in mentions(ctx, mm, &submission.submission_notes).await? {
+                if user_id != submission.submission_submitter_id {
+                    notifications.push((user_id, NotificationKind::Mention, message.clone()));
+                }
+            }
+        }
+        ("submission", EventKind::Transitioned { to, .. }) => {
+            let submission = SubmissionBmc::get(ctx, mm, event.id).await?;
+            let title = bounty_title(ctx, mm, submission.submission_bounty_id).await?;
+            let submitter_id = submission.submission_submitter_id;
+            notifications.push((
+                submitter_id,
+                NotificationKind::Review,
+                format!(
+                    "Your submission to \"{title}\" was reviewed: {}",
+                    to.replace('_', " ")
+                ),
+            ));
+            if let Some(comment) = submission.submission_review_comment {
+                notifications.push((
+                    submitter_id,
+                    NotificationKind::Comment,
+                    format!("Review comment on \"{title}\": {comment}"),
+                ));
+                let message = format!("You were mentioned in a review of \"{title}\"");
+                for user_id in mentions(ctx, mm, &comment).await? {
+                    if Some(user_id) != submission.submission_reviewer_id && user_id != submitter_id
+                    {
+                        notifications.push((user_id, NotificationKind::Mention, message.clone()));
+                    }
+                }
+            }
+        }
+        ("bounty", EventKind::Transitioned { to, .. }) if to == BountyStatus::Paid.as_ref() => {
+            let bounty = BountyBmc::get(ctx, mm, event.id).await?;
+            if let Some(user_id) =
+                assignee_user_id(ctx, mm, event.id, &bounty.bounty_assignee).await?
+            {
+                notifications.push((
+                    user_id,
+                    NotificationKind::Payout,
+                    format!(
+                        "Bounty \"{}\" was paid ({})",
+                        bounty.bounty_title,
+                        bounty.bounty_reward_display.unwrap_or(bounty.bounty_reward)
+                    ),
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    let mut ids = Vec::with_capacity(notifications.len());
+    for (user_id, kind, message) in notifications {
+        let entity = (event.entity, event.id);
+        if let Some(id) = NotificationBmc::notify(ctx, mm, user_id, kind, entity, message).await? {
+            ids.push(id);
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Notify the applicant of the decision on its application
+/// (in the transaction of the decision, see `ApplicationBmc`).
+pub async fn notify_decision(
+    ctx: &Ctx,
+    mm: &ModelManager,
+    application: &Application,
+    to: ApplicationStatus,
+) -> Result<Option<i64>> {
+    let title = bounty_title(ctx, mm, application.application_bounty_id).await?;
+    NotificationBmc::notify(
+        ctx,
+        mm,
+        application.application_applicant_id,
+        NotificationKind::Assignment,
+        (ApplicationBmc::TABLE, application.id),
+        format!("Your application to \"{title}\" was {}", to.as_ref()),
+    )
+    .await
+}
+
+async fn bounty_title(ctx: &Ctx, mm: &ModelManager, bounty_id: i64) -> Result<String> {
+    Ok(BountyBmc::get(ctx, mm, bounty_id).await?.bounty_title)
+}
+
+/// The users `@mentioned` in the text (the existing ones, at most
+/// `MENTIONS_MAX`).
+async fn mentions(ctx: &Ctx, mm: &ModelManager, text: &str) -> Result<Vec<i64>> {
+    let mut usernames: Vec<&str> = regex!(r"(?:^|[^\w@])@([\w.-]+)")
+        .captures_iter(text)
+        .filter_map(|captures| captures.get(1))
+        .map(|username| username.as_str().trim_end_matches('.'))
+        .collect();
+    usernames.dedup();
+
+    let mut user_ids = Vec::new();
+    for username in usernames.into_iter().take(MENTIONS_MAX) {
+        if let Some(user) = UserBmc::first_by_username::<User>(ctx, mm, username).await? {
+            if !user_ids.contains(&user.id) {
+                user_ids.push(user.id);
+            }
+        }
+    }
+
+    Ok(user_ids)
+}
+
+/// The user of the bounty assignee address, the applicant of the accepted
+/// application to the bounty, or the user of the linked wallet.
+async fn assignee_user_id(
+    ctx: &Ctx,
+    mm: &ModelManager,
+    bounty_id: i64,
+    assignee: &str,
+) -> Result<Option<i64>> {
+    let filter = ApplicationFilter {
+        application_bounty_id: Some(bounty_id),
+        application_status: Some(ApplicationStatus::Accepted),
+        ..Default::default()
+    };
+    let applications = ApplicationBmc::list(ctx, mm, Some(filter), None).await?;
+    if let Some(application) = applications
+        .into_iter()
+        .find(|a| a.application_address == assignee)
+    {
+        return Ok(Some(application.application_applicant_id));
+    }
+
+    let wallet = WalletBmc::first_by_address(ctx, mm, assignee).await?;
+
+    Ok(wallet.map(|wallet| wallet.wallet_user_id))
+}
+
+// endregion: --- Notifier
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::{Seed, Seeded};
+    use crate::model::application::ApplicationForCreate;
+    use anyhow::{Context, Result};
+
+    const SEED_TOML: &str = r#"
+[[users]]
+username = "alice"
+pwd_clear = "alice pwd 01"
+
+[[users]]
+username = "bob"
+pwd_clear = "bob pwd 01"
+
+[[bounties]]
+key = "b1"
+bounty_title = "Notification bounty 01"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "A PR"
+bounty_description = "To apply to"
+"#;
+
+    /// Alice applies to the bounty, mentioning `@bob` (and herself).
+    async fn application_create(mm: &ModelManager, seeded: &Seeded) -> Result<i64> {
+        let alice_ctx = Ctx::new(*seeded.users.get("alice").context("alice")?)?;
+        let application_c = ApplicationForCreate {
+            application_bounty_id: *seeded.bounties.get("b1").context("b1")?,
+            application_message: "With @bob and @alice, cc @nobody.".to_string(),
+            application_estimated_days: 5,
+            application_address: "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn".to_string(),
+            application_deadline: None,
+        };
+
+        Ok(ApplicationBmc::create(&alice_ctx, mm, application_c).await?)
+    }
+
+    #[tokio::test]
+    async fn test_notify_event_application_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let alice_ctx = Ctx::new(*seeded.users.get("alice").context("alice")?)?;
+        let bob_ctx = Ctx::new(*seeded.users.get("bob").context("bob")?)?;
+        let application_id = application_create(&mm, &seeded).await?;
+
+        // -- Exec
+        let created = ModelEvent::new("bounty_application", application_id, EventKind::Created);
+        notify_event(&ctx, &mm, &created).await?;
+        // (notifies the applicant in its transaction, see `notify_decision`)
+        ApplicationBmc::accept(&ctx, &mm, application_id).await?;
+
+        // -- Check
+        let bob_notifications = NotificationBmc::list(&bob_ctx, &mm, None, None).await?;
+        let kinds: Vec<_> = bob_notifications
+            .iter()
+            .map(|n| n.notification_kind)
+            .collect();
+        assert_eq!(kinds, [NotificationKind::Mention]);
+
+        let alice_notifications = NotificationBmc::list(&alice_ctx, &mm, None, None).await?;
+        assert_eq!(alice_notifications.len(), 1, "not mentioned by herself");
+        let notification = &alice_notifications[0];
+        assert_eq!(notification.notification_kind, NotificationKind::Assignment);
+        assert_eq!(
+            (
+                notification.notification_entity.as_str(),
+                notification.notification_entity_id
+            ),
+            ("bounty_application", application_id)
+        );
+        assert_eq!(
+            notification.notification_message,
+            "Your application to \"Notification bounty 01\" was accepted"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_notifier_outbox_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await.with_event_handler(Notifier);
+        let ctx = Ctx::root_ctx();
+        let seeded = &toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let bob_ctx = Ctx::new(*seeded.users.get("bob").context("bob")?)?;
+        let mut events = mm.events().subscribe();
+
+        // -- Exec
+        let res: Result<i64> = mm
+            .transaction(|mm| async move {
+                application_create(&mm, seeded).await?;
+                Err(anyhow::anyhow!("fail after create"))
+            })
+            .await;
+        assert!(res.is_err());
+        let rolled_back = NotificationBmc::list(&bob_ctx, &mm, None, None).await?;
+        application_create(&mm, seeded).await?;
+
+        // -- Check
+        assert!(rolled_back.is_empty(), "none for a rolled back change");
+        let notifications = NotificationBmc::list(&bob_ctx, &mm, None, None).await?;
+        assert_eq!(notifications.len(), 1);
+        let mut notified = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if event.entity == NotificationBmc::TABLE {
+                notified.push((event.id, event.user_id));
+            }
+        }
+        assert!(notified.contains(&(notifications[0].id, Some(bob_ctx.user_id()))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_notify_prefs_disabled_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let bob_ctx = Ctx::new(*seeded.users.get("bob").context("bob")?)?;
+        let application_id = application_create(&mm, &seeded).await?;
+        let prefs_u = NotificationPrefsForUpdate {
+            pref_mention: Some(false),
+            ..Default::default()
+        };
+
+        // -- Exec
+        NotificationPrefsBmc::set(&bob_ctx, &mm, prefs_u).await?;
+        let created = ModelEvent::new("bounty_application", application_id, EventKind::Created);
+        let ids = notify_event(&ctx, &mm, &created).await?;
+
+        // -- Check
+        assert!(ids.is_empty());
+        let prefs = NotificationPrefsBmc::get_for_user(&ctx, &mm, bob_ctx.user_id()).await?;
+        assert!(!prefs.pref_mention);
+        assert!(prefs.pref_review, "unset, unchanged");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mark_read_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let alice_ctx = Ctx::new(*seeded.users.get("alice").context("alice")?)?;
+        let bob_ctx = Ctx::new(*seeded.users.get("bob").context("bob")?)?;
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let id = NotificationBmc::notify(
+                &ctx,
+                &mm,
+                alice_ctx.user_id(),
+                NotificationKind::Review,
+                ("submission", 1000 + i),
+                format!("Review {i}"),
+            )
+            .await?
+            .context("notified")?;
+            ids.push(id);
+        }
+
+        // -- Exec & Check
+        assert_eq!(
+            NotificationBmc::unread_count(&alice_ctx, &mm).await?.unread,
+            3
+        );
+        assert_eq!(
+            NotificationBmc::unread_count(&bob_ctx, &mm).await?.unread,
+            0
+        );
+
+        let res = NotificationBmc::mark_read(&bob_ctx, &mm, &ids[..1]).await;
+        assert!(
+            matches!(res, Err(Error::EntityNotFound { .. })),
+            "not bob's notification"
+        );
+
+        NotificationBmc::mark_read(&alice_ctx, &mm, &ids[..1]).await?;
+        assert_eq!(
+            NotificationBmc::unread_count(&alice_ctx, &mm).await?.unread,
+            2
+        );
+        let unread_filter = NotificationFilter {
+            notification_read: Some(false),
+            ..Default::default()
+        };
+        let unread = NotificationBmc::list(&alice_ctx, &mm, Some(unread_filter), None).await?;
+        assert_eq!(unread.iter().map(|n| n.id).collect::<Vec<_>>(), ids[1..]);
+
+        assert_eq!(NotificationBmc::mark_all_read(&alice_ctx, &mm).await?, 2);
+        assert_eq!(
+            NotificationBmc::unread_count(&alice_ctx, &mm).await?.unread,
+            0
+        );
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
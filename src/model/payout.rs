@@ -0,0 +1,816 @@
+//! Payouts of the approved bounties, from the project lead wallet.
+//!
+//! Design:
+//!
+//! - The backend builds the unsigned Cosmos SDK bank-send transaction
+//!   (the amino JSON `StdSignDoc`) for one or many approved bounties of the
+//!   same lead, with one `MsgSend` per assignee, its coins grouped by denom.
+//! - The lead signs and broadcasts it with their wallet (the backend never
+//!   holds keys), then confirms the payout with the signed tx hash, which
+//!   marks the bounties `Paid` (and so posts their ledger payout entries,
+//!   see `model::ledger`).
+//! - The sign doc is stored and returned as the exact canonical JSON to
+//!   sign (sorted keys, compact), so no client re-serialization can change
+//!   the signed bytes.
+//! - A bounty is in a single pending payout (its row locked while
+//!   building), and pays its reward, frozen once assigned (the escrowed
+//!   amount, see `BountyBmc::update`).
+//! - Only the project lead builds it, and only the owner of the
+//!   `payout_from_address` wallet confirms it.
+//! - The project lead cancels a pending payout (e.g., never broadcast),
+//!   releasing its bounties for a new one.
+//!
+
+use crate::ctx::Ctx;
+use crate::model::base::{self, DbBmc};
+use crate::model::bounty::{BountyBmc, BountyStatus};
+use crate::model::event::{EventKind, ModelEvent};
+use crate::model::ledger::parse_amount;
+use crate::model::project::ProjectBmc;
+use crate::model::store::memory::Row;
+use crate::model::wallet::WalletBmc;
+use crate::model::{Error, ModelManager, Result};
+use serde::{Deserialize, Serialize};
+use sqlb::Fields;
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use validator::Validate;
+
+// region:    --- Cosmos Types
+
+// Note: the amino JSON fields are declared in the sorted key order.
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Validate)]
+pub struct Coin {
+    #[validate(length(min = 1, max = 78))]
+    pub amount: String,
+    #[validate(length(min = 1, max = 128))]
+    pub denom: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StdFee {
+    pub amount: Vec<Coin>,
+    pub gas: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MsgSend {
+    pub amount: Vec<Coin>,
+    pub from_address: String,
+    pub to_address: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AminoMsg {
+    #[serde(rename = "type")]
+    pub typ: &'static str,
+    pub value: MsgSend,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StdSignDoc {
+    pub account_number: String,
+    pub chain_id: String,
+    pub fee: StdFee,
+    pub memo: String,
+    pub msgs: Vec<AminoMsg>,
+    pub sequence: String,
+}
+
+impl StdSignDoc {
+    /// The canonical amino JSON (sorted keys, compact, and `<`, `>`, `&`
+    /// escaped as by the Cosmos SDK).
+    pub fn to_sign_json(&self) -> Result<String> {
+        let json = serde_json::to_string(self)?;
+
+        Ok(json
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+            .replace('&', "\\u0026"))
+    }
+}
+
+// endregion: --- Cosmos Types
+
+// region:    --- Payout Types
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct Payout {
+    pub id: i64,
+    pub payout_from_address: String,
+    pub payout_chain_id: String,
+    pub payout_sign_doc: String,
+    pub payout_status: PayoutStatus,
+    pub payout_tx_hash: Option<String>,
+}
+
+/// The `payout_status` db enum.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type, strum_macros::AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "payout_status", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PayoutStatus {
+    Pending,
+    Confirmed,
+    Cancelled,
+}
+sqlb::bindable!(PayoutStatus);
+
+/// The payout with the ids of its bounties.
+#[derive(Debug, Serialize)]
+pub struct PayoutWithBounties {
+    #[serde(flatten)]
+    pub payout: Payout,
+    pub bounty_ids: Vec<i64>,
+}
+
+/// The signer account and fee of the transaction to build.
+#[derive(Deserialize, Validate)]
+pub struct PayoutForBuild {
+    #[validate(length(min = 1, max = 64))]
+    pub bounty_ids: Vec<i64>,
+    #[validate(length(min = 1, max = 64))]
+    pub chain_id: String,
+    pub account_number: u64,
+    pub sequence: u64,
+    #[validate(length(max = 256))]
+    #[serde(default)]
+    pub memo: String,
+    #[validate]
+    #[serde(default)]
+    pub fee: Vec<Coin>,
+    #[validate(range(min = 1))]
+    pub gas: u64,
+}
+
+#[derive(Serialize, Fields)]
+pub struct PayoutForInsert {
+    pub payout_from_address: String,
+    pub payout_chain_id: String,
+    pub payout_sign_doc: String,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct PayoutForConfirm {
+    #[validate(length(min = 1, max = 64))]
+    pub tx_hash: String,
+}
+
+#[derive(Serialize, Fields)]
+pub struct PayoutForConfirmed {
+    pub payout_status: PayoutStatus,
+    pub payout_tx_hash: String,
+}
+
+#[derive(Serialize, Fields)]
+pub struct PayoutForCancelled {
+    pub payout_status: PayoutStatus,
+}
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct PayoutBounty {
+    pub id: i64,
+    pub payout_id: i64,
+    pub bounty_id: i64,
+}
+
+#[derive(Serialize, Fields)]
+pub struct PayoutBountyForInsert {
+    pub payout_id: i64,
+    pub bounty_id: i64,
+}
+
+#[derive(Default, Serialize, Fields)]
+pub struct PayoutBountyFilter {
+    pub payout_id: Option<i64>,
+    pub bounty_id: Option<i64>,
+}
+
+/// The tx hash, as the uppercase hex of its 32 bytes.
+pub fn normalize_tx_hash(value: &str) -> Result<String> {
+    let hash = value.trim();
+    if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(Error::PayoutInvalidTxHash {
+            value: value.to_string(),
+        });
+    }
+
+    Ok(hash.to_ascii_uppercase())
+}
+
+// endregion: --- Payout Types
+
+pub struct PayoutBmc;
+
+impl DbBmc for PayoutBmc {
+    const TABLE: &'static str = "payout";
+    const UNIQUE_FIELDS: &'static [&'static str] = &["payout_tx_hash"];
+
+    fn column_defaults() -> Row {
+        Row::from_iter([("payout_status".to_string(), "pending".into())])
+    }
+}
+
+pub struct PayoutBountyBmc;
+
+impl DbBmc for PayoutBountyBmc {
+    const TABLE: &'static str = "payout_bounty";
+}
+
+impl PayoutBmc {
+    /// Build the unsigned transaction paying the approved bounties
+    /// (all of the same project lead, the signer), as this lead.
+    pub async fn build(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        payout_b: PayoutForBuild,
+    ) -> Result<PayoutWithBounties> {
+        let PayoutForBuild {
+            mut bounty_ids,
+            chain_id,
+            account_number,
+            sequence,
+            memo,
+            fee,
+            gas,
+        } = payout_b;
+        bounty_ids.sort_unstable();
+        bounty_ids.dedup();
+
+        let id = mm
+            .transaction(|mm| async move {
+                let (from_address, amounts) = Self::amounts(ctx, &mm, &bounty_ids).await?;
+
+                // -- The sign doc.
+                let msgs = amounts
+                    .into_iter()
+                    .map(|(to_address, coins)| AminoMsg {
+                        typ: "cosmos-sdk/MsgSend",
+                        value: MsgSend {
+                            amount: coins
+                                .into_iter()
+                                .map(|(denom, amount)| Coin {
+                                    amount: amount.to_string(),
+                                    denom,
+                                })
+                                .collect(),
+                            from_address: from_address.clone(),
+                            to_address,
+                        },
+                    })
+                    .collect();
+                let sign_doc = StdSignDoc {
+                    account_number: account_number.to_string(),
+                    chain_id: chain_id.clone(),
+                    fee: StdFee {
+                        amount: fee,
+                        gas: gas.to_string(),
+                    },
+                    memo,
+                    msgs,
+                    sequence: sequence.to_string(),
+                };
+
+                let payout_i = PayoutForInsert {
+                    payout_from_address: from_address,
+                    payout_chain_id: chain_id,
+                    payout_sign_doc: sign_doc.to_sign_json()?,
+                };
+                let id = base::create::<Self, _>(ctx, &mm, payout_i).await?;
+                for bounty_id in bounty_ids {
+                    let link_i = PayoutBountyForInsert {
+                        payout_id: id,
+                        bounty_id,
+                    };
+                    base::create::<PayoutBountyBmc, _>(ctx, &mm, link_i).await?;
+                }
+                Ok::<_, Error>(id)
+            })
+            .await?;
+
+        Self::get_with_bounties(ctx, mm, id).await
+    }
+
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Payout> {
+        base::get::<Self, _>(ctx, mm, id).await
+    }
+
+    pub async fn get_with_bounties(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        id: i64,
+    ) -> Result<PayoutWithBounties> {
+        let payout = Self::get(ctx, mm, id).await?;
+        let bounty_ids = Self::bounty_ids(ctx, mm, id).await?;
+
+        Ok(PayoutWithBounties { payout, bounty_ids })
+    }
+
+    /// Confirm the pending payout with the hash of the signed tx,
+    /// marking its bounties paid.
+    /// (by the owner of the `payout_from_address` wallet only)
+    pub async fn confirm(ctx: &Ctx, mm: &ModelManager, id: i64, tx_hash: &str) -> Result<()> {
+        let tx_hash = normalize_tx_hash(tx_hash)?;
+
+        mm.transaction(|mm| async move {
+            let payout = Self::get(ctx, &mm, id).await?;
+            Self::check_signer(ctx, &mm, &payout).await?;
+            let (from, to) = (payout.payout_status, PayoutStatus::Confirmed);
+            Self::check_pending(&payout, to)?;
+
+            let payout_c = PayoutForConfirmed {
+                payout_status: to,
+                payout_tx_hash: tx_hash,
+            };
+            base::update::<Self, _>(ctx, &mm, id, None, payout_c).await?;
+
+            for bounty_id in Self::bounty_ids(ctx, &mm, id).await? {
+                BountyBmc::transition(ctx, &mm, bounty_id, BountyStatus::Paid).await?;
+            }
+
+            mm.publish(ModelEvent::new(
+                Self::TABLE,
+                id,
+                EventKind::Transitioned {
+                    from: from.as_ref().to_string(),
+                    to: to.as_ref().to_string(),
+                },
+            ));
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Cancel the pending payout, releasing its bounties for a new payout.
+    /// (by the lead of the bounty projects only)
+    pub async fn cancel(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        mm.transaction(|mm| async move {
+            let payout = Self::get(ctx, &mm, id).await?;
+            for bounty_id in Self::bounty_ids(ctx, &mm, id).await? {
+                let bounty = BountyBmc::get(ctx, &mm, bounty_id).await?;
+                BountyBmc::check_lead(ctx, &mm, &bounty).await?;
+            }
+            let (from, to) = (payout.payout_status, PayoutStatus::Cancelled);
+            Self::check_pending(&payout, to)?;
+
+            let payout_c = PayoutForCancelled { payout_status: to };
+            base::update::<Self, _>(ctx, &mm, id, None, payout_c).await?;
+
+            mm.publish(ModelEvent::new(
+                Self::TABLE,
+                id,
+                EventKind::Transitioned {
+                    from: from.as_ref().to_string(),
+                    to: to.as_ref().to_string(),
+                },
+            ));
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// The signer address, and the amounts per assignee and denom, of the
+    /// approved bounties (all of the same project lead, and in no other
+    /// pending payout).
+    async fn amounts(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        bounty_ids: &[i64],
+    ) -> Result<(String, BTreeMap<String, BTreeMap<String, i64>>)> {
+        let mut from_address: Option<String> = None;
+        let mut amounts: BTreeMap<String, BTreeMap<String, i64>> = BTreeMap::new();
+        for bounty_id in bounty_ids {
+            // (until the commit, not to be linked to two pending payouts)
+            base::lock::<BountyBmc>(ctx, mm, *bounty_id).await?;
+            let bounty = BountyBmc::get(ctx, mm, *bounty_id).await?;
+            if bounty.bounty_status != BountyStatus::Approved {
+                return Err(Error::BountyNotApproved {
+                    id: bounty.id,
+                    status: bounty.bounty_status.as_ref().to_string(),
+                });
+            }
+            Self::check_not_pending(ctx, mm, bounty.id).await?;
+            let project_id = bounty
+                .bounty_project_id
+                .ok_or(Error::PayoutBountyWithoutProject { id: bounty.id })?;
+            let project = ProjectBmc::check_lead(ctx, mm, project_id).await?;
+
+            let lead = from_address.get_or_insert_with(|| project.project_lead_address.clone());
+            if *lead != project.project_lead_address {
+                return Err(Error::PayoutLeadMismatch {
+                    id: bounty.id,
+                    expected: lead.clone(),
+                    actual: project.project_lead_address,
+                });
+            }
+
+            let amount = parse_amount(&bounty.bounty_reward)?;
+            *amounts
+                .entry(bounty.bounty_assignee)
+                .or_default()
+                .entry(project.project_denom)
+                .or_default() += amount;
+        }
+        // (`bounty_ids` is validated as not empty)
+        let from_address = from_address.unwrap_or_default();
+
+        Ok((from_address, amounts))
+    }
+
+    /// The bounty must not be in a pending payout (`PayoutBountyPending`).
+    async fn check_not_pending(ctx: &Ctx, mm: &ModelManager, bounty_id: i64) -> Result<()> {
+        let filter = PayoutBountyFilter {
+            bounty_id: Some(bounty_id),
+            ..Default::default()
+        };
+        let links: Vec<PayoutBounty> =
+            base::list::<PayoutBountyBmc, _, _>(ctx, mm, Some(filter), None).await?;
+        for link in links {
+            let payout = Self::get(ctx, mm, link.payout_id).await?;
+            if payout.payout_status == PayoutStatus::Pending {
+                return Err(Error::PayoutBountyPending {
+                    id: bounty_id,
+                    payout_id: payout.id,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The payout must be pending, to move to `to` (`InvalidTransition`
+    /// otherwise).
+    fn check_pending(payout: &Payout, to: PayoutStatus) -> Result<()> {
+        if payout.payout_status == PayoutStatus::Pending {
+            return Ok(());
+        }
+
+        Err(Error::InvalidTransition {
+            entity: Self::TABLE,
+            id: payout.id,
+            from: payout.payout_status.as_ref().to_string(),
+            to: to.as_ref().to_string(),
+        })
+    }
+
+    /// The `Ctx` user must have the `payout_from_address` wallet linked
+    /// (`PayoutNotSigner` otherwise).
+    /// (the root ctx, of the system tasks, always has)
+    async fn check_signer(ctx: &Ctx, mm: &ModelManager, payout: &Payout) -> Result<()> {
+        if ctx.is_root() {
+            return Ok(());
+        }
+
+        let wallet = WalletBmc::first_by_address(ctx, mm, &payout.payout_from_address).await?;
+        match wallet {
+            Some(wallet) if wallet.wallet_user_id == ctx.user_id() => Ok(()),
+            _ => Err(Error::PayoutNotSigner {
+                id: payout.id,
+                user_id: ctx.user_id(),
+            }),
+        }
+    }
+
+    async fn bounty_ids(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Vec<i64>> {
+        let filter = PayoutBountyFilter {
+            payout_id: Some(id),
+            ..Default::default()
+        };
+        let links: Vec<PayoutBounty> =
+            base::list::<PayoutBountyBmc, _, _>(ctx, mm, Some(filter), None).await?;
+
+        Ok(links.into_iter().map(|link| link.bounty_id).collect())
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::{Seed, Seeded};
+    use crate::model::ledger::{AccountKind, LedgerBmc, ProjectFunding};
+    use anyhow::{Context, Result};
+    use serde_json::{json, Value};
+
+    const SEED_TOML: &str = r#"
+[[users]]
+username = "lead"
+pwd_clear = "lead pwd 01"
+
+[[users]]
+username = "alice"
+pwd_clear = "alice pwd 01"
+
+[[wallets]]
+username = "lead"
+key = "lead"
+
+# (lead, the address of the `lead` dev wallet)
+[[projects]]
+key = "p1"
+project_id = "payout-project-01"
+project_name = "Payout Project 01"
+project_lead_address = "cosmos1gf3c5mgfe6zd0qnmuwy7c5zf6yj5t5ajyn7sth"
+project_budget = "1000"
+project_denom = "uatom"
+project_type = "dev"
+project_description = "A project paying in uatom"
+
+[[projects]]
+key = "p2"
+project_id = "payout-project-02"
+project_name = "Payout Project 02"
+project_lead_address = "cosmos1gf3c5mgfe6zd0qnmuwy7c5zf6yj5t5ajyn7sth"
+project_budget = "1000"
+project_denom = "uosmo"
+project_type = "dev"
+project_description = "A project paying in uosmo"
+
+[[projects]]
+key = "p3"
+project_id = "payout-project-03"
+project_name = "Payout Project 03"
+project_lead_address = "cosmos1mamszye4qhk3hfnxm939fm2h0y427pwvtuvs4s"
+project_budget = "1000"
+project_denom = "uatom"
+project_type = "dev"
+project_description = "A project of another lead"
+
+[[bounties]]
+key = "b1"
+project = "p1"
+bounty_title = "Payout bounty 01"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "A PR"
+bounty_description = "For alice"
+
+[[bounties]]
+key = "b2"
+project = "p1"
+bounty_title = "Payout bounty 02"
+bounty_assignee = ""
+bounty_reward = "50"
+bounty_deliverables = "A PR"
+bounty_description = "For alice too"
+
+[[bounties]]
+key = "b3"
+project = "p2"
+bounty_title = "Payout bounty 03"
+bounty_assignee = ""
+bounty_reward = "70"
+bounty_deliverables = "A PR"
+bounty_description = "For alice, in uosmo"
+
+[[bounties]]
+key = "b4"
+project = "p1"
+bounty_title = "Payout bounty 04"
+bounty_assignee = ""
+bounty_reward = "30"
+bounty_deliverables = "A PR"
+bounty_description = "For bob"
+
+[[bounties]]
+key = "b5"
+project = "p3"
+bounty_title = "Payout bounty 05"
+bounty_assignee = ""
+bounty_reward = "10"
+bounty_deliverables = "A PR"
+bounty_description = "Of another lead"
+"#;
+
+    const TX_HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    /// Seed, fund the projects, and approve all the bounties.
+    async fn fixture(mm: &ModelManager) -> Result<Seeded> {
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, mm).await?;
+        for project_id in seeded.projects.values() {
+            let funding = ProjectFunding {
+                amount: 1000,
+                memo: "funding".to_string(),
+            };
+            LedgerBmc::fund_project(&ctx, mm, *project_id, funding).await?;
+        }
+
+        for (key, assignee) in [
+            ("b1", "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn"),
+            ("b2", "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn"),
+            ("b3", "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn"),
+            ("b4", "cosmos1sxmr0k8u6trd5c6eu6trzyapzux7090y3u5dan"),
+            ("b5", "cosmos1sxmr0k8u6trd5c6eu6trzyapzux7090y3u5dan"),
+        ] {
+            let id = *seeded.bounties.get(key).context(key)?;
+            BountyBmc::assign(&ctx, mm, id, assignee).await?;
+            BountyBmc::transition(&ctx, mm, id, BountyStatus::Submitted).await?;
+            BountyBmc::transition(&ctx, mm, id, BountyStatus::Approved).await?;
+        }
+
+        Ok(seeded)
+    }
+
+    fn user_ctx(seeded: &Seeded, username: &str) -> Result<Ctx> {
+        Ok(Ctx::new(*seeded.users.get(username).context("user")?)?)
+    }
+
+    fn payout_b(bounty_ids: Vec<i64>) -> PayoutForBuild {
+        PayoutForBuild {
+            bounty_ids,
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 42,
+            sequence: 7,
+            memo: "bounties <payout>".to_string(),
+            fee: vec![Coin {
+                amount: "5000".to_string(),
+                denom: "uatom".to_string(),
+            }],
+            gas: 200000,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_build_confirm_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = fixture(&mm).await?;
+        let bounties = &seeded.bounties;
+        let bounty_ids: Vec<i64> = ["b1", "b2", "b3", "b4"]
+            .iter()
+            .map(|key| bounties[*key])
+            .collect();
+
+        // -- Exec
+        let payout = PayoutBmc::build(&ctx, &mm, payout_b(bounty_ids.clone())).await?;
+
+        // -- Check - sign doc
+        let sign_doc = &payout.payout.payout_sign_doc;
+        assert!(
+            sign_doc.starts_with(r#"{"account_number":"42","chain_id":"cosmoshub-4","fee":"#),
+            "sorted keys"
+        );
+        assert!(
+            sign_doc.contains(r#""memo":"bounties \u003cpayout\u003e""#),
+            "escaped as by the sdk"
+        );
+        let sign_doc: Value = serde_json::from_str(sign_doc)?;
+        assert_eq!(
+            sign_doc["msgs"],
+            json!([
+                {
+                    "type": "cosmos-sdk/MsgSend",
+                    "value": {
+                        "amount": [
+                            {"amount": "150", "denom": "uatom"},
+                            {"amount": "70", "denom": "uosmo"},
+                        ],
+                        "from_address": "cosmos1gf3c5mgfe6zd0qnmuwy7c5zf6yj5t5ajyn7sth",
+                        "to_address": "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn",
+                    }
+                },
+                {
+                    "type": "cosmos-sdk/MsgSend",
+                    "value": {
+                        "amount": [{"amount": "30", "denom": "uatom"}],
+                        "from_address": "cosmos1gf3c5mgfe6zd0qnmuwy7c5zf6yj5t5ajyn7sth",
+                        "to_address": "cosmos1sxmr0k8u6trd5c6eu6trzyapzux7090y3u5dan",
+                    }
+                },
+            ])
+        );
+        assert_eq!(payout.bounty_ids, bounty_ids);
+
+        // -- Exec & Check - confirm, by the signer
+        PayoutBmc::confirm(&user_ctx(&seeded, "lead")?, &mm, payout.payout.id, TX_HASH).await?;
+        let confirmed = PayoutBmc::get(&ctx, &mm, payout.payout.id).await?;
+        assert_eq!(confirmed.payout_status, PayoutStatus::Confirmed);
+        assert_eq!(
+            confirmed.payout_tx_hash.as_deref(),
+            Some(TX_HASH.to_ascii_uppercase().as_str())
+        );
+        for id in &bounty_ids {
+            let bounty = BountyBmc::get(&ctx, &mm, *id).await?;
+            assert_eq!(bounty.bounty_status, BountyStatus::Paid);
+        }
+        let project_id = BountyBmc::get(&ctx, &mm, bounties["b1"])
+            .await?
+            .bounty_project_id
+            .context("b1 project")?;
+        let paid = LedgerBmc::project_balances(&ctx, &mm, project_id)
+            .await?
+            .into_iter()
+            .find(|b| b.account_kind == AccountKind::ProjectPaid)
+            .map(|b| b.balance);
+        assert_eq!(paid, Some(180));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_build_confirm_err() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = fixture(&mm).await?;
+        let bounties = &seeded.bounties;
+
+        // -- Exec & Check - another lead
+        let res = PayoutBmc::build(&ctx, &mm, payout_b(vec![bounties["b1"], bounties["b5"]])).await;
+        assert!(
+            matches!(&res, Err(Error::PayoutLeadMismatch { actual, .. }) if actual == "cosmos1mamszye4qhk3hfnxm939fm2h0y427pwvtuvs4s"),
+            "PayoutLeadMismatch not matching"
+        );
+
+        // -- Exec & Check - not the lead
+        let res = PayoutBmc::build(
+            &user_ctx(&seeded, "alice")?,
+            &mm,
+            payout_b(vec![bounties["b1"]]),
+        )
+        .await;
+        assert!(
+            matches!(res, Err(Error::NotProjectLead { .. })),
+            "NotProjectLead not matching"
+        );
+
+        // -- Exec & Check - already in a pending payout
+        let payout = PayoutBmc::build(&ctx, &mm, payout_b(vec![bounties["b1"]])).await?;
+        let res = PayoutBmc::build(&ctx, &mm, payout_b(vec![bounties["b2"], bounties["b1"]])).await;
+        assert!(
+            matches!(res, Err(Error::PayoutBountyPending { id, payout_id }) if id == bounties["b1"] && payout_id == payout.payout.id),
+            "PayoutBountyPending not matching"
+        );
+
+        // -- Exec & Check - not the signer
+        let res =
+            PayoutBmc::confirm(&user_ctx(&seeded, "alice")?, &mm, payout.payout.id, TX_HASH).await;
+        assert!(
+            matches!(res, Err(Error::PayoutNotSigner { .. })),
+            "PayoutNotSigner not matching"
+        );
+
+        // -- Exec & Check - invalid tx hash
+        let res = PayoutBmc::confirm(&ctx, &mm, payout.payout.id, "not-a-hash").await;
+        assert!(
+            matches!(res, Err(Error::PayoutInvalidTxHash { .. })),
+            "PayoutInvalidTxHash not matching"
+        );
+
+        // -- Exec & Check - paid, cannot be paid again
+        PayoutBmc::confirm(&ctx, &mm, payout.payout.id, TX_HASH).await?;
+        let res = PayoutBmc::build(&ctx, &mm, payout_b(vec![bounties["b1"]])).await;
+        assert!(
+            matches!(&res, Err(Error::BountyNotApproved { status, .. }) if status == "paid"),
+            "BountyNotApproved not matching"
+        );
+        let res = PayoutBmc::confirm(&ctx, &mm, payout.payout.id, TX_HASH).await;
+        assert!(
+            matches!(res, Err(Error::InvalidTransition { .. })),
+            "InvalidTransition not matching"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cancel_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let seeded = fixture(&mm).await?;
+        let lead_ctx = user_ctx(&seeded, "lead")?;
+        let bounty_ids = vec![seeded.bounties["b1"]];
+        let payout = PayoutBmc::build(&lead_ctx, &mm, payout_b(bounty_ids.clone())).await?;
+        let id = payout.payout.id;
+
+        // -- Exec
+        let res = PayoutBmc::cancel(&user_ctx(&seeded, "alice")?, &mm, id).await;
+        PayoutBmc::cancel(&lead_ctx, &mm, id).await?;
+
+        // -- Check
+        assert!(
+            matches!(res, Err(Error::NotProjectLead { .. })),
+            "NotProjectLead not matching"
+        );
+        let cancelled = PayoutBmc::get(&lead_ctx, &mm, id).await?;
+        assert_eq!(cancelled.payout_status, PayoutStatus::Cancelled);
+        let res = PayoutBmc::confirm(&lead_ctx, &mm, id, TX_HASH).await;
+        assert!(
+            matches!(res, Err(Error::InvalidTransition { .. })),
+            "InvalidTransition not matching"
+        );
+        // (the bounty is released for a new payout)
+        PayoutBmc::build(&lead_ctx, &mm, payout_b(bounty_ids)).await?;
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
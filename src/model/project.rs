@@ -1,12 +1,20 @@
+use crate::model::address::{self, validate_address};
+use crate::model::denom::DenomBmc;
+use crate::model::ledger::LedgerBmc;
+use crate::model::tag;
+use crate::model::user::UserBmc;
+use crate::model::wallet::WalletBmc;
 use crate::model::{Error, Result};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use sqlb::{Fields, HasFields};
 use sqlx::{postgres::PgRow, FromRow};
+use validator::Validate;
 
 use crate::ctx::Ctx;
 
 use super::{
-    base::{self, DbBmc},
+    base::{self, DbBmc, ListOptions},
     ModelManager,
 };
 
@@ -21,30 +29,64 @@ pub struct Project {
     pub project_denom: String,
     pub project_type: String,
     pub project_description: String,
+    pub version: i64,
 }
 
-#[derive(Deserialize, Fields)]
+#[derive(Deserialize, Serialize, Fields, Validate)]
 pub struct ProjectForCreate {
+    #[validate(length(min = 1, max = 256))]
     pub project_id: String,
+    #[validate(length(min = 1, max = 256))]
     pub project_name: String,
+    #[validate(length(min = 1, max = 256), custom = "validate_address")]
     pub project_lead_address: String,
+    #[validate(length(min = 1, max = 256))]
     pub project_budget: String,
+    #[validate(length(min = 1, max = 256))]
     pub project_denom: String,
+    #[validate(length(max = 256))]
     pub project_type: String,
+    #[validate(length(max = 256))]
     pub project_description: String,
 }
-#[derive(Deserialize, Fields)]
+#[derive(Default, Deserialize, Serialize, Fields, Validate)]
 pub struct ProjectForUpdate {
+    #[validate(length(min = 1, max = 256))]
     pub project_id: Option<String>,
+    #[validate(length(min = 1, max = 256))]
     pub project_name: Option<String>,
+    #[validate(length(min = 1, max = 256), custom = "validate_address")]
     pub project_lead_address: Option<String>,
+    #[validate(length(min = 1, max = 256))]
     pub project_budget: Option<String>,
+    #[validate(length(min = 1, max = 256))]
     pub project_denom: Option<String>,
+    #[validate(length(max = 256))]
     pub project_type: Option<String>,
+    #[validate(length(max = 256))]
     pub project_description: Option<String>,
+
+    /// Expected row version, the update fails with a conflict if stale.
+    #[field(skip)]
+    pub version: Option<i64>,
+}
+
+#[derive(Default, Deserialize, Serialize, Fields)]
+pub struct ProjectFilter {
+    pub project_id: Option<String>,
+    pub project_lead_address: Option<String>,
+    pub project_denom: Option<String>,
+    pub project_type: Option<String>,
+
+    /// Tag name (see `model::tag`).
+    #[field(skip)]
+    pub tag: Option<String>,
 }
 
-pub trait ProjectBy: HasFields + for<'r> FromRow<'r, PgRow> + Unpin + Send {}
+pub trait ProjectBy:
+    HasFields + DeserializeOwned + for<'r> FromRow<'r, PgRow> + Unpin + Send
+{
+}
 
 impl ProjectBy for Project {}
 
@@ -52,10 +94,21 @@ pub struct ProjectBmc;
 
 impl DbBmc for ProjectBmc {
     const TABLE: &'static str = "project";
+    const VERSIONED: bool = true;
+    const UNIQUE_FIELDS: &'static [&'static str] = &["project_id"];
+    const TAG_LINK: Option<(&'static str, &'static str)> = Some(("project_tag", "project_id"));
 }
 
 impl ProjectBmc {
-    pub async fn create(ctx: &Ctx, mm: &ModelManager, project_c: ProjectForCreate) -> Result<i64> {
+    pub async fn create(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        mut project_c: ProjectForCreate,
+    ) -> Result<i64> {
+        project_c.project_lead_address = address::normalize(&project_c.project_lead_address);
+        project_c.project_denom = DenomBmc::resolve(ctx, mm, &project_c.project_denom)
+            .await?
+            .denom_base;
         base::create::<Self, _>(ctx, mm, project_c).await
     }
 
@@ -63,24 +116,114 @@ impl ProjectBmc {
         base::get::<Self, _>(ctx, mm, id).await
     }
 
-    pub async fn list(ctx: &Ctx, mm: &ModelManager) -> Result<Vec<Project>> {
-        base::list::<Self, _>(ctx, mm).await
+    /// The project, if the `Ctx` user is its lead, i.e., has the
+    /// `project_lead_address` wallet linked (`NotProjectLead` otherwise).
+    /// (the root ctx, of the system tasks, always is)
+    pub async fn check_lead(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Project> {
+        let project = Self::get(ctx, mm, id).await?;
+        if ctx.is_root() {
+            return Ok(project);
+        }
+
+        if Self::lead_user_id(ctx, mm, &project).await? == Some(ctx.user_id()) {
+            Ok(project)
+        } else {
+            Err(Error::NotProjectLead {
+                project_id: Some(id),
+                user_id: ctx.user_id(),
+            })
+        }
+    }
+
+    /// The project, if the `Ctx` user is its lead or an admin
+    /// (`NotProjectLead` otherwise).
+    async fn check_lead_or_admin(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Project> {
+        if UserBmc::is_admin(ctx, mm).await? {
+            Self::get(ctx, mm, id).await
+        } else {
+            Self::check_lead(ctx, mm, id).await
+        }
+    }
+
+    /// The user with the `project_lead_address` wallet linked, if any.
+    pub async fn lead_user_id(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        project: &Project,
+    ) -> Result<Option<i64>> {
+        let wallet = WalletBmc::first_by_address(ctx, mm, &project.project_lead_address).await?;
+
+        Ok(wallet.map(|wallet| wallet.wallet_user_id))
     }
+
+    pub async fn first_by_project_id(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        project_id: &str,
+    ) -> Result<Option<Project>> {
+        base::first_by::<Self, _, _>(ctx, mm, "project_id", project_id).await
+    }
+
+    pub async fn get_by_project_id(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        project_id: &str,
+    ) -> Result<Project> {
+        Self::first_by_project_id(ctx, mm, project_id)
+            .await?
+            .ok_or(Error::EntityNotFoundBy {
+                entity: Self::TABLE,
+                field: "project_id",
+                value: project_id.to_string(),
+            })
+    }
+
+    pub async fn list(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        filter: Option<ProjectFilter>,
+        list_options: Option<ListOptions>,
+    ) -> Result<Vec<Project>> {
+        let tags: Vec<String> = filter
+            .as_ref()
+            .and_then(|f| f.tag.as_deref())
+            .map(tag::normalize)
+            .into_iter()
+            .collect();
+
+        base::list_tagged::<Self, _, _>(ctx, mm, filter, &tags, list_options).await
+    }
+    /// Update the project, as its lead or an admin (so only they can
+    /// transfer the `project_lead_address`).
+    /// (its denom is frozen once it has a ledger, see `model::ledger`)
     pub async fn update(
         ctx: &Ctx,
         mm: &ModelManager,
         id: i64,
-        bounty_u: ProjectForUpdate,
+        mut bounty_u: ProjectForUpdate,
     ) -> Result<()> {
-        base::update::<Self, _>(ctx, mm, id, bounty_u).await
+        let project = Self::check_lead_or_admin(ctx, mm, id).await?;
+
+        bounty_u.project_lead_address = bounty_u
+            .project_lead_address
+            .as_deref()
+            .map(address::normalize);
+        if let Some(denom) = &bounty_u.project_denom {
+            let denom = DenomBmc::resolve(ctx, mm, denom).await?.denom_base;
+            if denom != project.project_denom && LedgerBmc::has_ledger(ctx, mm, id).await? {
+                return Err(Error::ProjectDenomFrozen {
+                    id,
+                    denom: project.project_denom,
+                });
+            }
+            bounty_u.project_denom = Some(denom);
+        }
+        base::update::<Self, _>(ctx, mm, id, bounty_u.version, bounty_u).await
     }
+    /// Delete the project, as its lead or an admin.
     pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        Self::check_lead_or_admin(ctx, mm, id).await?;
+
         base::delete::<Self>(ctx, mm, id).await
     }
-
-    // TODO: Implement Update for almost all project's fields
-
-    // TODO: Implement delete project
-
-    // TODO: Implement other getters
 }
@@ -0,0 +1,215 @@
+//! Schema consistency check between the model types and the db.
+//!
+//! Every `DbBmc::TABLE` and the `Fields` of the types read from or written to it
+//! are compared against `information_schema.columns`, so a drift between a
+//! migration and a model struct fails at startup (and in tests) rather than
+//! on the first query.
+//!
+
+use crate::model::application::{
+    Application, ApplicationBmc, ApplicationFilter, ApplicationForDecide, ApplicationForInsert,
+};
+use crate::model::approval::{
+    ApprovalBmc, ApprovalVote, ApprovalVoteFilter, ApprovalVoteForInsert,
+};
+use crate::model::base::DbBmc;
+use crate::model::bounty::{
+    Bounty, BountyBmc, BountyFilter, BountyForCreate, BountyForTransition, BountyForUpdate,
+};
+use crate::model::deliverable::{
+    Deliverable, DeliverableBmc, DeliverableFilter, DeliverableForCreate,
+};
+use crate::model::denom::{Denom, DenomBmc, DenomFilter, DenomForCreate, DenomForUpdate};
+use crate::model::notification::{
+    Notification, NotificationBmc, NotificationFilter, NotificationForInsert, NotificationForRead,
+    NotificationPrefs, NotificationPrefsBmc, NotificationPrefsForInsert,
+    NotificationPrefsForUpdate, NotificationPrefsRow,
+};
+use crate::model::payout::{
+    Payout, PayoutBmc, PayoutBounty, PayoutBountyBmc, PayoutBountyFilter, PayoutBountyForInsert,
+    PayoutForConfirmed, PayoutForInsert,
+};
+use crate::model::project::{
+    Project, ProjectBmc, ProjectFilter, ProjectForCreate, ProjectForUpdate,
+};
+use crate::model::submission::{
+    Submission, SubmissionBmc, SubmissionFilter, SubmissionForDecide, SubmissionForInsert,
+    SubmissionItem, SubmissionItemBmc, SubmissionItemFilter, SubmissionItemForInsert,
+};
+use crate::model::tag::{Tag, TagBmc, TagForCreate};
+use crate::model::user::{
+    User, UserBmc, UserForAdmin, UserForAuth, UserForInsert, UserForLogin, UserForUpdatePwd,
+};
+use crate::model::wallet::{Wallet, WalletBmc, WalletFilter, WalletForInsert};
+use crate::model::webhook::{
+    Webhook, WebhookBmc, WebhookDelivery, WebhookDeliveryBmc, WebhookDeliveryFilter,
+    WebhookDeliveryForInsert, WebhookFilter, WebhookForInsert, WebhookForPatch,
+};
+use crate::model::{Error, ModelManager, Result};
+use serde::Serialize;
+use sqlb::HasFields;
+use std::any::type_name;
+
+/// The columns a model type expects in its table.
+struct EntityColumns {
+    table: &'static str,
+    entity: &'static str,
+    fields: &'static [&'static str],
+}
+
+fn entity_columns<MC, E>() -> EntityColumns
+where
+    MC: DbBmc,
+    E: HasFields,
+{
+    EntityColumns {
+        table: MC::TABLE,
+        entity: type_name::<E>(),
+        fields: E::field_names(),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SchemaMismatch {
+    pub table: &'static str,
+    pub entity: &'static str,
+    pub missing_columns: Vec<String>,
+}
+
+/// Check all the model types against the db columns.
+pub async fn check_schema(mm: &ModelManager) -> Result<()> {
+    let entities = [
+        // -- Approval
+        entity_columns::<ApprovalBmc, ApprovalVote>(),
+        entity_columns::<ApprovalBmc, ApprovalVoteForInsert>(),
+        entity_columns::<ApprovalBmc, ApprovalVoteFilter>(),
+        // -- Bounty
+        entity_columns::<BountyBmc, Bounty>(),
+        entity_columns::<BountyBmc, BountyForCreate>(),
+        entity_columns::<BountyBmc, BountyForUpdate>(),
+        entity_columns::<BountyBmc, BountyForTransition>(),
+        entity_columns::<BountyBmc, BountyFilter>(),
+        // -- Bounty Application
+        entity_columns::<ApplicationBmc, Application>(),
+        entity_columns::<ApplicationBmc, ApplicationForInsert>(),
+        entity_columns::<ApplicationBmc, ApplicationForDecide>(),
+        entity_columns::<ApplicationBmc, ApplicationFilter>(),
+        // -- Deliverable
+        entity_columns::<DeliverableBmc, Deliverable>(),
+        entity_columns::<DeliverableBmc, DeliverableForCreate>(),
+        entity_columns::<DeliverableBmc, DeliverableFilter>(),
+        // -- Denom
+        entity_columns::<DenomBmc, Denom>(),
+        entity_columns::<DenomBmc, DenomForCreate>(),
+        entity_columns::<DenomBmc, DenomForUpdate>(),
+        entity_columns::<DenomBmc, DenomFilter>(),
+        // -- Notification
+        entity_columns::<NotificationBmc, Notification>(),
+        entity_columns::<NotificationBmc, NotificationForInsert>(),
+        entity_columns::<NotificationBmc, NotificationForRead>(),
+        entity_columns::<NotificationBmc, NotificationFilter>(),
+        entity_columns::<NotificationPrefsBmc, NotificationPrefs>(),
+        entity_columns::<NotificationPrefsBmc, NotificationPrefsRow>(),
+        entity_columns::<NotificationPrefsBmc, NotificationPrefsForInsert>(),
+        entity_columns::<NotificationPrefsBmc, NotificationPrefsForUpdate>(),
+        // -- Payout
+        entity_columns::<PayoutBmc, Payout>(),
+        entity_columns::<PayoutBmc, PayoutForInsert>(),
+        entity_columns::<PayoutBmc, PayoutForConfirmed>(),
+        entity_columns::<PayoutBountyBmc, PayoutBounty>(),
+        entity_columns::<PayoutBountyBmc, PayoutBountyForInsert>(),
+        entity_columns::<PayoutBountyBmc, PayoutBountyFilter>(),
+        // -- Project
+        entity_columns::<ProjectBmc, Project>(),
+        entity_columns::<ProjectBmc, ProjectForCreate>(),
+        entity_columns::<ProjectBmc, ProjectForUpdate>(),
+        entity_columns::<ProjectBmc, ProjectFilter>(),
+        // -- Submission
+        entity_columns::<SubmissionBmc, Submission>(),
+        entity_columns::<SubmissionBmc, SubmissionForInsert>(),
+        entity_columns::<SubmissionBmc, SubmissionForDecide>(),
+        entity_columns::<SubmissionBmc, SubmissionFilter>(),
+        entity_columns::<SubmissionItemBmc, SubmissionItem>(),
+        entity_columns::<SubmissionItemBmc, SubmissionItemForInsert>(),
+        entity_columns::<SubmissionItemBmc, SubmissionItemFilter>(),
+        // -- Tag
+        entity_columns::<TagBmc, Tag>(),
+        entity_columns::<TagBmc, TagForCreate>(),
+        // -- User
+        entity_columns::<UserBmc, User>(),
+        entity_columns::<UserBmc, UserForInsert>(),
+        entity_columns::<UserBmc, UserForUpdatePwd>(),
+        entity_columns::<UserBmc, UserForAdmin>(),
+        entity_columns::<UserBmc, UserForLogin>(),
+        entity_columns::<UserBmc, UserForAuth>(),
+        // -- Wallet
+        entity_columns::<WalletBmc, Wallet>(),
+        entity_columns::<WalletBmc, WalletForInsert>(),
+        entity_columns::<WalletBmc, WalletFilter>(),
+        // -- Webhook
+        entity_columns::<WebhookBmc, Webhook>(),
+        entity_columns::<WebhookBmc, WebhookForInsert>(),
+        entity_columns::<WebhookBmc, WebhookForPatch>(),
+        entity_columns::<WebhookBmc, WebhookFilter>(),
+        entity_columns::<WebhookDeliveryBmc, WebhookDelivery>(),
+        entity_columns::<WebhookDeliveryBmc, WebhookDeliveryForInsert>(),
+        entity_columns::<WebhookDeliveryBmc, WebhookDeliveryFilter>(),
+    ];
+
+    let mut mismatches = Vec::new();
+    for entity in entities {
+        let columns = table_columns(mm, entity.table).await?;
+        let missing_columns: Vec<String> = entity
+            .fields
+            .iter()
+            .filter(|f| !columns.iter().any(|c| c == *f))
+            .map(|f| f.to_string())
+            .collect();
+
+        if !missing_columns.is_empty() {
+            mismatches.push(SchemaMismatch {
+                table: entity.table,
+                entity: entity.entity,
+                missing_columns,
+            });
+        }
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::SchemaMismatch(mismatches))
+    }
+}
+
+async fn table_columns(mm: &ModelManager, table: &str) -> Result<Vec<String>> {
+    let columns: Vec<(String,)> = sqlx::query_as(
+        "SELECT column_name::text FROM information_schema.columns \
+         WHERE table_schema = current_schema() AND table_name = $1",
+    )
+    .bind(table)
+    .fetch_all(mm.db()?)
+    .await?;
+
+    Ok(columns.into_iter().map(|(c,)| c).collect())
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use anyhow::Result;
+
+    #[tokio::test]
+    async fn test_check_schema_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+
+        // -- Exec & Check
+        check_schema(&mm).await?;
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
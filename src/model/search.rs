@@ -0,0 +1,301 @@
+//! Full-text search across projects and bounties.
+//!
+//! Design:
+//!
+//! - Each searchable table has a `search_tsv` column, maintained by a trigger
+//!   (see `sql/migrations/0006_search.up.sql`), weighting the titles over
+//!   the descriptions.
+//! - The query uses the `websearch_to_tsquery` syntax
+//!   (e.g., `rust -frontend "smart contract"`).
+//! - Hits of both tables are ranked together, with the title and snippet
+//!   highlighted with `<mark>` tags. The source text is HTML-escaped before
+//!   highlighting, so the `<mark>` tags are the only markup of a hit.
+//! - Db only (not supported by the in-memory storage).
+//!
+
+use crate::ctx::Ctx;
+use crate::model::bounty::BountyStatus;
+use crate::model::{ListOptions, ModelManager, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+const SEARCH_LIMIT_DEFAULT: i64 = 20;
+const SEARCH_LIMIT_MAX: i64 = 100;
+
+const SQL_SEARCH: &str = r#"
+WITH q AS (SELECT websearch_to_tsquery('english', $1) AS query),
+hits AS (
+  SELECT 'project' AS entity, p.id, p.project_name AS title, p.project_description AS body,
+         ts_rank(p.search_tsv, q.query) AS rank,
+         NULL::bounty_status AS status, p.project_denom AS denom
+    FROM project p, q
+   WHERE p.search_tsv @@ q.query
+  UNION ALL
+  SELECT 'bounty', b.id, b.bounty_title, b.bounty_description,
+         ts_rank(b.search_tsv, q.query),
+         b.bounty_status, p.project_denom
+    FROM bounty b LEFT JOIN project p ON p.id = b.bounty_project_id, q
+   WHERE b.search_tsv @@ q.query
+)
+SELECT entity, id,
+       ts_headline('english', e.title, q.query, 'HighlightAll=true, StartSel=<mark>, StopSel=</mark>') AS title,
+       ts_headline('english', e.body, q.query, 'MaxFragments=2, StartSel=<mark>, StopSel=</mark>') AS snippet,
+       rank, status, denom
+  FROM hits, q,
+       LATERAL (
+         SELECT replace(replace(replace(replace(replace(hits.title,
+                  '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '"', '&quot;'), '''', '&#39;') AS title,
+                replace(replace(replace(replace(replace(hits.body,
+                  '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '"', '&quot;'), '''', '&#39;') AS body
+       ) e
+ WHERE ($2::text IS NULL OR entity = $2)
+   AND ($3::bounty_status IS NULL OR status = $3)
+   AND ($4::text IS NULL OR denom = $4)
+ ORDER BY rank DESC, entity, id
+ LIMIT $5 OFFSET $6
+"#;
+
+// region:    --- Search Types
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, strum_macros::AsRefStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SearchEntity {
+    Project,
+    Bounty,
+}
+
+/// All set properties must match.
+/// (`status` only matches bounties, `denom` is the project one for bounties)
+#[derive(Debug, Default, Deserialize)]
+pub struct SearchFilter {
+    pub entity: Option<SearchEntity>,
+    pub status: Option<BountyStatus>,
+    pub denom: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct SearchHit {
+    /// `project` or `bounty`
+    pub entity: String,
+    pub id: i64,
+    /// Highlighted title (HTML).
+    pub title: String,
+    /// Highlighted description fragments (HTML).
+    pub snippet: String,
+    pub rank: f32,
+    pub status: Option<BountyStatus>,
+    pub denom: Option<String>,
+}
+
+// endregion: --- Search Types
+
+/// Ranked hits of the `query` across projects and bounties.
+/// (`list_options.limit` defaults to `SEARCH_LIMIT_DEFAULT`, capped to `SEARCH_LIMIT_MAX`)
+pub async fn search(
+    _ctx: &Ctx,
+    mm: &ModelManager,
+    query: &str,
+    filter: Option<SearchFilter>,
+    list_options: Option<ListOptions>,
+) -> Result<Vec<SearchHit>> {
+    let SearchFilter {
+        entity,
+        status,
+        denom,
+    } = filter.unwrap_or_default();
+    let ListOptions { limit, offset } = list_options.unwrap_or_default();
+
+    let hits = mm
+        .dbx()?
+        .query_fetch_all(
+            sqlx::query_as(SQL_SEARCH)
+                .bind(query)
+                .bind(entity.map(|e| e.as_ref().to_string()))
+                .bind(status)
+                .bind(denom)
+                .bind(
+                    limit
+                        .unwrap_or(SEARCH_LIMIT_DEFAULT)
+                        .clamp(0, SEARCH_LIMIT_MAX),
+                )
+                .bind(offset.unwrap_or(0).max(0)),
+        )
+        .await?;
+
+    Ok(hits)
+}
+
+/// Recompute the search documents of all the projects and bounties
+/// (e.g., after a change of the trigger weights), see the
+/// `search_reindex` job. Returns the number of reindexed rows.
+pub async fn reindex(_ctx: &Ctx, mm: &ModelManager) -> Result<u64> {
+    let dbx = mm.dbx()?;
+    // (the triggers compute `search_tsv` on any update)
+    let projects = dbx
+        .query_exec(sqlx::query("UPDATE project SET search_tsv = NULL"))
+        .await?;
+    let bounties = dbx
+        .query_exec(sqlx::query("UPDATE bounty SET search_tsv = NULL"))
+        .await?;
+
+    Ok(projects + bounties)
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::Seed;
+    use crate::model::bounty::{BountyBmc, BountyForUpdate};
+    use anyhow::{Context, Result};
+
+    const SEED_TOML: &str = r#"
+[[projects]]
+key = "wallet"
+project_id = "search-wallet"
+project_name = "Wallet Kit"
+project_lead_address = "cosmos1udzkhs056fc0f2texd6cv30acg0rjept43xsph"
+project_budget = "1000"
+project_denom = "uosmo"
+project_type = "library"
+project_description = "Wallet connection components"
+
+[[bounties]]
+key = "keplr"
+project = "wallet"
+bounty_title = "Keplr connector"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "Connector"
+bounty_description = "Connect the wallet with the Keplr extension"
+
+[[bounties]]
+key = "docs"
+bounty_title = "Documentation"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "README"
+bounty_description = "Mention the wallet setup"
+
+[[bounties]]
+key = "xss"
+bounty_title = "<img src=x onerror=alert(1)> exploit"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "None"
+bounty_description = "An <script>alert('exploit')</script> & more"
+"#;
+
+    #[tokio::test]
+    async fn test_search_ranked_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let project_id = *seeded.projects.get("wallet").context("wallet")?;
+
+        // -- Exec
+        let hits = search(&ctx, &mm, "wallet", None, None).await?;
+
+        // -- Check
+        // Title match (weight A) first, description only matches last.
+        let ids: Vec<(&str, i64)> = hits.iter().map(|h| (h.entity.as_str(), h.id)).collect();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[0], ("project", project_id));
+        assert_eq!(hits[0].title, "<mark>Wallet</mark> Kit");
+        assert!(hits[2].snippet.contains("<mark>wallet</mark>"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_escapes_html_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+
+        // -- Exec
+        let hits = search(&ctx, &mm, "exploit", None, None).await?;
+
+        // -- Check
+        let hit = hits.first().context("should have a hit")?;
+        assert_eq!(
+            hit.title,
+            "&lt;img src=x onerror=alert(1)&gt; <mark>exploit</mark>"
+        );
+        // (the fragments can start after the escaped `<` of the text)
+        assert!(!hit.snippet.contains("<script"), "{}", hit.snippet);
+        assert!(hit.snippet.contains("&lt;/script&gt;"), "{}", hit.snippet);
+        assert!(hit.snippet.contains("&amp; more"), "{}", hit.snippet);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_filter_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let keplr_id = *seeded.bounties.get("keplr").context("keplr")?;
+
+        // -- Exec & Check - denom (the bounty project one)
+        let filter = SearchFilter {
+            entity: Some(SearchEntity::Bounty),
+            denom: Some("uosmo".to_string()),
+            ..Default::default()
+        };
+        let hits = search(&ctx, &mm, "wallet", Some(filter), None).await?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, keplr_id);
+
+        // -- Exec & Check - status
+        let filter = SearchFilter {
+            status: Some(BountyStatus::Assigned),
+            ..Default::default()
+        };
+        let hits = search(&ctx, &mm, "wallet", Some(filter), None).await?;
+        assert!(hits.is_empty(), "no assigned bounty");
+
+        // -- Exec & Check - pagination
+        let list_options = ListOptions {
+            limit: Some(1),
+            offset: Some(1),
+        };
+        let hits = search(&ctx, &mm, "wallet", None, Some(list_options)).await?;
+        assert_eq!(hits.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_trigger_on_update_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let docs_id = *seeded.bounties.get("docs").context("docs")?;
+
+        // -- Exec
+        let bounty_u = BountyForUpdate {
+            bounty_title: Some("Documentation for the indexer".to_string()),
+            bounty_reward: None,
+            bounty_deliverables: None,
+            bounty_description: None,
+            bounty_deadline: None,
+            version: None,
+        };
+        BountyBmc::update(&ctx, &mm, docs_id, bounty_u).await?;
+
+        // -- Check
+        let hits = search(&ctx, &mm, "indexer", None, None).await?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, docs_id);
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
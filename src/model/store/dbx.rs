@@ -0,0 +1,252 @@
+//! Db executor with optional transaction.
+//!
+//! `Dbx` runs the `sqlb` builders either on the pool, or, when created
+//! with a transaction (see `ModelManager::transaction`), on the shared
+//! open transaction. Nested `begin_txn` calls use savepoints.
+//!
+
+use crate::model::store::{Db, Error, Result};
+use sqlb::SqlBuilder;
+use sqlx::postgres::{PgArguments, PgRow};
+use sqlx::query::{Query, QueryAs};
+use sqlx::{Execute, FromRow, Postgres, Transaction};
+use std::sync::Arc;
+use tokio::sync::{Mutex, MutexGuard};
+
+#[derive(Clone)]
+pub struct Dbx {
+    db_pool: Db,
+    txn_holder: Arc<Mutex<Option<TxnHolder>>>,
+    with_txn: bool,
+}
+
+struct TxnHolder {
+    txn: Transaction<'static, Postgres>,
+    /// Number of open (nested) transactions, 1 being the db transaction,
+    /// the others savepoints.
+    depth: usize,
+}
+
+impl Dbx {
+    pub fn new(db_pool: Db, with_txn: bool) -> Self {
+        Dbx {
+            db_pool,
+            txn_holder: Arc::default(),
+            with_txn,
+        }
+    }
+
+    pub fn with_txn(&self) -> bool {
+        self.with_txn
+    }
+
+    pub fn db(&self) -> &Db {
+        &self.db_pool
+    }
+}
+
+// region:    --- Txn
+
+impl Dbx {
+    /// Begin a transaction, or a savepoint if one is already open.
+    pub async fn begin_txn(&self) -> Result<()> {
+        if !self.with_txn {
+            return Err(Error::TxnCantBeginNoTxnDbx);
+        }
+
+        let mut holder = self.txn_holder.lock().await;
+        match holder.as_mut() {
+            Some(holder) => {
+                let sql = format!("SAVEPOINT sp_{}", holder.depth);
+                sqlx::query(&sql)
+                    .execute(&mut *holder.txn)
+                    .await
+                    .map_err(txn_fail)?;
+                holder.depth += 1;
+            }
+            None => {
+                let txn = self.db_pool.begin().await.map_err(txn_fail)?;
+                *holder = Some(TxnHolder { txn, depth: 1 });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Commit the innermost open transaction (or release its savepoint).
+    pub async fn commit_txn(&self) -> Result<()> {
+        let mut guard = self.txn_holder.lock().await;
+        let holder = guard.as_mut().ok_or(Error::TxnCantCommitNoOpenTxn)?;
+
+        if holder.depth > 1 {
+            holder.depth -= 1;
+            let sql = format!("RELEASE SAVEPOINT sp_{}", holder.depth);
+            sqlx::query(&sql)
+                .execute(&mut *holder.txn)
+                .await
+                .map_err(txn_fail)?;
+
+            Ok(())
+        } else {
+            let holder = guard.take().ok_or(Error::TxnCantCommitNoOpenTxn)?;
+            holder.txn.commit().await.map_err(txn_fail)
+        }
+    }
+
+    /// Roll back the innermost open transaction (or to its savepoint).
+    pub async fn rollback_txn(&self) -> Result<()> {
+        let mut guard = self.txn_holder.lock().await;
+        let holder = guard.as_mut().ok_or(Error::TxnCantRollbackNoOpenTxn)?;
+
+        if holder.depth > 1 {
+            holder.depth -= 1;
+            let sql = format!("ROLLBACK TO SAVEPOINT sp_{}", holder.depth);
+            sqlx::query(&sql)
+                .execute(&mut *holder.txn)
+                .await
+                .map_err(txn_fail)?;
+
+            Ok(())
+        } else {
+            let holder = guard.take().ok_or(Error::TxnCantRollbackNoOpenTxn)?;
+            holder.txn.rollback().await.map_err(txn_fail)
+        }
+    }
+}
+
+fn txn_fail(ex: sqlx::Error) -> Error {
+    Error::TxnFail(ex.to_string())
+}
+
+// endregion: --- Txn
+
+// region:    --- Executors
+
+// Note: The sqlx errors are returned as is, so that the model layer
+//       can decode them (e.g., constraint violations).
+impl Dbx {
+    pub async fn fetch_one<'a, Q, D>(&self, sb: &'a Q) -> core::result::Result<D, sqlx::Error>
+    where
+        Q: SqlBuilder<'a>,
+        D: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+    {
+        let (sql, args) = sql_and_args(sb);
+        self.query_fetch_one(sqlx::query_as_with(&sql, args)).await
+    }
+
+    pub async fn fetch_optional<'a, Q, D>(
+        &self,
+        sb: &'a Q,
+    ) -> core::result::Result<Option<D>, sqlx::Error>
+    where
+        Q: SqlBuilder<'a>,
+        D: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+    {
+        let (sql, args) = sql_and_args(sb);
+        self.query_fetch_optional(sqlx::query_as_with(&sql, args))
+            .await
+    }
+
+    pub async fn fetch_all<'a, Q, D>(&self, sb: &'a Q) -> core::result::Result<Vec<D>, sqlx::Error>
+    where
+        Q: SqlBuilder<'a>,
+        D: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+    {
+        let (sql, args) = sql_and_args(sb);
+        self.query_fetch_all(sqlx::query_as_with(&sql, args)).await
+    }
+
+    pub async fn exec<'a, Q>(&self, sb: &'a Q) -> core::result::Result<u64, sqlx::Error>
+    where
+        Q: SqlBuilder<'a>,
+    {
+        let (sql, args) = sql_and_args(sb);
+        self.query_exec(sqlx::query_with(&sql, args)).await
+    }
+}
+
+// -- Raw sqlx queries (for what sqlb cannot express).
+impl Dbx {
+    pub async fn query_fetch_one<'q, D>(
+        &self,
+        query: QueryAs<'q, Postgres, D, PgArguments>,
+    ) -> core::result::Result<D, sqlx::Error>
+    where
+        D: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+    {
+        let mut guard = self.txn_holder().await;
+        match guard.as_deref_mut().and_then(Option::as_mut) {
+            Some(holder) => query.fetch_one(&mut *holder.txn).await,
+            None => query.fetch_one(&self.db_pool).await,
+        }
+    }
+
+    pub async fn query_fetch_optional<'q, D>(
+        &self,
+        query: QueryAs<'q, Postgres, D, PgArguments>,
+    ) -> core::result::Result<Option<D>, sqlx::Error>
+    where
+        D: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+    {
+        let mut guard = self.txn_holder().await;
+        match guard.as_deref_mut().and_then(Option::as_mut) {
+            Some(holder) => query.fetch_optional(&mut *holder.txn).await,
+            None => query.fetch_optional(&self.db_pool).await,
+        }
+    }
+
+    pub async fn query_fetch_all<'q, D>(
+        &self,
+        query: QueryAs<'q, Postgres, D, PgArguments>,
+    ) -> core::result::Result<Vec<D>, sqlx::Error>
+    where
+        D: for<'r> FromRow<'r, PgRow> + Unpin + Send,
+    {
+        let mut guard = self.txn_holder().await;
+        match guard.as_deref_mut().and_then(Option::as_mut) {
+            Some(holder) => query.fetch_all(&mut *holder.txn).await,
+            None => query.fetch_all(&self.db_pool).await,
+        }
+    }
+
+    pub async fn query_exec<'q>(
+        &self,
+        query: Query<'q, Postgres, PgArguments>,
+    ) -> core::result::Result<u64, sqlx::Error> {
+        let mut guard = self.txn_holder().await;
+        let res = match guard.as_deref_mut().and_then(Option::as_mut) {
+            Some(holder) => query.execute(&mut *holder.txn).await?,
+            None => query.execute(&self.db_pool).await?,
+        };
+
+        Ok(res.rows_affected())
+    }
+
+    /// The open transaction guard, if this Dbx is transactional.
+    async fn txn_holder(&self) -> Option<MutexGuard<'_, Option<TxnHolder>>> {
+        if self.with_txn {
+            Some(self.txn_holder.lock().await)
+        } else {
+            None
+        }
+    }
+}
+
+/// Render the sqlb builder into its sql and bound arguments.
+fn sql_and_args<'a, Q>(sb: &'a Q) -> (String, PgArguments)
+where
+    Q: SqlBuilder<'a>,
+{
+    let sql = sb.sql();
+    let args = {
+        let mut query = sqlx::query::<Postgres>(&sql);
+        for val in sb.vals() {
+            query = val.bind_query(query);
+        }
+        query.take_arguments().unwrap_or_default()
+    };
+
+    (sql, args)
+}
+
+// endregion: --- Executors
@@ -5,6 +5,43 @@ pub type Result<T> = core::result::Result<T, Error>;
 #[derive(Debug, Serialize)]
 pub enum Error {
     FailToCreatePool(String),
+    /// Db only operation (e.g., migrations, raw sql) on the in-memory storage.
+    NoDbInMemoryStorage,
+
+    // -- Txn
+    TxnCantBeginNoTxnDbx,
+    TxnCantCommitNoOpenTxn,
+    TxnCantRollbackNoOpenTxn,
+    TxnFail(String),
+
+    // -- Migration
+    MigrationDirFail {
+        dir: String,
+        cause: String,
+    },
+    MigrationDuplicateVersion {
+        version: i64,
+    },
+    MigrationTableFail(String),
+    MigrationLockFail(String),
+    MigrationChecksumMismatch {
+        version: i64,
+    },
+    MigrationMissing {
+        version: i64,
+    },
+    MigrationNoDown {
+        version: i64,
+    },
+    /// Pending migration older than the last applied one.
+    MigrationOutOfOrder {
+        version: i64,
+        last_applied: i64,
+    },
+    MigrationFailed {
+        version: i64,
+        cause: String,
+    },
 }
 
 // region:    --- Error Boilerplate
@@ -0,0 +1,500 @@
+//! In-memory store, for tests and local demos without a db process.
+//!
+//! Design:
+//!
+//! - Rows are `serde_json` objects, per table, keyed by id. `model::base`
+//!   maps the entities to/from rows, so the `DbBmc` semantics are the same
+//!   as on Postgres (ids starting at 1000, `version`, unique fields,
+//!   not found and version conflict errors).
+//! - Each transaction handle (see `new_with_txn`) keeps an undo log of its
+//!   own writes (the previous rows), replayed backwards on rollback (nested
+//!   ones included), so the writes committed meanwhile by others are kept.
+//!   Transactions are not isolated from each other (no locks, a row written
+//!   by both is restored to its state before the rolled back write), which
+//!   is fine for the small, mostly sequential, use cases of this store.
+//!   As Postgres sequences, the ids are not reused after a rollback.
+//!
+
+use crate::model::store::{Error as StoreError, Result as StoreResult};
+use crate::model::{Error, Result};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A table row, column name to value.
+pub type Row = Map<String, Value>;
+
+/// Same start as the `GENERATED ... IDENTITY (START WITH 1000)` of the sql schema.
+const ID_START: i64 = 1000;
+
+#[derive(Clone, Default)]
+pub struct MemStore {
+    state: Arc<Mutex<MemState>>,
+    /// Undo log of each open (nested) transaction of this handle.
+    txn_undos: Arc<Mutex<Vec<Vec<Undo>>>>,
+}
+
+#[derive(Default)]
+struct MemState {
+    tables: HashMap<&'static str, Table>,
+}
+
+/// The row before a transaction write (`None` if inserted).
+struct Undo {
+    table: &'static str,
+    id: i64,
+    row: Option<Row>,
+}
+
+#[derive(Clone)]
+struct Table {
+    rows: BTreeMap<i64, Row>,
+    next_id: i64,
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Table {
+            rows: BTreeMap::new(),
+            next_id: ID_START,
+        }
+    }
+}
+
+// region:    --- Rows
+
+impl MemStore {
+    /// Insert the row, with its generated id (and `version` if versioned).
+    /// Returns the new id.
+    pub fn insert(
+        &self,
+        table: &'static str,
+        unique_fields: &[&'static str],
+        versioned: bool,
+        data: Row,
+    ) -> Result<i64> {
+        let mut state = self.state();
+        let table_rows = state.tables.entry(table).or_default();
+
+        check_unique(table, &table_rows.rows, unique_fields, None, &data)?;
+
+        let id = table_rows.next_id;
+        table_rows.next_id += 1;
+
+        let mut row = data;
+        row.insert("id".to_string(), id.into());
+        if versioned {
+            row.insert("version".to_string(), 1.into());
+        }
+        table_rows.rows.insert(id, row);
+        self.record_undo(table, id, None);
+
+        Ok(id)
+    }
+
+    pub fn get(&self, table: &'static str, id: i64) -> Option<Row> {
+        self.state()
+            .tables
+            .get(table)
+            .and_then(|t| t.rows.get(&id).cloned())
+    }
+
+    /// The rows matching all the `filter` values, in id order.
+    pub fn select(&self, table: &'static str, filter: &Row) -> Vec<Row> {
+        let state = self.state();
+        let Some(table_rows) = state.tables.get(table) else {
+            return Vec::new();
+        };
+
+        table_rows
+            .rows
+            .values()
+            .filter(|row| filter.iter().all(|(name, val)| row.get(name) == Some(val)))
+            .cloned()
+            .collect()
+    }
+
+    /// Update the row `data` columns, checking the `expected_version` if given.
+    pub fn update(
+        &self,
+        table: &'static str,
+        unique_fields: &[&'static str],
+        versioned: bool,
+        id: i64,
+        expected_version: Option<i64>,
+        data: Row,
+    ) -> Result<()> {
+        let mut state = self.state();
+        let table_rows = state.tables.entry(table).or_default();
+
+        check_unique(table, &table_rows.rows, unique_fields, Some(id), &data)?;
+
+        let row = table_rows
+            .rows
+            .get_mut(&id)
+            .ok_or(Error::EntityNotFound { entity: table, id })?;
+
+        let current = row.get("version").and_then(Value::as_i64).unwrap_or(1);
+        if let Some(expected) = expected_version.filter(|v| versioned && *v != current) {
+            return Err(Error::VersionConflict {
+                entity: table,
+                id,
+                expected,
+                current,
+            });
+        }
+
+        self.record_undo(table, id, Some(row.clone()));
+        if versioned {
+            row.insert("version".to_string(), (current + 1).into());
+        }
+        row.extend(data);
+
+        Ok(())
+    }
+
+    /// Returns false if there was no row to delete.
+    pub fn delete(&self, table: &'static str, id: i64) -> bool {
+        let removed = self
+            .state()
+            .tables
+            .get_mut(table)
+            .and_then(|t| t.rows.remove(&id));
+
+        match removed {
+            Some(row) => {
+                self.record_undo(table, id, Some(row));
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, MemState> {
+        self.state.lock().unwrap_or_else(|ex| ex.into_inner())
+    }
+
+    fn txn_undos(&self) -> MutexGuard<'_, Vec<Vec<Undo>>> {
+        self.txn_undos.lock().unwrap_or_else(|ex| ex.into_inner())
+    }
+
+    /// Log the previous `row` of a write, if in a transaction.
+    fn record_undo(&self, table: &'static str, id: i64, row: Option<Row>) {
+        if let Some(undos) = self.txn_undos().last_mut() {
+            undos.push(Undo { table, id, row });
+        }
+    }
+}
+
+/// Fails with `UniqueViolation` if another row (than `id`)
+/// has the same value for one of the `unique_fields` of `data`.
+fn check_unique(
+    table: &'static str,
+    rows: &BTreeMap<i64, Row>,
+    unique_fields: &[&'static str],
+    id: Option<i64>,
+    data: &Row,
+) -> Result<()> {
+    for field in unique_fields {
+        let Some(val) = data.get(*field) else {
+            continue;
+        };
+        let taken = rows
+            .iter()
+            .any(|(row_id, row)| Some(*row_id) != id && row.get(*field) == Some(val));
+        if taken {
+            return Err(Error::UniqueViolation {
+                entity: table.to_string(),
+                field: field.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// endregion: --- Rows
+
+// region:    --- Txn
+
+impl MemStore {
+    /// A handle on the same tables, with its own transactions.
+    pub fn new_with_txn(&self) -> MemStore {
+        MemStore {
+            state: self.state.clone(),
+            txn_undos: Arc::default(),
+        }
+    }
+
+    pub fn begin_txn(&self) {
+        self.txn_undos().push(Vec::new());
+    }
+
+    /// Keep the writes, in the parent transaction undo log if nested.
+    pub fn commit_txn(&self) -> StoreResult<()> {
+        let mut txn_undos = self.txn_undos();
+        let undos = txn_undos.pop().ok_or(StoreError::TxnCantCommitNoOpenTxn)?;
+        if let Some(parent) = txn_undos.last_mut() {
+            parent.extend(undos);
+        }
+
+        Ok(())
+    }
+
+    /// Undo the writes of the transaction, latest first.
+    pub fn rollback_txn(&self) -> StoreResult<()> {
+        let undos = self
+            .txn_undos()
+            .pop()
+            .ok_or(StoreError::TxnCantRollbackNoOpenTxn)?;
+
+        let mut state = self.state();
+        for Undo { table, id, row } in undos.into_iter().rev() {
+            let table_rows = state.tables.entry(table).or_default();
+            match row {
+                Some(row) => table_rows.rows.insert(id, row),
+                None => table_rows.rows.remove(&id),
+            };
+        }
+
+        Ok(())
+    }
+}
+
+// endregion: --- Txn
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use crate::ctx::Ctx;
+    use crate::model::bounty::{BountyBmc, BountyFilter, BountyForCreate, BountyForUpdate};
+    use crate::model::denom::{DenomBmc, DenomForCreate};
+    use crate::model::project::{ProjectBmc, ProjectForCreate};
+    use crate::model::user::{UserBmc, UserForCreate, UserForLogin};
+    use crate::model::{Error, ModelManager};
+    use anyhow::{anyhow, Context, Result};
+
+    fn bounty_c(title: &str) -> BountyForCreate {
+        BountyForCreate {
+            bounty_title: title.to_string(),
+            bounty_assignee: title.to_string(),
+            bounty_reward: title.to_string(),
+            bounty_deliverables: title.to_string(),
+            bounty_description: title.to_string(),
+            bounty_project_id: None,
+            bounty_deadline: None,
+        }
+    }
+
+    fn project_c(project_id: &str) -> ProjectForCreate {
+        ProjectForCreate {
+            project_id: project_id.to_string(),
+            project_name: project_id.to_string(),
+            project_lead_address: project_id.to_string(),
+            project_budget: "100".to_string(),
+            project_denom: "uatom".to_string(),
+            project_type: project_id.to_string(),
+            project_description: project_id.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_bounty_crud_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = ModelManager::new_in_memory();
+        let ctx = Ctx::root_ctx();
+        let fx_titles = [
+            "test_memory_bounty_crud_ok 01",
+            "test_memory_bounty_crud_ok 02",
+        ];
+
+        // -- Exec
+        let id_01 = BountyBmc::create(&ctx, &mm, bounty_c(fx_titles[0])).await?;
+        let id_02 = BountyBmc::create(&ctx, &mm, bounty_c(fx_titles[1])).await?;
+        BountyBmc::update(
+            &ctx,
+            &mm,
+            id_01,
+            BountyForUpdate {
+                bounty_title: None,
+                bounty_reward: None,
+                bounty_deliverables: None,
+                bounty_description: Some("alice".to_string()),
+                bounty_deadline: None,
+                version: Some(1),
+            },
+        )
+        .await?;
+        BountyBmc::delete(&ctx, &mm, id_02).await?;
+
+        // -- Check
+        assert_eq!(id_01, 1000);
+        let bounty = BountyBmc::get(&ctx, &mm, id_01).await?;
+        assert_eq!(bounty.bounty_title, fx_titles[0]);
+        assert_eq!(bounty.bounty_description, "alice");
+        assert_eq!(bounty.version, 2);
+
+        let filter = BountyFilter {
+            bounty_assignee: Some(fx_titles[0].to_string()),
+            ..Default::default()
+        };
+        let bounties = BountyBmc::list(&ctx, &mm, Some(filter), None).await?;
+        assert_eq!(bounties.len(), 1);
+
+        let res = BountyBmc::get(&ctx, &mm, id_02).await;
+        assert!(
+            matches!(res, Err(Error::EntityNotFound { entity: "bounty", id }) if id == id_02),
+            "EntityNotFound not matching"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_update_err_version_conflict() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = ModelManager::new_in_memory();
+        let ctx = Ctx::root_ctx();
+        let id = BountyBmc::create(&ctx, &mm, bounty_c("test_memory_update_version")).await?;
+        let bounty_u = || BountyForUpdate {
+            bounty_title: Some("updated".to_string()),
+            bounty_reward: None,
+            bounty_deliverables: None,
+            bounty_description: None,
+            bounty_deadline: None,
+            version: Some(1),
+        };
+        BountyBmc::update(&ctx, &mm, id, bounty_u()).await?;
+
+        // -- Exec
+        let res = BountyBmc::update(&ctx, &mm, id, bounty_u()).await;
+
+        // -- Check
+        assert!(
+            matches!(
+                res,
+                Err(Error::VersionConflict {
+                    expected: 1,
+                    current: 2,
+                    ..
+                })
+            ),
+            "VersionConflict not matching"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_create_err_unique() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = ModelManager::new_in_memory();
+        let ctx = Ctx::root_ctx();
+        let fx_project_id = "test_memory_create_err_unique";
+        let denom_c = DenomForCreate {
+            denom_base: "uatom".to_string(),
+            denom_display: "atom".to_string(),
+            denom_exponent: 6,
+            denom_symbol: "ATOM".to_string(),
+            denom_enabled: None,
+        };
+        DenomBmc::create(&ctx, &mm, denom_c).await?;
+        ProjectBmc::create(&ctx, &mm, project_c(fx_project_id)).await?;
+
+        // -- Exec
+        let res = ProjectBmc::create(&ctx, &mm, project_c(fx_project_id)).await;
+
+        // -- Check
+        assert!(
+            matches!(
+                &res,
+                Err(Error::UniqueViolation { entity, field })
+                    if entity == "project" && field == "project_id"
+            ),
+            "UniqueViolation not matching: {res:?}"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_transaction_rollback_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = ModelManager::new_in_memory();
+        let ctx = &Ctx::root_ctx();
+
+        // -- Exec
+        let res: Result<()> = mm
+            .transaction(|mm| async move {
+                BountyBmc::create(ctx, &mm, bounty_c("test_memory_transaction")).await?;
+                Err(anyhow!("fail after create"))
+            })
+            .await;
+
+        // -- Check
+        assert!(res.is_err(), "transaction should fail");
+        assert!(BountyBmc::list(ctx, &mm, None, None).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_transaction_rollback_keeps_concurrent_writes() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = ModelManager::new_in_memory();
+        let ctx = &Ctx::root_ctx();
+        let fx_id = BountyBmc::create(ctx, &mm, bounty_c("before")).await?;
+
+        // -- Exec
+        // (the outer `mm` writes commit right away, while the transaction is open)
+        let outer_mm = mm.clone();
+        let res: Result<()> = mm
+            .transaction(|mm| async move {
+                BountyBmc::create(ctx, &mm, bounty_c("in_txn")).await?;
+                BountyBmc::delete(ctx, &mm, fx_id).await?;
+                BountyBmc::create(ctx, &outer_mm, bounty_c("concurrent")).await?;
+                Err(anyhow!("fail after the writes"))
+            })
+            .await;
+
+        // -- Check
+        assert!(res.is_err(), "transaction should fail");
+        let titles: Vec<String> = BountyBmc::list(ctx, &mm, None, None)
+            .await?
+            .into_iter()
+            .map(|b| b.bounty_title)
+            .collect();
+        assert_eq!(titles, vec!["before", "concurrent"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_user_create_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = ModelManager::new_in_memory();
+        let ctx = Ctx::root_ctx();
+        let fx_username = "test_memory_user_create_ok";
+
+        // -- Exec
+        UserBmc::create(
+            &ctx,
+            &mm,
+            UserForCreate {
+                username: fx_username.to_string(),
+                pwd_clear: "welcome-pwd".to_string(),
+            },
+        )
+        .await?;
+
+        // -- Check
+        let user: UserForLogin = UserBmc::first_by_username(&ctx, &mm, fx_username)
+            .await?
+            .context("user should exist")?;
+        assert!(user.pwd.is_some(), "pwd should be set");
+        assert_ne!(user.pwd_salt, user.token_salt);
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
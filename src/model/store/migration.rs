@@ -0,0 +1,472 @@
+//! Versioned SQL migrations.
+//!
+//! Design:
+//!
+//! - Migrations are files in the `DB_MIGRATIONS_DIR` folder, named
+//!   `{version}_{name}.up.sql` and `{version}_{name}.down.sql`
+//!   (e.g., `0001_init.up.sql`). The `down` file is optional,
+//!   but a migration without one cannot be rolled back.
+//! - Applied migrations are tracked in the `schema_migrations` table,
+//!   with the checksum of their `up` file. Editing an applied migration
+//!   is reported as a checksum mismatch rather than silently ignored.
+//! - Each migration runs with its tracking row in a single transaction.
+//!   Files are executed as a whole (no statement splitting), so functions
+//!   and `DO` blocks are supported.
+//! - `migrate` and `rollback` hold a session advisory lock for the whole run,
+//!   so concurrent app instances starting together apply each migration once.
+//! - A pending migration with a version lower than the last applied one
+//!   (e.g., from a merged branch) is an error, not silently applied out of order.
+//!
+
+use crate::model::store::{Db, Error, Result};
+use sha2::{Digest, Sha256};
+use sqlx::pool::PoolConnection;
+use sqlx::{FromRow, PgConnection, Postgres};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+const SQL_CREATE_MIGRATIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS schema_migrations (
+  version BIGINT PRIMARY KEY,
+  name varchar(256) NOT NULL,
+  checksum varchar(256) NOT NULL,
+  applied_at timestamp with time zone NOT NULL DEFAULT now()
+)"#;
+
+/// Key of the `pg_advisory_lock` held while migrating.
+const MIGRATION_LOCK_KEY: i64 = 0x706f_7274_615f_6d67; // "porta_mg"
+
+// region:    --- Migration Types
+
+#[derive(Debug, Clone)]
+pub struct Migration {
+    pub version: i64,
+    pub name: String,
+    pub up_sql: String,
+    pub down_sql: Option<String>,
+    pub checksum: String,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct AppliedMigration {
+    version: i64,
+    name: String,
+    checksum: String,
+}
+
+#[derive(Debug)]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    ChecksumMismatch,
+    /// Applied in the db, but its files are gone.
+    Missing,
+}
+
+#[derive(Debug)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub state: MigrationState,
+}
+
+// endregion: --- Migration Types
+
+// region:    --- Runner
+
+/// Apply all the pending migrations, in version order.
+/// Returns the applied versions.
+pub async fn migrate(db: &Db, dir: &str) -> Result<Vec<i64>> {
+    let migrations = load_migrations(dir)?;
+
+    let mut conn = lock(db).await?;
+    let res = migrate_locked(&mut conn, &migrations).await;
+    unlock(conn).await;
+
+    res
+}
+
+async fn migrate_locked(
+    conn: &mut PgConnection,
+    migrations: &BTreeMap<i64, Migration>,
+) -> Result<Vec<i64>> {
+    let applied = applied_migrations(&mut *conn).await?;
+
+    check_checksums(migrations, &applied)?;
+    check_order(migrations, &applied)?;
+
+    let mut versions = Vec::new();
+    for migration in migrations.values() {
+        if applied.contains_key(&migration.version) {
+            continue;
+        }
+
+        info!(
+            "{:<12} - migrate: {:04}_{}",
+            "MIGRATION", migration.version, migration.name
+        );
+
+        let mut tx = sqlx::Connection::begin(&mut *conn)
+            .await
+            .map_err(migration_failed(migration))?;
+        sqlx::raw_sql(&migration.up_sql)
+            .execute(&mut *tx)
+            .await
+            .map_err(migration_failed(migration))?;
+        sqlx::query("INSERT INTO schema_migrations (version, name, checksum) VALUES ($1, $2, $3)")
+            .bind(migration.version)
+            .bind(&migration.name)
+            .bind(&migration.checksum)
+            .execute(&mut *tx)
+            .await
+            .map_err(migration_failed(migration))?;
+        tx.commit().await.map_err(migration_failed(migration))?;
+
+        versions.push(migration.version);
+    }
+
+    Ok(versions)
+}
+
+/// Roll back the last applied migration.
+/// Returns the rolled back version, or `None` if nothing was applied.
+pub async fn rollback(db: &Db, dir: &str) -> Result<Option<i64>> {
+    let migrations = load_migrations(dir)?;
+
+    let mut conn = lock(db).await?;
+    let res = rollback_locked(&mut conn, &migrations).await;
+    unlock(conn).await;
+
+    res
+}
+
+async fn rollback_locked(
+    conn: &mut PgConnection,
+    migrations: &BTreeMap<i64, Migration>,
+) -> Result<Option<i64>> {
+    let applied = applied_migrations(&mut *conn).await?;
+
+    let Some(last) = applied.values().next_back() else {
+        return Ok(None);
+    };
+
+    let migration = migrations
+        .get(&last.version)
+        .ok_or(Error::MigrationMissing {
+            version: last.version,
+        })?;
+    let down_sql = migration.down_sql.as_ref().ok_or(Error::MigrationNoDown {
+        version: migration.version,
+    })?;
+
+    info!(
+        "{:<12} - rollback: {:04}_{}",
+        "MIGRATION", migration.version, migration.name
+    );
+
+    let mut tx = sqlx::Connection::begin(&mut *conn)
+        .await
+        .map_err(migration_failed(migration))?;
+    sqlx::raw_sql(down_sql)
+        .execute(&mut *tx)
+        .await
+        .map_err(migration_failed(migration))?;
+    sqlx::query("DELETE FROM schema_migrations WHERE version = $1")
+        .bind(migration.version)
+        .execute(&mut *tx)
+        .await
+        .map_err(migration_failed(migration))?;
+    tx.commit().await.map_err(migration_failed(migration))?;
+
+    Ok(Some(migration.version))
+}
+
+/// Status of every known migration (from the files and the db), in version order.
+pub async fn status(db: &Db, dir: &str) -> Result<Vec<MigrationStatus>> {
+    let migrations = load_migrations(dir)?;
+    let mut conn = db
+        .acquire()
+        .await
+        .map_err(|ex| Error::MigrationTableFail(ex.to_string()))?;
+    let applied = applied_migrations(&mut conn).await?;
+
+    let mut statuses: Vec<MigrationStatus> = migrations
+        .values()
+        .map(|m| MigrationStatus {
+            version: m.version,
+            name: m.name.clone(),
+            state: match applied.get(&m.version) {
+                Some(a) if a.checksum == m.checksum => MigrationState::Applied,
+                Some(_) => MigrationState::ChecksumMismatch,
+                None => MigrationState::Pending,
+            },
+        })
+        .collect();
+
+    statuses.extend(
+        applied
+            .values()
+            .filter(|a| !migrations.contains_key(&a.version))
+            .map(|a| MigrationStatus {
+                version: a.version,
+                name: a.name.clone(),
+                state: MigrationState::Missing,
+            }),
+    );
+    statuses.sort_by_key(|s| s.version);
+
+    Ok(statuses)
+}
+
+/// Take the migration lock on a pool connection, waiting for the
+/// other instances to be done.
+async fn lock(db: &Db) -> Result<PoolConnection<Postgres>> {
+    let mut conn = db
+        .acquire()
+        .await
+        .map_err(|ex| Error::MigrationLockFail(ex.to_string()))?;
+    sqlx::query("SELECT pg_advisory_lock($1)")
+        .bind(MIGRATION_LOCK_KEY)
+        .execute(&mut *conn)
+        .await
+        .map_err(|ex| Error::MigrationLockFail(ex.to_string()))?;
+
+    Ok(conn)
+}
+
+/// Release the migration lock. If the unlock fails, the connection is
+/// closed rather than returned to the pool (which releases the lock).
+async fn unlock(mut conn: PoolConnection<Postgres>) {
+    let unlocked = sqlx::query("SELECT pg_advisory_unlock($1)")
+        .bind(MIGRATION_LOCK_KEY)
+        .execute(&mut *conn)
+        .await;
+    if unlocked.is_err() {
+        drop(conn.detach());
+    }
+}
+
+fn migration_failed(migration: &Migration) -> impl Fn(sqlx::Error) -> Error + '_ {
+    move |ex| Error::MigrationFailed {
+        version: migration.version,
+        cause: ex.to_string(),
+    }
+}
+
+fn check_checksums(
+    migrations: &BTreeMap<i64, Migration>,
+    applied: &BTreeMap<i64, AppliedMigration>,
+) -> Result<()> {
+    for a in applied.values() {
+        if let Some(m) = migrations.get(&a.version) {
+            if m.checksum != a.checksum {
+                return Err(Error::MigrationChecksumMismatch { version: a.version });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Pending migrations must all come after the last applied one.
+fn check_order(
+    migrations: &BTreeMap<i64, Migration>,
+    applied: &BTreeMap<i64, AppliedMigration>,
+) -> Result<()> {
+    let Some(&last_applied) = applied.keys().next_back() else {
+        return Ok(());
+    };
+
+    let out_of_order = migrations
+        .keys()
+        .find(|version| **version < last_applied && !applied.contains_key(version));
+    if let Some(&version) = out_of_order {
+        return Err(Error::MigrationOutOfOrder {
+            version,
+            last_applied,
+        });
+    }
+
+    Ok(())
+}
+
+async fn applied_migrations(conn: &mut PgConnection) -> Result<BTreeMap<i64, AppliedMigration>> {
+    sqlx::raw_sql(SQL_CREATE_MIGRATIONS_TABLE)
+        .execute(&mut *conn)
+        .await
+        .map_err(|ex| Error::MigrationTableFail(ex.to_string()))?;
+
+    let applied: Vec<AppliedMigration> =
+        sqlx::query_as("SELECT version, name, checksum FROM schema_migrations ORDER BY version")
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|ex| Error::MigrationTableFail(ex.to_string()))?;
+
+    Ok(applied.into_iter().map(|a| (a.version, a)).collect())
+}
+
+// endregion: --- Runner
+
+// region:    --- Migration Files
+
+/// Load the migrations from the `dir` folder, keyed by version.
+pub fn load_migrations(dir: &str) -> Result<BTreeMap<i64, Migration>> {
+    let entries = fs::read_dir(dir).map_err(|ex| Error::MigrationDirFail {
+        dir: dir.to_string(),
+        cause: ex.to_string(),
+    })?;
+
+    let mut ups: BTreeMap<i64, (String, String)> = BTreeMap::new();
+    let mut downs: BTreeMap<i64, String> = BTreeMap::new();
+
+    for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let Some((version, name, is_up)) = parse_file_name(file_name) else {
+            continue;
+        };
+        let content = read_file(&path)?;
+
+        if is_up {
+            if ups.insert(version, (name, content)).is_some() {
+                return Err(Error::MigrationDuplicateVersion { version });
+            }
+        } else if downs.insert(version, content).is_some() {
+            return Err(Error::MigrationDuplicateVersion { version });
+        }
+    }
+
+    let migrations = ups
+        .into_iter()
+        .map(|(version, (name, up_sql))| {
+            let migration = Migration {
+                version,
+                name,
+                checksum: checksum(&up_sql),
+                down_sql: downs.remove(&version),
+                up_sql,
+            };
+            (version, migration)
+        })
+        .collect();
+
+    Ok(migrations)
+}
+
+/// Parse `0001_init.up.sql` into `(1, "init", true)`.
+fn parse_file_name(file_name: &str) -> Option<(i64, String, bool)> {
+    let (stem, is_up) = if let Some(stem) = file_name.strip_suffix(".up.sql") {
+        (stem, true)
+    } else {
+        (file_name.strip_suffix(".down.sql")?, false)
+    };
+    let (version, name) = stem.split_once('_')?;
+    let version = version.parse::<i64>().ok()?;
+
+    Some((version, name.to_string(), is_up))
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|ex| Error::MigrationDirFail {
+        dir: path.to_string_lossy().to_string(),
+        cause: ex.to_string(),
+    })
+}
+
+fn checksum(content: &str) -> String {
+    base64_url::encode(&Sha256::digest(content.as_bytes()))
+}
+
+// endregion: --- Migration Files
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use anyhow::{Context, Result};
+
+    #[test]
+    fn test_parse_file_name_ok() -> Result<()> {
+        // -- Exec & Check
+        assert_eq!(
+            parse_file_name("0001_init.up.sql"),
+            Some((1, "init".to_string(), true))
+        );
+        assert_eq!(
+            parse_file_name("0012_bounty_columns.down.sql"),
+            Some((12, "bounty_columns".to_string(), false))
+        );
+        assert_eq!(parse_file_name("0001_init.sql"), None);
+        assert_eq!(parse_file_name("init.up.sql"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_migrations_ok() -> Result<()> {
+        // -- Exec
+        let migrations = load_migrations("sql/migrations")?;
+
+        // -- Check
+        let init = migrations.get(&1).ok_or(anyhow::anyhow!("no 0001"))?;
+        assert_eq!(init.name, "init");
+        assert!(init.down_sql.is_some(), "0001_init should have a down");
+        assert_eq!(init.checksum, checksum(&init.up_sql));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_migrate_rollback_migrate_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let migrations = load_migrations("sql/migrations")?;
+        let last = *migrations.keys().next_back().context("no migration")?;
+
+        // -- Exec
+        let rolled_back = mm.rollback().await?;
+        let pending = mm.migration_status().await?;
+        let reapplied = mm.migrate().await?;
+        let reapplied_again = mm.migrate().await?;
+
+        // -- Check
+        assert_eq!(rolled_back, Some(last));
+        let last_status = pending.last().context("no status")?;
+        assert_eq!(last_status.version, last);
+        assert!(matches!(last_status.state, MigrationState::Pending));
+        assert_eq!(reapplied, vec![last]);
+        assert!(reapplied_again.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_migrate_err_out_of_order() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let dir = std::env::temp_dir().join(format!("porta_migrations_{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        for (version, migration) in load_migrations("sql/migrations")? {
+            let file = format!("{version:04}_{}.up.sql", migration.name);
+            fs::write(dir.join(file), migration.up_sql)?;
+        }
+        fs::write(dir.join("0000_late_branch.up.sql"), "SELECT 1;")?;
+
+        // -- Exec
+        let res = migrate(mm.db()?, &dir.to_string_lossy()).await;
+        fs::remove_dir_all(&dir)?;
+
+        // -- Check
+        assert!(
+            matches!(res, Err(Error::MigrationOutOfOrder { version: 0, .. })),
+            "should be MigrationOutOfOrder, was {res:?}"
+        );
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
@@ -1,10 +1,14 @@
 // region:    --- Modules
 
+pub mod dbx;
 mod error;
+pub mod memory;
+pub mod migration;
 
 pub use self::error::{Error, Result};
 
-use crate::config;
+use crate::model::store::dbx::Dbx;
+use crate::model::store::memory::MemStore;
 use sqlx::postgres::PgPoolOptions;
 use sqlx::{Pool, Postgres};
 
@@ -12,11 +16,18 @@ use sqlx::{Pool, Postgres};
 
 pub type Db = Pool<Postgres>;
 
-pub async fn new_db_pool() -> Result<Db> {
+/// The storage behind the `ModelManager`.
+#[derive(Clone)]
+pub enum Storage {
+    Db(Dbx),
+    Memory(MemStore),
+}
+
+pub async fn new_db_pool(db_url: &str) -> Result<Db> {
     let max_connections = if cfg!(test) { 1 } else { 5 };
     PgPoolOptions::new()
         .max_connections(max_connections)
-        .connect(&config().DB_URL)
+        .connect(db_url)
         .await
         .map_err(|ex| Error::FailToCreatePool(ex.to_string()))
 }
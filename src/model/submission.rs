@@ -0,0 +1,750 @@
+//! Work submissions of a bounty assignee, and their review.
+//!
+//! Design:
+//!
+//! - The assignee (the `Ctx` user with the accepted `model::application`)
+//!   submits notes, and a link and notes per deliverable of the bounty
+//!   checklist (see `model::deliverable`), all of them being required.
+//!   The bounty goes `Assigned` -> `Submitted`.
+//! - A reviewer (the project lead, or a reviewer of the project policy, but
+//!   never the submitter) decides on the pending submission:
+//!   - approve: bounty `Approved`, once the approval votes satisfy the
+//!     project policy (see `model::approval`),
+//!   - request changes (with a comment): bounty back to `Assigned`,
+//!     for the assignee to resubmit,
+//!   - reject: bounty `Cancelled`.
+//! - A resubmission is a new submission, so the previous ones (and their
+//!   decisions) are the history of the bounty.
+//!
+
+use crate::ctx::Ctx;
+use crate::model::application::{ApplicationBmc, ApplicationFilter, ApplicationStatus};
+use crate::model::approval::{ApprovalBmc, ApprovalVote};
+use crate::model::base::{self, DbBmc};
+use crate::model::bounty::{BountyBmc, BountyStatus};
+use crate::model::deliverable::DeliverableBmc;
+use crate::model::event::{EventKind, ModelEvent};
+use crate::model::store::memory::Row;
+use crate::model::{Error, ModelManager, Result};
+use serde::{Deserialize, Serialize};
+use sqlb::Fields;
+use sqlx::FromRow;
+use validator::Validate;
+
+// region:    --- Submission Types
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct Submission {
+    pub id: i64,
+    pub submission_bounty_id: i64,
+    pub submission_submitter_id: i64,
+    pub submission_notes: String,
+    pub submission_status: SubmissionStatus,
+    pub submission_reviewer_id: Option<i64>,
+    pub submission_review_comment: Option<String>,
+}
+
+/// The `submission_status` db enum.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, sqlx::Type, strum_macros::AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "submission_status", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SubmissionStatus {
+    Pending,
+    Approved,
+    ChangesRequested,
+    Rejected,
+}
+sqlb::bindable!(SubmissionStatus);
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct SubmissionItem {
+    pub id: i64,
+    pub item_submission_id: i64,
+    pub item_deliverable_id: i64,
+    pub item_link: String,
+    pub item_notes: String,
+}
+
+/// The submission with its items (one per deliverable) and approval votes.
+#[derive(Debug, Serialize)]
+pub struct SubmissionWithItems {
+    #[serde(flatten)]
+    pub submission: Submission,
+    pub items: Vec<SubmissionItem>,
+    pub votes: Vec<ApprovalVote>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct SubmissionForCreate {
+    pub submission_bounty_id: i64,
+    #[validate(length(max = 2048))]
+    pub submission_notes: String,
+    #[validate]
+    pub items: Vec<SubmissionItemForCreate>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct SubmissionItemForCreate {
+    pub deliverable_id: i64,
+    #[validate(length(min = 1, max = 1024))]
+    pub link: String,
+    #[validate(length(max = 2048))]
+    pub notes: String,
+}
+
+#[derive(Serialize, Fields)]
+pub struct SubmissionForInsert {
+    pub submission_bounty_id: i64,
+    pub submission_submitter_id: i64,
+    pub submission_notes: String,
+}
+
+#[derive(Serialize, Fields)]
+pub struct SubmissionItemForInsert {
+    pub item_submission_id: i64,
+    pub item_deliverable_id: i64,
+    pub item_link: String,
+    pub item_notes: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Approve,
+    RequestChanges,
+    Reject,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct SubmissionForReview {
+    pub decision: ReviewDecision,
+    /// Required to request changes.
+    #[validate(length(min = 1, max = 2048))]
+    pub comment: Option<String>,
+}
+
+#[derive(Serialize, Fields)]
+pub struct SubmissionForDecide {
+    pub submission_status: SubmissionStatus,
+    pub submission_reviewer_id: i64,
+    pub submission_review_comment: Option<String>,
+}
+
+#[derive(Default, Deserialize, Serialize, Fields)]
+pub struct SubmissionFilter {
+    pub submission_bounty_id: Option<i64>,
+    pub submission_submitter_id: Option<i64>,
+    pub submission_status: Option<SubmissionStatus>,
+}
+
+#[derive(Default, Serialize, Fields)]
+pub struct SubmissionItemFilter {
+    pub item_submission_id: Option<i64>,
+}
+
+// endregion: --- Submission Types
+
+pub struct SubmissionBmc;
+
+impl DbBmc for SubmissionBmc {
+    const TABLE: &'static str = "submission";
+
+    fn column_defaults() -> Row {
+        Row::from_iter([("submission_status".to_string(), "pending".into())])
+    }
+}
+
+pub struct SubmissionItemBmc;
+
+impl DbBmc for SubmissionItemBmc {
+    const TABLE: &'static str = "submission_item";
+}
+
+impl SubmissionBmc {
+    /// Submit the work on the assigned bounty, as its assignee (the `Ctx` user).
+    pub async fn create(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        submission_c: SubmissionForCreate,
+    ) -> Result<i64> {
+        let SubmissionForCreate {
+            submission_bounty_id: bounty_id,
+            submission_notes,
+            items,
+        } = submission_c;
+
+        mm.transaction(|mm| async move {
+            Self::check_assignee(ctx, &mm, bounty_id).await?;
+            Self::check_checklist(ctx, &mm, bounty_id, &items).await?;
+
+            let submission_i = SubmissionForInsert {
+                submission_bounty_id: bounty_id,
+                submission_submitter_id: ctx.user_id(),
+                submission_notes,
+            };
+            let id = base::create::<Self, _>(ctx, &mm, submission_i).await?;
+
+            for item in items {
+                let item_i = SubmissionItemForInsert {
+                    item_submission_id: id,
+                    item_deliverable_id: item.deliverable_id,
+                    item_link: item.link,
+                    item_notes: item.notes,
+                };
+                base::create::<SubmissionItemBmc, _>(ctx, &mm, item_i).await?;
+            }
+
+            BountyBmc::transition(ctx, &mm, bounty_id, BountyStatus::Submitted).await?;
+
+            Ok(id)
+        })
+        .await
+    }
+
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Submission> {
+        base::get::<Self, _>(ctx, mm, id).await
+    }
+
+    pub async fn get_with_items(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        id: i64,
+    ) -> Result<SubmissionWithItems> {
+        let submission = Self::get(ctx, mm, id).await?;
+        let filter = SubmissionItemFilter {
+            item_submission_id: Some(id),
+        };
+        let items = base::list::<SubmissionItemBmc, _, _>(ctx, mm, Some(filter), None).await?;
+        let votes = ApprovalBmc::list_votes(ctx, mm, id).await?;
+
+        Ok(SubmissionWithItems {
+            submission,
+            items,
+            votes,
+        })
+    }
+
+    /// The submissions of the bounty, oldest first (the review history).
+    pub async fn list_for_bounty(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        bounty_id: i64,
+    ) -> Result<Vec<Submission>> {
+        let filter = SubmissionFilter {
+            submission_bounty_id: Some(bounty_id),
+            ..Default::default()
+        };
+        base::list::<Self, _, _>(ctx, mm, Some(filter), None).await
+    }
+
+    /// Decide on the pending submission, as the reviewer (the `Ctx` user),
+    /// moving the bounty accordingly.
+    /// (an approval only records the reviewer vote until the policy is satisfied)
+    pub async fn review(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        id: i64,
+        submission_r: SubmissionForReview,
+    ) -> Result<()> {
+        let SubmissionForReview { decision, comment } = submission_r;
+        let (to, bounty_to) = match decision {
+            ReviewDecision::Approve => (SubmissionStatus::Approved, BountyStatus::Approved),
+            ReviewDecision::RequestChanges => {
+                (SubmissionStatus::ChangesRequested, BountyStatus::Assigned)
+            }
+            ReviewDecision::Reject => (SubmissionStatus::Rejected, BountyStatus::Cancelled),
+        };
+        if decision == ReviewDecision::RequestChanges && comment.is_none() {
+            return Err(Error::ReviewCommentRequired { id });
+        }
+
+        mm.transaction(|mm| async move {
+            let submission = Self::get(ctx, &mm, id).await?;
+            let from = submission.submission_status;
+            if from != SubmissionStatus::Pending {
+                return Err(Error::InvalidTransition {
+                    entity: Self::TABLE,
+                    id,
+                    from: from.as_ref().to_string(),
+                    to: to.as_ref().to_string(),
+                });
+            }
+
+            if submission.submission_submitter_id == ctx.user_id() {
+                return Err(Error::SubmissionSelfReview {
+                    id,
+                    user_id: ctx.user_id(),
+                });
+            }
+
+            let bounty_id = submission.submission_bounty_id;
+            let policy = ApprovalBmc::policy_for_bounty(ctx, &mm, bounty_id).await?;
+            if !policy.can_review(ctx.user_id()) {
+                return Err(Error::NotPolicyReviewer {
+                    id,
+                    user_id: ctx.user_id(),
+                });
+            }
+
+            if decision == ReviewDecision::Approve {
+                ApprovalBmc::vote(ctx, &mm, id).await?;
+                let approver_ids: Vec<i64> = ApprovalBmc::list_votes(ctx, &mm, id)
+                    .await?
+                    .into_iter()
+                    .map(|v| v.vote_reviewer_id)
+                    .collect();
+                if !policy.is_satisfied(&approver_ids) {
+                    return Ok(());
+                }
+            }
+
+            let submission_d = SubmissionForDecide {
+                submission_status: to,
+                submission_reviewer_id: ctx.user_id(),
+                submission_review_comment: comment,
+            };
+            base::update::<Self, _>(ctx, &mm, id, None, submission_d).await?;
+            mm.publish(ModelEvent::new(
+                Self::TABLE,
+                id,
+                EventKind::Transitioned {
+                    from: from.as_ref().to_string(),
+                    to: to.as_ref().to_string(),
+                },
+            ));
+
+            BountyBmc::transition(ctx, &mm, bounty_id, bounty_to).await
+        })
+        .await
+    }
+
+    /// The `Ctx` user must have the accepted application of the bounty.
+    async fn check_assignee(ctx: &Ctx, mm: &ModelManager, bounty_id: i64) -> Result<()> {
+        let filter = ApplicationFilter {
+            application_bounty_id: Some(bounty_id),
+            application_applicant_id: Some(ctx.user_id()),
+            application_status: Some(ApplicationStatus::Accepted),
+        };
+        let accepted = ApplicationBmc::list(ctx, mm, Some(filter), None).await?;
+
+        if accepted.is_empty() {
+            Err(Error::NotBountyAssignee {
+                bounty_id,
+                user_id: ctx.user_id(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The items must cover exactly the deliverables of the bounty.
+    async fn check_checklist(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        bounty_id: i64,
+        items: &[SubmissionItemForCreate],
+    ) -> Result<()> {
+        let deliverable_ids: Vec<i64> = DeliverableBmc::list_for_bounty(ctx, mm, bounty_id)
+            .await?
+            .into_iter()
+            .map(|d| d.id)
+            .collect();
+        let item_ids: Vec<i64> = items.iter().map(|i| i.deliverable_id).collect();
+
+        let missing: Vec<i64> = deliverable_ids
+            .iter()
+            .filter(|id| !item_ids.contains(id))
+            .copied()
+            .collect();
+        let unknown: Vec<i64> = item_ids
+            .iter()
+            .filter(|id| !deliverable_ids.contains(id))
+            .copied()
+            .collect();
+
+        if missing.is_empty() && unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::SubmissionChecklistMismatch { missing, unknown })
+        }
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::Seed;
+    use crate::model::application::ApplicationForCreate;
+    use crate::model::approval::ApprovalPolicy;
+    use crate::model::deliverable::DeliverableForCreate;
+    use crate::model::ledger::{LedgerBmc, ProjectFunding};
+    use anyhow::{Context, Result};
+
+    const SEED_TOML: &str = r#"
+[[users]]
+username = "alice"
+pwd_clear = "alice pwd 01"
+
+[[users]]
+username = "reviewer"
+pwd_clear = "reviewer pwd 01"
+
+[[users]]
+username = "reviewer2"
+pwd_clear = "reviewer2 pwd 01"
+
+[[users]]
+username = "carol"
+pwd_clear = "carol pwd 01"
+
+[[wallets]]
+username = "reviewer"
+key = "lead"
+
+# (led by reviewer, the address of its `lead` dev wallet)
+[[projects]]
+key = "p1"
+project_id = "submission-project-01"
+project_name = "Submission Project 01"
+project_lead_address = "cosmos1gf3c5mgfe6zd0qnmuwy7c5zf6yj5t5ajyn7sth"
+project_budget = "1000"
+project_denom = "uatom"
+project_type = "dev"
+project_description = "A project with reviews"
+
+[[bounties]]
+key = "b1"
+project = "p1"
+bounty_title = "Submission bounty 01"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "Code and docs"
+bounty_description = "A bounty to submit to"
+"#;
+
+    struct Fixture {
+        project_id: i64,
+        bounty_id: i64,
+        deliverable_ids: Vec<i64>,
+        alice_ctx: Ctx,
+        reviewer_ctx: Ctx,
+        reviewer2_ctx: Ctx,
+        carol_ctx: Ctx,
+    }
+
+    /// Bounty with two deliverables, assigned to alice.
+    async fn fixture(mm: &ModelManager) -> Result<Fixture> {
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, mm).await?;
+        let bounty_id = *seeded.bounties.get("b1").context("b1")?;
+        let alice_ctx = Ctx::new(*seeded.users.get("alice").context("alice")?)?;
+        let reviewer_ctx = Ctx::new(*seeded.users.get("reviewer").context("reviewer")?)?;
+        let reviewer2_ctx = Ctx::new(*seeded.users.get("reviewer2").context("reviewer2")?)?;
+        let carol_ctx = Ctx::new(*seeded.users.get("carol").context("carol")?)?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let funding = ProjectFunding {
+            amount: 1000,
+            memo: "funding".to_string(),
+        };
+        LedgerBmc::fund_project(&ctx, mm, project_id, funding).await?;
+
+        let mut deliverable_ids = Vec::new();
+        for title in ["Code", "Docs"] {
+            let deliverable_c = DeliverableForCreate {
+                deliverable_bounty_id: bounty_id,
+                deliverable_title: title.to_string(),
+                deliverable_description: String::new(),
+            };
+            deliverable_ids.push(DeliverableBmc::create(&ctx, mm, deliverable_c).await?);
+        }
+
+        let application_c = ApplicationForCreate {
+            application_bounty_id: bounty_id,
+            application_message: "On it".to_string(),
+            application_estimated_days: 2,
+            application_address: "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn".to_string(),
+            application_deadline: None,
+        };
+        let application_id = ApplicationBmc::create(&alice_ctx, mm, application_c).await?;
+        ApplicationBmc::accept(&ctx, mm, application_id).await?;
+
+        Ok(Fixture {
+            project_id,
+            bounty_id,
+            deliverable_ids,
+            alice_ctx,
+            reviewer_ctx,
+            reviewer2_ctx,
+            carol_ctx,
+        })
+    }
+
+    fn submission_c(bounty_id: i64, deliverable_ids: &[i64]) -> SubmissionForCreate {
+        SubmissionForCreate {
+            submission_bounty_id: bounty_id,
+            submission_notes: "Done".to_string(),
+            items: deliverable_ids
+                .iter()
+                .map(|id| SubmissionItemForCreate {
+                    deliverable_id: *id,
+                    link: format!("https://example.com/pr/{id}"),
+                    notes: String::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit_review_history_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let fx = fixture(&mm).await?;
+        let submission_c = || submission_c(fx.bounty_id, &fx.deliverable_ids);
+
+        // -- Exec
+        let first_id = SubmissionBmc::create(&fx.alice_ctx, &mm, submission_c()).await?;
+        let review = SubmissionForReview {
+            decision: ReviewDecision::RequestChanges,
+            comment: Some("Add the tests".to_string()),
+        };
+        SubmissionBmc::review(&fx.reviewer_ctx, &mm, first_id, review).await?;
+        let bounty_after_changes = BountyBmc::get(&fx.alice_ctx, &mm, fx.bounty_id).await?;
+
+        let second_id = SubmissionBmc::create(&fx.alice_ctx, &mm, submission_c()).await?;
+        let review = SubmissionForReview {
+            decision: ReviewDecision::Approve,
+            comment: None,
+        };
+        SubmissionBmc::review(&fx.reviewer_ctx, &mm, second_id, review).await?;
+
+        // -- Check
+        assert_eq!(bounty_after_changes.bounty_status, BountyStatus::Assigned);
+        let bounty = BountyBmc::get(&fx.alice_ctx, &mm, fx.bounty_id).await?;
+        assert_eq!(bounty.bounty_status, BountyStatus::Approved);
+
+        let history = SubmissionBmc::list_for_bounty(&fx.alice_ctx, &mm, fx.bounty_id).await?;
+        let statuses: Vec<_> = history.iter().map(|s| s.submission_status).collect();
+        assert_eq!(
+            statuses,
+            [
+                SubmissionStatus::ChangesRequested,
+                SubmissionStatus::Approved
+            ]
+        );
+        assert_eq!(
+            history[0].submission_review_comment.as_deref(),
+            Some("Add the tests")
+        );
+        assert_eq!(
+            history[0].submission_reviewer_id,
+            Some(fx.reviewer_ctx.user_id())
+        );
+
+        let first = SubmissionBmc::get_with_items(&fx.alice_ctx, &mm, first_id).await?;
+        assert_eq!(first.items.len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_review_approval_policy_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let fx = fixture(&mm).await?;
+        let policy = ApprovalPolicy {
+            required: 2,
+            reviewers: vec![fx.reviewer_ctx.user_id(), fx.reviewer2_ctx.user_id()],
+            required_reviewers: Vec::new(),
+        };
+        ApprovalBmc::set_policy(&Ctx::root_ctx(), &mm, fx.project_id, Some(policy)).await?;
+        let id = SubmissionBmc::create(
+            &fx.alice_ctx,
+            &mm,
+            submission_c(fx.bounty_id, &fx.deliverable_ids),
+        )
+        .await?;
+        let approve = || SubmissionForReview {
+            decision: ReviewDecision::Approve,
+            comment: None,
+        };
+
+        // -- Exec & Check - not a reviewer
+        let res = SubmissionBmc::review(&fx.carol_ctx, &mm, id, approve()).await;
+        assert!(
+            matches!(res, Err(Error::NotPolicyReviewer { .. })),
+            "NotPolicyReviewer not matching"
+        );
+
+        // -- Exec & Check - first approval, still pending
+        SubmissionBmc::review(&fx.reviewer_ctx, &mm, id, approve()).await?;
+        let submission = SubmissionBmc::get_with_items(&fx.alice_ctx, &mm, id).await?;
+        assert_eq!(
+            submission.submission.submission_status,
+            SubmissionStatus::Pending
+        );
+        assert_eq!(submission.votes.len(), 1);
+        let res = SubmissionBmc::review(&fx.reviewer_ctx, &mm, id, approve()).await;
+        assert!(
+            matches!(res, Err(Error::UniqueViolation { .. })),
+            "a reviewer votes once"
+        );
+
+        // -- Exec & Check - second approval, policy satisfied
+        SubmissionBmc::review(&fx.reviewer2_ctx, &mm, id, approve()).await?;
+        let submission = SubmissionBmc::get(&fx.alice_ctx, &mm, id).await?;
+        assert_eq!(submission.submission_status, SubmissionStatus::Approved);
+        let bounty = BountyBmc::get(&fx.alice_ctx, &mm, fx.bounty_id).await?;
+        assert_eq!(bounty.bounty_status, BountyStatus::Approved);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_review_err_not_reviewer() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let fx = fixture(&mm).await?;
+        let id = SubmissionBmc::create(
+            &fx.alice_ctx,
+            &mm,
+            submission_c(fx.bounty_id, &fx.deliverable_ids),
+        )
+        .await?;
+        let approve = || SubmissionForReview {
+            decision: ReviewDecision::Approve,
+            comment: None,
+        };
+
+        // -- Exec & Check - self approval
+        let res = SubmissionBmc::review(&fx.alice_ctx, &mm, id, approve()).await;
+        assert!(
+            matches!(res, Err(Error::SubmissionSelfReview { user_id, .. }) if user_id == fx.alice_ctx.user_id()),
+            "SubmissionSelfReview not matching"
+        );
+
+        // -- Exec & Check - neither the lead nor a policy reviewer
+        let res = SubmissionBmc::review(&fx.reviewer2_ctx, &mm, id, approve()).await;
+        assert!(
+            matches!(res, Err(Error::NotPolicyReviewer { .. })),
+            "NotPolicyReviewer not matching"
+        );
+
+        // -- Check - no vote recorded
+        let submission = SubmissionBmc::get_with_items(&fx.alice_ctx, &mm, id).await?;
+        assert_eq!(
+            submission.submission.submission_status,
+            SubmissionStatus::Pending
+        );
+        assert!(submission.votes.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_submit_review_err() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let fx = fixture(&mm).await?;
+
+        // -- Exec & Check - not the assignee
+        let res = SubmissionBmc::create(
+            &fx.reviewer_ctx,
+            &mm,
+            submission_c(fx.bounty_id, &fx.deliverable_ids),
+        )
+        .await;
+        assert!(
+            matches!(res, Err(Error::NotBountyAssignee { .. })),
+            "NotBountyAssignee not matching"
+        );
+
+        // -- Exec & Check - incomplete checklist
+        let res = SubmissionBmc::create(
+            &fx.alice_ctx,
+            &mm,
+            submission_c(fx.bounty_id, &fx.deliverable_ids[..1]),
+        )
+        .await;
+        assert!(
+            matches!(
+                &res,
+                Err(Error::SubmissionChecklistMismatch { missing, unknown })
+                    if missing == &fx.deliverable_ids[1..] && unknown.is_empty()
+            ),
+            "SubmissionChecklistMismatch not matching"
+        );
+
+        // -- Exec & Check - changes requested without comment
+        let id = SubmissionBmc::create(
+            &fx.alice_ctx,
+            &mm,
+            submission_c(fx.bounty_id, &fx.deliverable_ids),
+        )
+        .await?;
+        let review = SubmissionForReview {
+            decision: ReviewDecision::RequestChanges,
+            comment: None,
+        };
+        let res = SubmissionBmc::review(&fx.reviewer_ctx, &mm, id, review).await;
+        assert!(
+            matches!(res, Err(Error::ReviewCommentRequired { .. })),
+            "ReviewCommentRequired not matching"
+        );
+
+        // -- Exec & Check - already decided
+        let review = || SubmissionForReview {
+            decision: ReviewDecision::Reject,
+            comment: None,
+        };
+        SubmissionBmc::review(&fx.reviewer_ctx, &mm, id, review()).await?;
+        let res = SubmissionBmc::review(&fx.reviewer_ctx, &mm, id, review()).await;
+        assert!(
+            matches!(res, Err(Error::InvalidTransition { .. })),
+            "InvalidTransition not matching"
+        );
+        let bounty = BountyBmc::get(&fx.alice_ctx, &mm, fx.bounty_id).await?;
+        assert_eq!(bounty.bounty_status, BountyStatus::Cancelled);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_deliverable_err_frozen() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let fx = fixture(&mm).await?;
+        let deliverable_c = || DeliverableForCreate {
+            deliverable_bounty_id: fx.bounty_id,
+            deliverable_title: "Tests".to_string(),
+            deliverable_description: String::new(),
+        };
+
+        // -- Exec
+        let not_lead_res = DeliverableBmc::create(&fx.carol_ctx, &mm, deliverable_c()).await;
+        let create_res = DeliverableBmc::create(&fx.reviewer_ctx, &mm, deliverable_c()).await;
+        let delete_res = DeliverableBmc::delete(&fx.reviewer_ctx, &mm, fx.deliverable_ids[0]).await;
+
+        // -- Check
+        assert!(
+            matches!(not_lead_res, Err(Error::NotProjectLead { .. })),
+            "NotProjectLead not matching"
+        );
+        for res in [create_res.map(|_| ()), delete_res] {
+            assert!(
+                matches!(&res, Err(Error::BountyChecklistFrozen { status, .. }) if status == "assigned"),
+                "BountyChecklistFrozen not matching"
+            );
+        }
+        let deliverables =
+            DeliverableBmc::list_for_bounty(&fx.carol_ctx, &mm, fx.bounty_id).await?;
+        assert_eq!(deliverables.len(), fx.deliverable_ids.len());
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
@@ -0,0 +1,500 @@
+//! Tags (e.g., "rust", "frontend") on projects and bounties.
+//!
+//! Tag names are normalized (trimmed, lowercase), and created on their first use.
+//! The links are in the `project_tag` and `bounty_tag` tables
+//! (db only, not supported by the in-memory storage).
+//!
+//! Only the project lead tags or untags the project and its bounties (see
+//! `ProjectBmc::check_lead`).
+//!
+
+use crate::ctx::Ctx;
+use crate::model::base::{self, DbBmc};
+use crate::model::bounty::BountyBmc;
+use crate::model::event::{EventKind, ModelEvent};
+use crate::model::project::ProjectBmc;
+use crate::model::{Error, ModelManager, Result};
+use serde::{Deserialize, Serialize};
+use sqlb::Fields;
+use sqlx::FromRow;
+use validator::{Validate, ValidationError};
+
+// region:    --- Tag Types
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Fields, Validate)]
+pub struct TagForCreate {
+    #[validate(length(min = 1, max = 32), custom = "validate_tag_name")]
+    pub name: String,
+}
+
+impl TagForCreate {
+    /// With the normalized `name`.
+    pub fn new(name: &str) -> Self {
+        TagForCreate {
+            name: normalize(name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct TagWithCount {
+    pub id: i64,
+    pub name: String,
+    pub project_count: i64,
+    pub bounty_count: i64,
+}
+
+/// The taggable entities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagTarget {
+    Project,
+    Bounty,
+}
+
+impl TagTarget {
+    fn table(&self) -> &'static str {
+        match self {
+            TagTarget::Project => ProjectBmc::TABLE,
+            TagTarget::Bounty => BountyBmc::TABLE,
+        }
+    }
+
+    /// The `(link table, entity id column)` of the target (its `DbBmc::TAG_LINK`).
+    fn link(&self) -> Result<(&'static str, &'static str)> {
+        let tag_link = match self {
+            TagTarget::Project => ProjectBmc::TAG_LINK,
+            TagTarget::Bounty => BountyBmc::TAG_LINK,
+        };
+
+        tag_link.ok_or(Error::EntityNotTaggable {
+            entity: self.table(),
+        })
+    }
+
+    /// Fails with `EntityNotFound` if there is no target entity of `id`, or
+    /// `NotProjectLead` if the `Ctx` user is not the lead of its project.
+    async fn check_lead(&self, ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        match self {
+            TagTarget::Project => ProjectBmc::check_lead(ctx, mm, id).await.map(|_| ()),
+            TagTarget::Bounty => {
+                let bounty = BountyBmc::get(ctx, mm, id).await?;
+                BountyBmc::check_lead(ctx, mm, &bounty).await
+            }
+        }
+    }
+}
+
+/// Trimmed and lowercase tag name.
+pub fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Lowercase letters, digits, and inner `-`, `.`, `+`, `#` (e.g., "c++", "web3.js").
+fn validate_tag_name(name: &str) -> core::result::Result<(), ValidationError> {
+    let valid_char = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || "-.+#".contains(c);
+    let starts_alnum = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+
+    if starts_alnum && name.chars().all(valid_char) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("tag_name"))
+    }
+}
+
+// endregion: --- Tag Types
+
+pub struct TagBmc;
+
+impl DbBmc for TagBmc {
+    const TABLE: &'static str = "tag";
+    const UNIQUE_FIELDS: &'static [&'static str] = &["name"];
+}
+
+impl TagBmc {
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Tag> {
+        base::get::<Self, _>(ctx, mm, id).await
+    }
+
+    pub async fn first_by_name(ctx: &Ctx, mm: &ModelManager, name: &str) -> Result<Option<Tag>> {
+        base::first_by::<Self, _, _>(ctx, mm, "name", normalize(name)).await
+    }
+
+    /// The tag of `tag_c.name`, created if new.
+    /// (a single insert, so concurrent first uses of a name do not conflict)
+    pub async fn get_or_create(ctx: &Ctx, mm: &ModelManager, tag_c: TagForCreate) -> Result<Tag> {
+        mm.in_txn(|mm| async move {
+            let created: Option<Tag> = mm
+                .dbx()?
+                .query_fetch_optional(
+                    sqlx::query_as(
+                        "INSERT INTO tag (name) VALUES ($1) \
+                     ON CONFLICT (name) DO NOTHING RETURNING id, name",
+                    )
+                    .bind(&tag_c.name),
+                )
+                .await?;
+
+            match created {
+                Some(tag) => {
+                    mm.publish(ModelEvent::new(Self::TABLE, tag.id, EventKind::Created));
+                    Ok(tag)
+                }
+                None => Self::first_by_name(ctx, &mm, &tag_c.name).await?.ok_or(
+                    Error::EntityNotFoundBy {
+                        entity: Self::TABLE,
+                        field: "name",
+                        value: tag_c.name,
+                    },
+                ),
+            }
+        })
+        .await
+    }
+
+    /// All the tags, with their usage counts, most used first.
+    pub async fn list_with_counts(_ctx: &Ctx, mm: &ModelManager) -> Result<Vec<TagWithCount>> {
+        let tags = mm
+            .dbx()?
+            .query_fetch_all(sqlx::query_as(
+                r#"
+SELECT * FROM (
+  SELECT t.id, t.name,
+         (SELECT count(*) FROM project_tag pt WHERE pt.tag_id = t.id) AS project_count,
+         (SELECT count(*) FROM bounty_tag bt WHERE bt.tag_id = t.id) AS bounty_count
+    FROM tag t
+) counts
+ ORDER BY project_count + bounty_count DESC, name"#,
+            ))
+            .await?;
+
+        Ok(tags)
+    }
+
+    /// The tags of the target entity, by name.
+    pub async fn list_for(
+        _ctx: &Ctx,
+        mm: &ModelManager,
+        target: TagTarget,
+        target_id: i64,
+    ) -> Result<Vec<Tag>> {
+        let (link_table, link_column) = target.link()?;
+        let sql = format!(
+            "SELECT t.id, t.name FROM tag t JOIN {link_table} l ON l.tag_id = t.id \
+             WHERE l.{link_column} = $1 ORDER BY t.name"
+        );
+        let tags = mm
+            .dbx()?
+            .query_fetch_all(sqlx::query_as(&sql).bind(target_id))
+            .await?;
+
+        Ok(tags)
+    }
+
+    /// Tag the target entity (no-op if already tagged), as its project lead.
+    /// Returns the tags of the entity, or `EntityNotFound` if there is none.
+    pub async fn add_to(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        target: TagTarget,
+        target_id: i64,
+        tag_c: TagForCreate,
+    ) -> Result<Vec<Tag>> {
+        let (link_table, link_column) = target.link()?;
+
+        mm.transaction(|mm| async move {
+            target.check_lead(ctx, &mm, target_id).await?;
+            let tag = Self::get_or_create(ctx, &mm, tag_c).await?;
+
+            let sql = format!(
+                "INSERT INTO {link_table} ({link_column}, tag_id) VALUES ($1, $2) \
+                 ON CONFLICT DO NOTHING"
+            );
+            mm.dbx()?
+                .query_exec(sqlx::query(&sql).bind(target_id).bind(tag.id))
+                .await?;
+
+            Self::list_for(ctx, &mm, target, target_id).await
+        })
+        .await
+    }
+
+    /// Untag the target entity, as its project lead.
+    /// Returns the tags of the entity.
+    pub async fn remove_from(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        target: TagTarget,
+        target_id: i64,
+        name: &str,
+    ) -> Result<Vec<Tag>> {
+        let name = normalize(name);
+        let (link_table, link_column) = target.link()?;
+        target.check_lead(ctx, mm, target_id).await?;
+
+        let sql = format!(
+            "DELETE FROM {link_table} l USING tag t \
+             WHERE l.tag_id = t.id AND l.{link_column} = $1 AND t.name = $2"
+        );
+        let count = mm
+            .dbx()?
+            .query_exec(sqlx::query(&sql).bind(target_id).bind(&name))
+            .await?;
+
+        if count == 0 {
+            return Err(Error::EntityNotFoundBy {
+                entity: target.table(),
+                field: "tag",
+                value: name,
+            });
+        }
+
+        Self::list_for(ctx, mm, target, target_id).await
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::Seed;
+    use crate::model::bounty::BountyFilter;
+    use crate::model::project::ProjectFilter;
+    use anyhow::{Context, Result};
+
+    const SEED_TOML: &str = r#"
+[[users]]
+username = "alice"
+pwd_clear = "alice pwd 01"
+
+[[projects]]
+key = "p1"
+project_id = "tag-project-01"
+project_name = "Tag Project 01"
+project_lead_address = "cosmos1udzkhs056fc0f2texd6cv30acg0rjept43xsph"
+project_budget = "1000"
+project_denom = "uatom"
+project_type = "dev"
+project_description = "Tagged project"
+
+[[bounties]]
+key = "b1"
+project = "p1"
+bounty_title = "Tag bounty 01"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "A PR"
+bounty_description = "Rust bounty"
+
+[[bounties]]
+key = "b2"
+project = "p1"
+bounty_title = "Tag bounty 02"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "A PR"
+bounty_description = "Frontend bounty"
+"#;
+
+    #[test]
+    fn test_validate_tag_name() -> Result<()> {
+        assert!(TagForCreate::new(" Rust ").validate().is_ok());
+        assert!(TagForCreate::new("c++").validate().is_ok());
+        assert!(TagForCreate::new("web3.js").validate().is_ok());
+        assert!(TagForCreate::new("").validate().is_err());
+        assert!(TagForCreate::new("-rust").validate().is_err());
+        assert!(TagForCreate::new("two words").validate().is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_add_remove_list_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let p1 = *seeded.projects.get("p1").context("p1")?;
+        let b1 = *seeded.bounties.get("b1").context("b1")?;
+        let b2 = *seeded.bounties.get("b2").context("b2")?;
+
+        // -- Exec
+        for (target, id, name) in [
+            (TagTarget::Project, p1, "Rust"),
+            (TagTarget::Bounty, b1, "rust"),
+            (TagTarget::Bounty, b1, "backend"),
+            (TagTarget::Bounty, b2, "frontend"),
+        ] {
+            TagBmc::add_to(&ctx, &mm, target, id, TagForCreate::new(name)).await?;
+        }
+        // (already tagged, no-op)
+        TagBmc::add_to(&ctx, &mm, TagTarget::Bounty, b1, TagForCreate::new("RUST")).await?;
+        let b2_tags = TagBmc::remove_from(&ctx, &mm, TagTarget::Bounty, b2, "frontend").await?;
+
+        // -- Check
+        let b1_names: Vec<String> = TagBmc::list_for(&ctx, &mm, TagTarget::Bounty, b1)
+            .await?
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(b1_names, ["backend", "rust"]);
+        assert!(b2_tags.is_empty());
+
+        let counts = TagBmc::list_with_counts(&ctx, &mm).await?;
+        let rust = counts.first().context("should have tags")?;
+        assert_eq!(
+            (rust.name.as_str(), rust.project_count, rust.bounty_count),
+            ("rust", 1, 1)
+        );
+        let frontend = counts
+            .iter()
+            .find(|t| t.name == "frontend")
+            .context("frontend")?;
+        assert_eq!(frontend.bounty_count, 0);
+
+        let res = TagBmc::remove_from(&ctx, &mm, TagTarget::Bounty, b2, "frontend").await;
+        assert!(
+            matches!(res, Err(Error::EntityNotFoundBy { field: "tag", .. })),
+            "EntityNotFoundBy not matching"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_add_remove_err_not_lead() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let root_ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?
+            .apply(&root_ctx, &mm)
+            .await?;
+        let ctx = Ctx::new(*seeded.users.get("alice").context("alice")?)?;
+        let p1 = *seeded.projects.get("p1").context("p1")?;
+        let b1 = *seeded.bounties.get("b1").context("b1")?;
+        TagBmc::add_to(
+            &root_ctx,
+            &mm,
+            TagTarget::Bounty,
+            b1,
+            TagForCreate::new("rust"),
+        )
+        .await?;
+
+        // -- Exec
+        let project_res =
+            TagBmc::add_to(&ctx, &mm, TagTarget::Project, p1, TagForCreate::new("spam")).await;
+        let bounty_res =
+            TagBmc::add_to(&ctx, &mm, TagTarget::Bounty, b1, TagForCreate::new("spam")).await;
+        let remove_res = TagBmc::remove_from(&ctx, &mm, TagTarget::Bounty, b1, "rust").await;
+
+        // -- Check
+        for res in [project_res, bounty_res, remove_res] {
+            assert!(
+                matches!(res, Err(Error::NotProjectLead { .. })),
+                "NotProjectLead not matching"
+            );
+        }
+        let names: Vec<String> = TagBmc::list_for(&ctx, &mm, TagTarget::Bounty, b1)
+            .await?
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(names, ["rust"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list_filter_by_tag_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let p1 = *seeded.projects.get("p1").context("p1")?;
+        let b1 = *seeded.bounties.get("b1").context("b1")?;
+        TagBmc::add_to(&ctx, &mm, TagTarget::Project, p1, TagForCreate::new("rust")).await?;
+        TagBmc::add_to(&ctx, &mm, TagTarget::Bounty, b1, TagForCreate::new("rust")).await?;
+
+        // -- Exec
+        let filter = BountyFilter {
+            tag: Some("Rust".to_string()),
+            ..Default::default()
+        };
+        let bounties = BountyBmc::list(&ctx, &mm, Some(filter), None).await?;
+        let filter = ProjectFilter {
+            tag: Some("frontend".to_string()),
+            ..Default::default()
+        };
+        let projects = ProjectBmc::list(&ctx, &mm, Some(filter), None).await?;
+
+        // -- Check
+        assert_eq!(bounties.len(), 1);
+        assert_eq!(bounties[0].id, b1);
+        assert!(projects.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_add_to_err_target_not_found() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let fx_id = 999_999;
+
+        // -- Exec
+        let res = TagBmc::add_to(
+            &ctx,
+            &mm,
+            TagTarget::Bounty,
+            fx_id,
+            TagForCreate::new("rust"),
+        )
+        .await;
+
+        // -- Check
+        assert!(
+            matches!(
+                res,
+                Err(Error::EntityNotFound {
+                    entity: "bounty",
+                    id: 999_999
+                })
+            ),
+            "EntityNotFound not matching"
+        );
+        assert!(TagBmc::first_by_name(&ctx, &mm, "rust").await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_or_create_concurrent_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+
+        // -- Exec
+        let (tag_1, tag_2) = tokio::join!(
+            TagBmc::get_or_create(&ctx, &mm, TagForCreate::new("rust")),
+            TagBmc::get_or_create(&ctx, &mm, TagForCreate::new("Rust")),
+        );
+
+        // -- Check
+        assert_eq!(tag_1?.id, tag_2?.id);
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
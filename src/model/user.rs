@@ -1,33 +1,49 @@
 use crate::crypt::{pwd, EncryptContent};
 use crate::ctx::Ctx;
 use crate::model::base::{self, DbBmc};
+use crate::model::store::memory::Row;
 use crate::model::ModelManager;
 use crate::model::{Error, Result};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use sqlb::{Fields, HasFields};
 use sqlx::postgres::PgRow;
 use sqlx::FromRow;
 use uuid::Uuid;
+use validator::Validate;
 
 // region:    --- User Types
-#[derive(Clone, Fields, FromRow, Debug, Serialize)]
+#[derive(Clone, Fields, FromRow, Debug, Deserialize, Serialize)]
 pub struct User {
     pub id: i64,
     pub username: String,
+    pub admin: bool,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Validate)]
 pub struct UserForCreate {
+    #[validate(length(min = 1, max = 128))]
     pub username: String,
+    #[validate(length(min = 8))]
     pub pwd_clear: String,
 }
 
-#[derive(Fields)]
+#[derive(Fields, Serialize)]
 pub struct UserForInsert {
     pub username: String,
 }
 
-#[derive(Clone, FromRow, Fields, Debug)]
+#[derive(Fields, Serialize)]
+pub struct UserForUpdatePwd {
+    pub pwd: String, // encrypted
+}
+
+#[derive(Fields, Serialize)]
+pub struct UserForAdmin {
+    pub admin: bool,
+}
+
+#[derive(Clone, FromRow, Fields, Debug, Deserialize)]
 pub struct UserForLogin {
     pub id: i64,
     pub username: String,
@@ -38,7 +54,7 @@ pub struct UserForLogin {
     pub token_salt: Uuid,
 }
 
-#[derive(Clone, FromRow, Fields, Debug)]
+#[derive(Clone, FromRow, Fields, Debug, Deserialize)]
 pub struct UserForAuth {
     pub id: i64,
     pub username: String,
@@ -48,7 +64,7 @@ pub struct UserForAuth {
 }
 
 /// Marker trait
-pub trait UserBy: HasFields + for<'r> FromRow<'r, PgRow> + Unpin + Send {}
+pub trait UserBy: HasFields + DeserializeOwned + for<'r> FromRow<'r, PgRow> + Unpin + Send {}
 
 impl UserBy for User {}
 impl UserBy for UserForLogin {}
@@ -60,9 +76,34 @@ pub struct UserBmc;
 
 impl DbBmc for UserBmc {
     const TABLE: &'static str = "user";
+    const UNIQUE_FIELDS: &'static [&'static str] = &["username"];
+
+    fn column_defaults() -> Row {
+        Row::from_iter([
+            ("pwd_salt".to_string(), Uuid::new_v4().to_string().into()),
+            ("token_salt".to_string(), Uuid::new_v4().to_string().into()),
+            ("admin".to_string(), false.into()),
+        ])
+    }
 }
 
 impl UserBmc {
+    pub async fn create(ctx: &Ctx, mm: &ModelManager, user_c: UserForCreate) -> Result<i64> {
+        let UserForCreate {
+            username,
+            pwd_clear,
+        } = user_c;
+
+        mm.transaction(|mm| async move {
+            let user_fi = UserForInsert { username };
+            let id = base::create::<Self, _>(ctx, &mm, user_fi).await?;
+            Self::update_pwd(ctx, &mm, id, &pwd_clear).await?;
+
+            Ok(id)
+        })
+        .await
+    }
+
     pub async fn get<E>(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<E>
     where
         E: UserBy,
@@ -71,41 +112,70 @@ impl UserBmc {
     }
 
     pub async fn first_by_username<E>(
-        _ctx: &Ctx,
+        ctx: &Ctx,
         mm: &ModelManager,
         username: &str,
     ) -> Result<Option<E>>
     where
         E: UserBy,
     {
-        let db = mm.db();
+        base::first_by::<Self, _, _>(ctx, mm, "username", username).await
+    }
 
-        let user = sqlb::select()
-            .table(Self::TABLE)
-            .and_where("username", "=", username)
-            .fetch_optional::<_, E>(db)
-            .await?;
+    pub async fn get_by_username<E>(ctx: &Ctx, mm: &ModelManager, username: &str) -> Result<E>
+    where
+        E: UserBy,
+    {
+        Self::first_by_username(ctx, mm, username)
+            .await?
+            .ok_or(Error::EntityNotFoundBy {
+                entity: Self::TABLE,
+                field: "username",
+                value: username.to_string(),
+            })
+    }
 
-        Ok(user)
+    /// If the `Ctx` user is an admin (the root ctx, of the system tasks,
+    /// always is).
+    pub async fn is_admin(ctx: &Ctx, mm: &ModelManager) -> Result<bool> {
+        if ctx.is_root() {
+            return Ok(true);
+        }
+        let user: User = Self::get(ctx, mm, ctx.user_id()).await?;
+
+        Ok(user.admin)
     }
 
-    pub async fn update_pwd(ctx: &Ctx, mm: &ModelManager, id: i64, pwd_clear: &str) -> Result<()> {
-        let db = mm.db();
+    /// Check the `Ctx` user is an admin (`NotAdmin` otherwise).
+    pub async fn check_admin(ctx: &Ctx, mm: &ModelManager) -> Result<()> {
+        if Self::is_admin(ctx, mm).await? {
+            Ok(())
+        } else {
+            Err(Error::NotAdmin {
+                user_id: ctx.user_id(),
+            })
+        }
+    }
 
+    /// Grant or revoke the admin role of the user, as an admin.
+    pub async fn set_admin(ctx: &Ctx, mm: &ModelManager, id: i64, admin: bool) -> Result<()> {
+        Self::check_admin(ctx, mm).await?;
+
+        base::update::<Self, _>(ctx, mm, id, None, UserForAdmin { admin }).await
+    }
+
+    pub async fn update_pwd(ctx: &Ctx, mm: &ModelManager, id: i64, pwd_clear: &str) -> Result<()> {
         let user: UserForLogin = Self::get(ctx, mm, id).await?;
         let pwd = pwd::encrypt_pwd(&EncryptContent {
             content: pwd_clear.to_string(),
             salt: user.pwd_salt.to_string(),
         })?;
 
-        sqlb::update()
-            .table(Self::TABLE)
-            .and_where("id", "=", id)
-            .data(vec![("pwd", pwd.to_string()).into()])
-            .exec(db)
-            .await?;
+        let user_u = UserForUpdatePwd {
+            pwd: pwd.to_string(),
+        };
 
-        Ok(())
+        base::update::<Self, _>(ctx, mm, id, None, user_u).await
     }
 }
 
@@ -115,9 +185,7 @@ mod tests {
     use super::*;
     use crate::_dev_utils;
     use anyhow::{Context, Result};
-    use serial_test::serial;
 
-    #[serial]
     #[tokio::test]
     async fn test_first_ok_demo1() -> Result<()> {
         // -- Setup & Fixtures
@@ -135,5 +203,62 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_create_err_username_taken() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let fx_username = "demo1";
+
+        // -- Exec
+        let res = UserBmc::create(
+            &ctx,
+            &mm,
+            UserForCreate {
+                username: fx_username.to_string(),
+                pwd_clear: "test_create_err_username_taken pwd".to_string(),
+            },
+        )
+        .await;
+
+        // -- Check
+        assert!(
+            matches!(
+                &res,
+                Err(Error::UniqueViolation { entity, field })
+                    if entity == "user" && field == "username"
+            ),
+            "UniqueViolation not matching: {res:?}"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_by_username_err_not_found() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let fx_username = "test_get_by_username_err_not_found";
+
+        // -- Exec
+        let res = UserBmc::get_by_username::<User>(&ctx, &mm, fx_username).await;
+
+        // -- Check
+        assert!(
+            matches!(
+                &res,
+                Err(Error::EntityNotFoundBy {
+                    entity: "user",
+                    field: "username",
+                    value,
+                }) if value == fx_username
+            ),
+            "EntityNotFoundBy not matching"
+        );
+
+        Ok(())
+    }
 }
 // endregion: --- Tests
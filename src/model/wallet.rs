@@ -0,0 +1,357 @@
+//! Wallet addresses linked by the users (e.g., to be paid to, or to sign
+//! the project payouts with), each address being linked to a single user.
+//!
+//! Linking requires a proof of ownership: the wallet signs (ADR-036
+//! `signArbitrary`) the message of `WalletBmc::challenge`, which names the
+//! address, the user and an expiration, and carries a server HMAC (so the
+//! challenges are stateless).
+//!
+
+use crate::config;
+use crate::crypt::{adr036, encrypt_into_b64u, EncryptContent};
+use crate::ctx::Ctx;
+use crate::model::address::{self, validate_address};
+use crate::model::base::{self, DbBmc};
+use crate::model::{Error, ModelManager, Result};
+use crate::utils::{now_utc, now_utc_plus_sec_str, parse_utc};
+use serde::{Deserialize, Serialize};
+use sqlb::Fields;
+use sqlx::FromRow;
+use validator::Validate;
+
+// region:    --- Wallet Types
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct Wallet {
+    pub id: i64,
+    pub wallet_user_id: i64,
+    pub wallet_address: String,
+    pub wallet_label: String,
+}
+
+const CHALLENGE_DURATION_SEC: f64 = 600.0;
+const CHALLENGE_SALT: &str = "wallet_link";
+
+#[derive(Deserialize, Validate)]
+pub struct WalletForChallenge {
+    #[validate(length(min = 1, max = 256), custom = "validate_address")]
+    pub wallet_address: String,
+}
+
+/// The message to sign with the wallet, to link it.
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletChallenge {
+    pub wallet_address: String,
+    pub message: String,
+    /// Rfc3339
+    pub expires: String,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct WalletForLink {
+    #[validate(length(min = 1, max = 256), custom = "validate_address")]
+    pub wallet_address: String,
+    #[validate(length(max = 128))]
+    #[serde(default)]
+    pub wallet_label: String,
+    /// The `WalletChallenge` message.
+    #[validate(length(min = 1, max = 1024))]
+    pub challenge: String,
+    /// Secp256k1 compressed public key of the wallet (base64).
+    #[validate(length(min = 1, max = 128))]
+    pub pub_key: String,
+    /// ADR-036 signature of the challenge (base64).
+    #[validate(length(min = 1, max = 256))]
+    pub signature: String,
+}
+
+/// `WalletForLink` with the user (the `Ctx` user).
+#[derive(Serialize, Fields)]
+pub struct WalletForInsert {
+    pub wallet_user_id: i64,
+    pub wallet_address: String,
+    pub wallet_label: String,
+}
+
+#[derive(Default, Serialize, Fields)]
+pub struct WalletFilter {
+    pub wallet_user_id: Option<i64>,
+}
+
+// endregion: --- Wallet Types
+
+pub struct WalletBmc;
+
+impl DbBmc for WalletBmc {
+    const TABLE: &'static str = "user_wallet";
+    const UNIQUE_FIELDS: &'static [&'static str] = &["wallet_address"];
+}
+
+impl WalletBmc {
+    /// The message for the wallet to sign, to be linked to the `Ctx` user.
+    pub fn challenge(ctx: &Ctx, wallet_c: WalletForChallenge) -> Result<WalletChallenge> {
+        let wallet_address = address::normalize(&wallet_c.wallet_address);
+        let expires = now_utc_plus_sec_str(CHALLENGE_DURATION_SEC);
+        let message = challenge_message(ctx.user_id(), &wallet_address, &expires)?;
+
+        Ok(WalletChallenge {
+            wallet_address,
+            message,
+            expires,
+        })
+    }
+
+    /// Link the wallet address to the `Ctx` user, once its signature of
+    /// the challenge checked (`WalletProofInvalid` otherwise).
+    pub async fn link(ctx: &Ctx, mm: &ModelManager, wallet_l: WalletForLink) -> Result<i64> {
+        let wallet_address = address::normalize(&wallet_l.wallet_address);
+        check_proof(ctx, &wallet_address, &wallet_l)?;
+
+        let wallet_i = WalletForInsert {
+            wallet_user_id: ctx.user_id(),
+            wallet_address,
+            wallet_label: wallet_l.wallet_label,
+        };
+        base::create::<Self, _>(ctx, mm, wallet_i).await
+    }
+
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Wallet> {
+        base::get::<Self, _>(ctx, mm, id).await
+    }
+
+    pub async fn list_for_user(ctx: &Ctx, mm: &ModelManager, user_id: i64) -> Result<Vec<Wallet>> {
+        let filter = WalletFilter {
+            wallet_user_id: Some(user_id),
+        };
+        base::list::<Self, _, _>(ctx, mm, Some(filter), None).await
+    }
+
+    /// The wallet of the address (any case).
+    pub async fn first_by_address(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        address: &str,
+    ) -> Result<Option<Wallet>> {
+        base::first_by::<Self, _, _>(ctx, mm, "wallet_address", address::normalize(address)).await
+    }
+
+    /// Unlink a wallet of the `Ctx` user.
+    /// (not found for the wallets of the other users)
+    pub async fn unlink(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        let wallet = Self::get(ctx, mm, id).await?;
+        if wallet.wallet_user_id != ctx.user_id() {
+            return Err(Error::EntityNotFound {
+                entity: Self::TABLE,
+                id,
+            });
+        }
+        base::delete::<Self>(ctx, mm, id).await
+    }
+}
+
+// region:    --- Proof
+
+/// The challenge message, with the HMAC of its content.
+fn challenge_message(user_id: i64, wallet_address: &str, expires: &str) -> Result<String> {
+    let token = encrypt_into_b64u(
+        &config().TOKEN_KEY,
+        &EncryptContent {
+            content: format!("{wallet_address}.{user_id}.{expires}"),
+            salt: CHALLENGE_SALT.to_string(),
+        },
+    )?;
+
+    Ok(format!(
+        "Link the wallet {wallet_address} to the Porta user {user_id}.\n\
+         Expires: {expires}\n\
+         Token: {token}"
+    ))
+}
+
+/// The challenge must be the one of the user and address, not expired,
+/// and signed by the key of the address.
+fn check_proof(ctx: &Ctx, wallet_address: &str, wallet_l: &WalletForLink) -> Result<()> {
+    let invalid = |reason: &'static str| Error::WalletProofInvalid {
+        address: wallet_address.to_string(),
+        reason,
+    };
+
+    // -- Challenge
+    let expires = wallet_l
+        .challenge
+        .lines()
+        .find_map(|line| line.strip_prefix("Expires: "))
+        .ok_or(invalid("challenge"))?;
+    if challenge_message(ctx.user_id(), wallet_address, expires)? != wallet_l.challenge {
+        return Err(invalid("challenge"));
+    }
+    if parse_utc(expires).map_err(|_| invalid("challenge"))? < now_utc() {
+        return Err(invalid("expired"));
+    }
+
+    // -- Public key, of the address
+    let pub_key = adr036::b64_decode(&wallet_l.pub_key).map_err(|_| invalid("pub_key"))?;
+    let pub_key_address = address::from_pub_key(address::hrp(wallet_address), &pub_key)
+        .map_err(|_| invalid("pub_key"))?;
+    if pub_key_address != wallet_address {
+        return Err(invalid("pub_key"));
+    }
+
+    // -- Signature
+    let signature = adr036::b64_decode(&wallet_l.signature).map_err(|_| invalid("signature"))?;
+    adr036::verify(
+        wallet_address,
+        wallet_l.challenge.as_bytes(),
+        &pub_key,
+        &signature,
+    )
+    .map_err(|_| invalid("signature"))
+}
+
+// endregion: --- Proof
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::dev_wallet::DevWallet;
+    use crate::_dev_utils::seed::Seed;
+    use anyhow::{Context, Result};
+
+    const SEED_TOML: &str = r#"
+[[users]]
+username = "alice"
+pwd_clear = "alice pwd 01"
+
+[[users]]
+username = "bob"
+pwd_clear = "bob pwd 01"
+"#;
+
+    #[tokio::test]
+    async fn test_link_unlink_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?
+            .apply(&Ctx::root_ctx(), &mm)
+            .await?;
+        let alice_id = *seeded.users.get("alice").context("alice")?;
+        let alice_ctx = Ctx::new(alice_id)?;
+        let bob_ctx = Ctx::new(*seeded.users.get("bob").context("bob")?)?;
+        let alice_wallet = DevWallet::new("alice");
+        let fx_address = alice_wallet.address("cosmos");
+        let challenge = WalletBmc::challenge(
+            &alice_ctx,
+            WalletForChallenge {
+                wallet_address: fx_address.to_ascii_uppercase(),
+            },
+        )?;
+        let mut wallet_l = alice_wallet.sign_link(&fx_address, &challenge.message);
+        wallet_l.wallet_address = fx_address.to_ascii_uppercase();
+        wallet_l.wallet_label = "keplr".to_string();
+        wallet_l.validate()?;
+
+        // -- Exec
+        let id = WalletBmc::link(&alice_ctx, &mm, wallet_l).await?;
+
+        // -- Check
+        let wallets = WalletBmc::list_for_user(&alice_ctx, &mm, alice_id).await?;
+        assert_eq!(wallets.len(), 1);
+        assert_eq!(wallets[0].wallet_address, fx_address, "normalized");
+
+        let res = alice_wallet.link(&bob_ctx, &mm, "cosmos").await;
+        assert!(
+            matches!(res, Err(Error::UniqueViolation { .. })),
+            "UniqueViolation not matching"
+        );
+
+        let res = WalletBmc::unlink(&bob_ctx, &mm, id).await;
+        assert!(
+            matches!(res, Err(Error::EntityNotFound { .. })),
+            "EntityNotFound not matching"
+        );
+        WalletBmc::unlink(&alice_ctx, &mm, id).await?;
+        assert!(WalletBmc::list_for_user(&alice_ctx, &mm, alice_id)
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_link_err_proof() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?
+            .apply(&Ctx::root_ctx(), &mm)
+            .await?;
+        let alice_ctx = Ctx::new(*seeded.users.get("alice").context("alice")?)?;
+        let bob_ctx = Ctx::new(*seeded.users.get("bob").context("bob")?)?;
+        let alice_wallet = DevWallet::new("alice");
+        let fx_address = alice_wallet.address("cosmos");
+        let challenge = |ctx: &Ctx| {
+            WalletBmc::challenge(
+                ctx,
+                WalletForChallenge {
+                    wallet_address: fx_address.clone(),
+                },
+            )
+        };
+        let expired = challenge_message(alice_ctx.user_id(), &fx_address, "2020-01-01T00:00:00Z")?;
+
+        // -- Exec & Check
+        let cases = [
+            // (the challenge of another user)
+            (
+                alice_wallet.sign_link(&fx_address, &challenge(&bob_ctx)?.message),
+                "challenge",
+            ),
+            (alice_wallet.sign_link(&fx_address, &expired), "expired"),
+            // (signed by another key)
+            (
+                DevWallet::new("bob").sign_link(&fx_address, &challenge(&alice_ctx)?.message),
+                "pub_key",
+            ),
+            // (the signature of another message)
+            (
+                WalletForLink {
+                    challenge: challenge(&alice_ctx)?.message,
+                    ..alice_wallet.sign_link(&fx_address, "another message")
+                },
+                "signature",
+            ),
+        ];
+        for (wallet_l, reason) in cases {
+            let res = WalletBmc::link(&alice_ctx, &mm, wallet_l).await;
+            assert!(
+                matches!(&res, Err(Error::WalletProofInvalid { reason: r, .. }) if *r == reason),
+                "{reason}: {res:?}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_link_validate_err() -> Result<()> {
+        let wallet_l = WalletForLink {
+            wallet_address: "cosmos1alice".to_string(),
+            ..DevWallet::new("alice").sign_link("cosmos1alice", "challenge")
+        };
+
+        let errors = wallet_l.validate().err().context("should be invalid")?;
+        let field_errors = errors.field_errors();
+        let err = &field_errors
+            .get("wallet_address")
+            .context("wallet_address")?[0];
+        assert_eq!(err.code, "address");
+        assert_eq!(
+            err.message.as_deref(),
+            Some("must be a bech32 address (e.g., cosmos1...)")
+        );
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
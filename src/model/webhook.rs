@@ -0,0 +1,632 @@
+//! Project webhooks, notifying external services (e.g., a chat bot, the
+//! accounting scripts) of the project changes.
+//!
+//! Design:
+//!
+//! - A webhook subscribes a URL to event types of a project, each event
+//!   type being `<entity>.<kind>` of a `ModelEvent` (e.g.,
+//!   `bounty.transitioned`), `<entity>.*`, or `*`.
+//! - The events of the project, its bounties, and their applications and
+//!   submissions are delivered, as a `WebhookEvent` JSON body signed with
+//!   the webhook secret (see `worker::webhook`).
+//! - Each delivery attempt is logged as a `WebhookDelivery`.
+//! - Only the project lead manages the project webhooks (see
+//!   `ProjectBmc::check_lead`).
+//! - A webhook URL is https, its host resolving to public addresses only
+//!   (not loopback, private, link-local, or unique local), checked on
+//!   create, update, and again on delivery (see `check_webhook_url`).
+//!   (`WEBHOOK_ALLOW_LOCAL` allows http and any address, for dev only)
+//!
+
+use crate::config;
+use crate::ctx::Ctx;
+use crate::model::application::ApplicationBmc;
+use crate::model::base::{self, DbBmc, ListOptions};
+use crate::model::bounty::BountyBmc;
+use crate::model::event::{EventKind, ModelEvent};
+use crate::model::project::ProjectBmc;
+use crate::model::store::memory::Row;
+use crate::model::submission::SubmissionBmc;
+use crate::model::{Error, ModelManager, Result};
+use crate::utils::now_utc;
+use lazy_regex::regex_is_match;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlb::Fields;
+use sqlx::FromRow;
+use std::net::{IpAddr, SocketAddr};
+use time::OffsetDateTime;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+// region:    --- Webhook Types
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct Webhook {
+    pub id: i64,
+    pub webhook_project_id: i64,
+    pub webhook_url: String,
+    /// Comma separated event types.
+    pub webhook_events: String,
+    /// (never returned)
+    #[serde(skip_serializing)]
+    pub webhook_secret: String,
+    pub webhook_enabled: bool,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct WebhookForCreate {
+    pub webhook_project_id: i64,
+    #[validate(custom = "validate_webhook_url")]
+    pub webhook_url: String,
+    #[validate(custom = "validate_webhook_events")]
+    pub webhook_events: Vec<String>,
+    #[validate(length(min = 16, max = 256))]
+    pub webhook_secret: String,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct WebhookForUpdate {
+    #[validate(custom = "validate_webhook_url")]
+    pub webhook_url: Option<String>,
+    #[validate(custom = "validate_webhook_events")]
+    pub webhook_events: Option<Vec<String>>,
+    #[validate(length(min = 16, max = 256))]
+    pub webhook_secret: Option<String>,
+    pub webhook_enabled: Option<bool>,
+}
+
+/// `WebhookForCreate` with the joined event types.
+#[derive(Serialize, Fields)]
+pub struct WebhookForInsert {
+    pub webhook_project_id: i64,
+    pub webhook_url: String,
+    pub webhook_events: String,
+    pub webhook_secret: String,
+}
+
+/// `WebhookForUpdate` with the joined event types.
+#[derive(Serialize, Fields)]
+pub struct WebhookForPatch {
+    pub webhook_url: Option<String>,
+    pub webhook_events: Option<String>,
+    pub webhook_secret: Option<String>,
+    pub webhook_enabled: Option<bool>,
+}
+
+#[derive(Default, Deserialize, Serialize, Fields)]
+pub struct WebhookFilter {
+    pub webhook_project_id: Option<i64>,
+    pub webhook_enabled: Option<bool>,
+}
+
+fn validate_webhook_url(value: &str) -> core::result::Result<(), ValidationError> {
+    if value.len() <= 2048 && regex_is_match!(r"^https?://[^\s/?#]+[^\s]*$", value) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("webhook_url"))
+    }
+}
+
+fn validate_webhook_events(values: &[String]) -> core::result::Result<(), ValidationError> {
+    let valid = |value: &String| {
+        regex_is_match!(
+            r"^(\*|[a-z_]+\.(\*|created|updated|deleted|transitioned))$",
+            value
+        )
+    };
+    if !values.is_empty() && values.len() <= 32 && values.iter().all(valid) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("webhook_events"))
+    }
+}
+
+impl Webhook {
+    /// If the webhook subscribes to the event type (e.g.,
+    /// `bounty.transitioned`).
+    pub fn subscribes(&self, event_type: &str) -> bool {
+        let entity = event_type.split_once('.').map_or(event_type, |(e, _)| e);
+        self.webhook_events.split(',').any(|pattern| {
+            pattern == "*" || pattern == event_type || pattern.strip_suffix(".*") == Some(entity)
+        })
+    }
+}
+
+/// The JSON body of a webhook delivery.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WebhookEvent {
+    /// Unique per event, the same on the retries (for the receiver to
+    /// dedupe).
+    pub id: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub project_id: i64,
+    pub entity: String,
+    pub entity_id: i64,
+    /// The `EventKind` data (e.g., `{"from": "open", "to": "assigned"}`).
+    pub data: Value,
+    #[serde(with = "time::serde::rfc3339")]
+    pub time: OffsetDateTime,
+}
+
+impl WebhookEvent {
+    pub fn new(event: &ModelEvent, project_id: i64) -> Self {
+        let data = match &event.kind {
+            EventKind::Transitioned { from, to } => json!({ "from": from, "to": to }),
+            _ => Value::Null,
+        };
+        WebhookEvent {
+            id: Uuid::new_v4().to_string(),
+            event_type: event_type(event),
+            project_id,
+            entity: event.entity.to_string(),
+            entity_id: event.id,
+            data,
+            time: now_utc(),
+        }
+    }
+
+    /// The event sent by the webhook test.
+    pub fn sample(project_id: i64) -> Self {
+        WebhookEvent {
+            event_type: "webhook.test".to_string(),
+            ..Self::new(
+                &ModelEvent::new("webhook", 0, EventKind::Created),
+                project_id,
+            )
+        }
+    }
+}
+
+/// The `<entity>.<kind>` type of the event (e.g., `bounty.transitioned`).
+pub fn event_type(event: &ModelEvent) -> String {
+    format!(
+        "{}.{}",
+        event.entity,
+        event.kind.as_ref().to_ascii_lowercase()
+    )
+}
+
+// endregion: --- Webhook Types
+
+// region:    --- Webhook URL
+
+/// Check the webhook URL: https, its host resolving to public addresses only
+/// (`WebhookUrlNotAllowed` otherwise).
+/// (`allow_local` allowing http and any address, see `WEBHOOK_ALLOW_LOCAL`)
+pub async fn check_webhook_url(url: &str, allow_local: bool) -> Result<()> {
+    let not_allowed = |host: &str, reason| Error::WebhookUrlNotAllowed {
+        host: host.to_string(),
+        reason,
+    };
+    let url = reqwest::Url::parse(url).map_err(|_| not_allowed("", "invalid url"))?;
+    let host = url.host_str().ok_or(not_allowed("", "no host"))?;
+    match url.scheme() {
+        "https" => (),
+        "http" if allow_local => (),
+        _ => return Err(not_allowed(host, "not https")),
+    }
+
+    let port = url.port_or_known_default().unwrap_or(443);
+    resolve_public(host, port, allow_local).await?;
+
+    Ok(())
+}
+
+/// The addresses of the host, all public (`WebhookUrlNotAllowed` otherwise).
+/// (`allow_local` allowing any address)
+pub async fn resolve_public(host: &str, port: u16, allow_local: bool) -> Result<Vec<SocketAddr>> {
+    let not_allowed = |reason| Error::WebhookUrlNotAllowed {
+        host: host.to_string(),
+        reason,
+    };
+    // (bracketed in the URLs)
+    let name = host.trim_start_matches('[').trim_end_matches(']');
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name, port))
+        .await
+        .map_err(|_| not_allowed("unresolved"))?
+        .collect();
+
+    if addrs.is_empty() {
+        return Err(not_allowed("unresolved"));
+    }
+    if !allow_local && !addrs.iter().all(|addr| is_public_ip(addr.ip())) {
+        return Err(not_allowed("not a public address"));
+    }
+
+    Ok(addrs)
+}
+
+/// If the address is routable on the internet (not loopback, private,
+/// link-local, unique local, or otherwise reserved).
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // "this network" 0/8, shared 100.64/10, benchmarking 198.18/15,
+                // reserved 240/4
+                || a == 0
+                || (a == 100 && (b & 0xc0) == 64)
+                || (a == 198 && (b & 0xfe) == 18)
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(ip));
+            }
+            let segment = ip.segments()[0];
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                // unique local fc00::/7, link-local fe80::/10
+                || (segment & 0xfe00) == 0xfc00
+                || (segment & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+// endregion: --- Webhook URL
+
+// region:    --- WebhookDelivery Types
+
+#[derive(Debug, Clone, Fields, FromRow, Deserialize, Serialize)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub delivery_webhook_id: i64,
+    pub delivery_event_id: String,
+    pub delivery_event_type: String,
+    pub delivery_attempt: i32,
+    pub delivery_status_code: Option<i32>,
+    pub delivery_error: Option<String>,
+    pub delivery_ok: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub delivery_time: OffsetDateTime,
+}
+
+#[derive(Serialize, Fields)]
+pub struct WebhookDeliveryForInsert {
+    pub delivery_webhook_id: i64,
+    pub delivery_event_id: String,
+    pub delivery_event_type: String,
+    pub delivery_attempt: i32,
+    pub delivery_status_code: Option<i32>,
+    pub delivery_error: Option<String>,
+    pub delivery_ok: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub delivery_time: OffsetDateTime,
+}
+
+#[derive(Default, Deserialize, Serialize, Fields)]
+pub struct WebhookDeliveryFilter {
+    pub delivery_webhook_id: Option<i64>,
+    pub delivery_event_id: Option<String>,
+    pub delivery_ok: Option<bool>,
+}
+
+// endregion: --- WebhookDelivery Types
+
+pub struct WebhookBmc;
+
+impl DbBmc for WebhookBmc {
+    const TABLE: &'static str = "webhook";
+
+    fn column_defaults() -> Row {
+        Row::from_iter([("webhook_enabled".to_string(), true.into())])
+    }
+}
+
+impl WebhookBmc {
+    pub async fn create(ctx: &Ctx, mm: &ModelManager, webhook_c: WebhookForCreate) -> Result<i64> {
+        ProjectBmc::check_lead(ctx, mm, webhook_c.webhook_project_id).await?;
+        check_webhook_url(&webhook_c.webhook_url, config().WEBHOOK_ALLOW_LOCAL).await?;
+
+        let webhook_i = WebhookForInsert {
+            webhook_project_id: webhook_c.webhook_project_id,
+            webhook_url: webhook_c.webhook_url,
+            webhook_events: webhook_c.webhook_events.join(","),
+            webhook_secret: webhook_c.webhook_secret,
+        };
+        base::create::<Self, _>(ctx, mm, webhook_i).await
+    }
+
+    /// The webhook, for the lead of its project.
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<Webhook> {
+        let webhook: Webhook = base::get::<Self, _>(ctx, mm, id).await?;
+        ProjectBmc::check_lead(ctx, mm, webhook.webhook_project_id).await?;
+
+        Ok(webhook)
+    }
+
+    /// The webhooks of the `webhook_project_id` filter project, for its lead
+    /// (of all the projects for the root ctx).
+    pub async fn list(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        filter: Option<WebhookFilter>,
+        list_options: Option<ListOptions>,
+    ) -> Result<Vec<Webhook>> {
+        if !ctx.is_root() {
+            match filter.as_ref().and_then(|f| f.webhook_project_id) {
+                Some(project_id) => {
+                    ProjectBmc::check_lead(ctx, mm, project_id).await?;
+                }
+                None => {
+                    return Err(Error::NotProjectLead {
+                        project_id: None,
+                        user_id: ctx.user_id(),
+                    })
+                }
+            }
+        }
+
+        base::list::<Self, _, _>(ctx, mm, filter, list_options).await
+    }
+
+    pub async fn update(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        id: i64,
+        webhook_u: WebhookForUpdate,
+    ) -> Result<()> {
+        Self::get(ctx, mm, id).await?;
+        if let Some(url) = &webhook_u.webhook_url {
+            check_webhook_url(url, config().WEBHOOK_ALLOW_LOCAL).await?;
+        }
+
+        let webhook_p = WebhookForPatch {
+            webhook_url: webhook_u.webhook_url,
+            webhook_events: webhook_u.webhook_events.map(|events| events.join(",")),
+            webhook_secret: webhook_u.webhook_secret,
+            webhook_enabled: webhook_u.webhook_enabled,
+        };
+        base::update::<Self, _>(ctx, mm, id, None, webhook_p).await
+    }
+
+    pub async fn delete(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<()> {
+        Self::get(ctx, mm, id).await?;
+        base::delete::<Self>(ctx, mm, id).await
+    }
+
+    /// The enabled webhooks of the project subscribed to the event type.
+    pub async fn list_subscribed(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        project_id: i64,
+        event_type: &str,
+    ) -> Result<Vec<Webhook>> {
+        let filter = WebhookFilter {
+            webhook_project_id: Some(project_id),
+            webhook_enabled: Some(true),
+        };
+        let webhooks = Self::list(ctx, mm, Some(filter), None).await?;
+
+        Ok(webhooks
+            .into_iter()
+            .filter(|webhook| webhook.subscribes(event_type))
+            .collect())
+    }
+
+    /// The project of the event entity, if any.
+    /// (none for the deleted entities, their row being gone)
+    pub async fn event_project_id(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        event: &ModelEvent,
+    ) -> Result<Option<i64>> {
+        let bounty_id = match event.entity {
+            "project" => return Ok(Some(event.id)),
+            "bounty" => event.id,
+            "bounty_application" => match ApplicationBmc::get(ctx, mm, event.id).await {
+                Ok(application) => application.application_bounty_id,
+                Err(Error::EntityNotFound { .. }) => return Ok(None),
+                Err(ex) => return Err(ex),
+            },
+            "submission" => match SubmissionBmc::get(ctx, mm, event.id).await {
+                Ok(submission) => submission.submission_bounty_id,
+                Err(Error::EntityNotFound { .. }) => return Ok(None),
+                Err(ex) => return Err(ex),
+            },
+            _ => return Ok(None),
+        };
+
+        match BountyBmc::get(ctx, mm, bounty_id).await {
+            Ok(bounty) => Ok(bounty.bounty_project_id),
+            Err(Error::EntityNotFound { .. }) => Ok(None),
+            Err(ex) => Err(ex),
+        }
+    }
+}
+
+pub struct WebhookDeliveryBmc;
+
+impl DbBmc for WebhookDeliveryBmc {
+    const TABLE: &'static str = "webhook_delivery";
+}
+
+impl WebhookDeliveryBmc {
+    pub async fn record(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        delivery_i: WebhookDeliveryForInsert,
+    ) -> Result<i64> {
+        base::create::<Self, _>(ctx, mm, delivery_i).await
+    }
+
+    pub async fn get(ctx: &Ctx, mm: &ModelManager, id: i64) -> Result<WebhookDelivery> {
+        base::get::<Self, _>(ctx, mm, id).await
+    }
+
+    /// The delivery attempts of the webhook (oldest first).
+    pub async fn list_for_webhook(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        webhook_id: i64,
+        filter: Option<WebhookDeliveryFilter>,
+        list_options: Option<ListOptions>,
+    ) -> Result<Vec<WebhookDelivery>> {
+        let filter = WebhookDeliveryFilter {
+            delivery_webhook_id: Some(webhook_id),
+            ..filter.unwrap_or_default()
+        };
+        base::list::<Self, _, _>(ctx, mm, Some(filter), list_options).await
+    }
+
+    /// The number of the delivery attempts of the event to the webhook.
+    pub async fn count_attempts(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        webhook_id: i64,
+        event_id: &str,
+    ) -> Result<i32> {
+        let filter = WebhookDeliveryFilter {
+            delivery_event_id: Some(event_id.to_string()),
+            ..Default::default()
+        };
+        let deliveries = Self::list_for_webhook(ctx, mm, webhook_id, Some(filter), None).await?;
+
+        Ok(deliveries.len() as i32)
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::Seed;
+    use anyhow::{Context, Result};
+
+    const SEED_TOML: &str = r#"
+[[users]]
+username = "alice"
+pwd_clear = "alice pwd 01"
+
+[[projects]]
+key = "p1"
+project_id = "webhook-model-project-01"
+project_name = "Webhook model project 01"
+project_lead_address = "cosmos1udzkhs056fc0f2texd6cv30acg0rjept43xsph"
+project_budget = "1000"
+project_denom = "uatom"
+project_type = "dev"
+project_description = "Not led by alice"
+"#;
+
+    fn webhook(events: &str) -> Webhook {
+        Webhook {
+            id: 1000,
+            webhook_project_id: 1000,
+            webhook_url: "http://localhost/hook".to_string(),
+            webhook_events: events.to_string(),
+            webhook_secret: "0123456789abcdef".to_string(),
+            webhook_enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_subscribes_ok() {
+        assert!(webhook("*").subscribes("bounty.created"));
+        assert!(webhook("bounty.*").subscribes("bounty.transitioned"));
+        assert!(!webhook("bounty.*").subscribes("bounty_application.created"));
+        assert!(webhook("project.updated,bounty.transitioned").subscribes("bounty.transitioned"));
+        assert!(!webhook("bounty.transitioned").subscribes("bounty.updated"));
+    }
+
+    #[test]
+    fn test_validate_webhook_events() {
+        let events = |values: &[&str]| -> Vec<String> {
+            values.iter().map(|value| value.to_string()).collect()
+        };
+        assert!(validate_webhook_events(&events(&["*", "bounty.transitioned"])).is_ok());
+        assert!(validate_webhook_events(&events(&["bounty_application.*"])).is_ok());
+        assert!(validate_webhook_events(&events(&[])).is_err());
+        assert!(validate_webhook_events(&events(&["bounty.closed"])).is_err());
+        assert!(validate_webhook_events(&events(&["bounty.created,project.*"])).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_webhook_url() -> Result<()> {
+        let reason = |res: Result<(), Error>| match res {
+            Err(Error::WebhookUrlNotAllowed { reason, .. }) => Some(reason),
+            _ => None,
+        };
+
+        assert!(check_webhook_url("https://8.8.8.8/hook", false)
+            .await
+            .is_ok());
+        assert!(
+            check_webhook_url("https://[2001:4860:4860::8888]/hook", false)
+                .await
+                .is_ok()
+        );
+        assert!(check_webhook_url("http://127.0.0.1/hook", true)
+            .await
+            .is_ok());
+        assert_eq!(
+            reason(check_webhook_url("http://8.8.8.8/hook", false).await),
+            Some("not https")
+        );
+        for url in [
+            "https://127.0.0.1/hook",
+            "https://localhost/hook",
+            "https://10.0.0.1/hook",
+            "https://192.168.1.1/hook",
+            "https://169.254.169.254/latest/meta-data",
+            "https://0.0.0.0/hook",
+            "https://[::1]/hook",
+            "https://[fd00::1]/hook",
+            "https://[fe80::1]/hook",
+            "https://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert_eq!(
+                reason(check_webhook_url(url, false).await),
+                Some("not a public address"),
+                "{url}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_err_not_lead() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?
+            .apply(&Ctx::root_ctx(), &mm)
+            .await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let alice_ctx = Ctx::new(*seeded.users.get("alice").context("alice")?)?;
+        let webhook_c = WebhookForCreate {
+            webhook_project_id: project_id,
+            webhook_url: "https://8.8.8.8/hook".to_string(),
+            webhook_events: vec!["*".to_string()],
+            webhook_secret: "webhook secret 01".to_string(),
+        };
+
+        // -- Exec
+        let res = WebhookBmc::create(&alice_ctx, &mm, webhook_c).await;
+        let list_res = WebhookBmc::list(&alice_ctx, &mm, None, None).await;
+
+        // -- Check
+        assert!(
+            matches!(res, Err(Error::NotProjectLead { .. })),
+            "NotProjectLead not matching"
+        );
+        assert!(
+            matches!(list_res, Err(Error::NotProjectLead { .. })),
+            "NotProjectLead not matching (list)"
+        );
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
@@ -0,0 +1,195 @@
+//! Background scheduler of the time based model changes.
+//!
+//! Design:
+//!
+//! - Spawned at startup (see `main`), it ticks every
+//!   `SCHEDULER_INTERVAL_SEC`, and at each tick:
+//!   - expires the overdue open or assigned bounties
+//!     (see `BountyBmc::list_overdue`),
+//!   - lapses the accepted applications past their deadline, un-assigning
+//!     their bounty (see `ApplicationBmc::list_lapsed`).
+//! - Changes go through the Bmc functions (as the root `Ctx`), so they
+//!   publish their `Transitioned` events and post their ledger entries as
+//!   any other change.
+//! - A failing change is logged and retried at the next tick, without
+//!   blocking the other ones.
+//! - The time comes from a `Clock`, a `ManualClock` in the tests.
+//!
+
+// region:    --- Modules
+
+mod clock;
+
+pub use self::clock::{Clock, ManualClock, SystemClock};
+
+use crate::ctx::Ctx;
+use crate::model::application::ApplicationBmc;
+use crate::model::bounty::{BountyBmc, BountyStatus};
+use crate::model::{ModelManager, Result};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, error};
+
+// endregion: --- Modules
+
+pub struct Scheduler {
+    mm: ModelManager,
+    clock: Arc<dyn Clock>,
+}
+
+/// The changes of a tick.
+#[derive(Debug, Default)]
+pub struct TickReport {
+    /// Expired bounty ids.
+    pub expired: Vec<i64>,
+    /// Lapsed application ids.
+    pub lapsed: Vec<i64>,
+}
+
+impl Scheduler {
+    pub fn new(mm: ModelManager, clock: Arc<dyn Clock>) -> Self {
+        Scheduler { mm, clock }
+    }
+
+    /// Tick every `interval`, until the runtime shuts down.
+    pub fn spawn(self, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match self.tick().await {
+                    Ok(report) => debug!("{:<12} - {report:?}", "SCHEDULER"),
+                    Err(ex) => error!("{:<12} - tick failed: {ex:?}", "SCHEDULER"),
+                }
+            }
+        })
+    }
+
+    /// Run the due changes, as of the clock time.
+    pub async fn tick(&self) -> Result<TickReport> {
+        let ctx = Ctx::root_ctx();
+        let mm = &self.mm;
+        let now = self.clock.now();
+        let mut report = TickReport::default();
+
+        // (lapsed first, so an un-assigned bounty past its deadline expires)
+        for application in ApplicationBmc::list_lapsed(&ctx, mm, now).await? {
+            match ApplicationBmc::lapse(&ctx, mm, application.id).await {
+                Ok(()) => report.lapsed.push(application.id),
+                Err(ex) => error!(
+                    "{:<12} - lapse application {}: {ex:?}",
+                    "SCHEDULER", application.id
+                ),
+            }
+        }
+
+        for bounty in BountyBmc::list_overdue(&ctx, mm, now).await? {
+            match BountyBmc::transition(&ctx, mm, bounty.id, BountyStatus::Expired).await {
+                Ok(()) => report.expired.push(bounty.id),
+                Err(ex) => error!("{:<12} - expire bounty {}: {ex:?}", "SCHEDULER", bounty.id),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::Seed;
+    use crate::model::application::{ApplicationForCreate, ApplicationStatus};
+    use anyhow::{Context, Result};
+    use time::macros::datetime;
+
+    const SEED_TOML: &str = r#"
+[[users]]
+username = "alice"
+pwd_clear = "alice pwd 01"
+
+[[bounties]]
+key = "b1"
+bounty_title = "Scheduler bounty 01"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "A PR"
+bounty_description = "Due first"
+bounty_deadline = "2030-01-01T00:00:00Z"
+
+[[bounties]]
+key = "b2"
+bounty_title = "Scheduler bounty 02"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "A PR"
+bounty_description = "Due later"
+bounty_deadline = "2030-06-01T00:00:00Z"
+
+[[bounties]]
+key = "b3"
+bounty_title = "Scheduler bounty 03"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "A PR"
+bounty_description = "Never due"
+"#;
+
+    #[tokio::test]
+    async fn test_tick_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let b1 = *seeded.bounties.get("b1").context("b1")?;
+        let b2 = *seeded.bounties.get("b2").context("b2")?;
+        let b3 = *seeded.bounties.get("b3").context("b3")?;
+        let alice_ctx = Ctx::new(*seeded.users.get("alice").context("alice")?)?;
+        let application_c = ApplicationForCreate {
+            application_bounty_id: b2,
+            application_message: "On it".to_string(),
+            application_estimated_days: 10,
+            application_address: "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn".to_string(),
+            application_deadline: Some(datetime!(2030-01-15 0:00 UTC)),
+        };
+        let application_id = ApplicationBmc::create(&alice_ctx, &mm, application_c).await?;
+        ApplicationBmc::accept(&ctx, &mm, application_id).await?;
+
+        let clock = ManualClock::new(datetime!(2029-12-01 0:00 UTC));
+        let scheduler = Scheduler::new(mm.clone(), Arc::new(clock.clone()));
+        let mut events = mm.events().subscribe();
+
+        // -- Exec & Check
+        let report = scheduler.tick().await?;
+        assert!(report.expired.is_empty() && report.lapsed.is_empty());
+
+        clock.set(datetime!(2030-02-01 0:00 UTC));
+        let report = scheduler.tick().await?;
+        assert_eq!(report.expired, [b1]);
+        assert_eq!(report.lapsed, [application_id]);
+        let application = ApplicationBmc::get(&ctx, &mm, application_id).await?;
+        assert_eq!(application.application_status, ApplicationStatus::Lapsed);
+        let bounty = BountyBmc::get(&ctx, &mm, b2).await?;
+        assert_eq!(bounty.bounty_status, BountyStatus::Open, "un-assigned");
+        assert_eq!(bounty.bounty_assignee, "");
+
+        let mut transitioned = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            transitioned.push((event.entity, event.id));
+        }
+        assert!(transitioned.contains(&("bounty", b1)));
+        assert!(transitioned.contains(&("bounty_application", application_id)));
+
+        clock.advance(time::Duration::days(365));
+        let report = scheduler.tick().await?;
+        assert_eq!(report.expired, [b2]);
+        assert!(report.lapsed.is_empty());
+        let bounty = BountyBmc::get(&ctx, &mm, b3).await?;
+        assert_eq!(bounty.bounty_status, BountyStatus::Open, "no deadline");
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
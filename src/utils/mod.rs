@@ -26,6 +26,26 @@ pub fn now_utc_plus_sec_str(sec: f64) -> String {
 pub fn parse_utc(moment: &str) -> Result<OffsetDateTime> {
     OffsetDateTime::parse(moment, &Rfc3339).map_err(|_| Error::DateFailParse(moment.to_string()))
 }
+
+/// Serde `with` of an optional rfc3339 time update, with `#[serde(default)]`
+/// (absent: `None`, unchanged; null: `Some(None)`, cleared).
+pub mod rfc3339_update {
+    use serde::{Deserializer, Serializer};
+    use time::OffsetDateTime;
+
+    pub fn serialize<S: Serializer>(
+        time: &Option<Option<OffsetDateTime>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        time::serde::rfc3339::option::serialize(&time.flatten(), serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Option<OffsetDateTime>>, D::Error> {
+        time::serde::rfc3339::option::deserialize(deserializer).map(Some)
+    }
+}
 // endregion: --- Time
 
 // region:    --- Base64
@@ -1,8 +1,10 @@
 use crate::{crypt, model, web};
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use serde::Serialize;
 use tracing::debug;
+use validator::ValidationErrors;
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -14,6 +16,12 @@ pub enum Error {
     RpcMissingParams { rpc_method: String },
     RpcFailJsonParams { rpc_method: String },
 
+    // -- Rest
+    RestInvalidRequest { status: u16, cause: String },
+
+    // -- Validation
+    ValidationFailed { fields: Vec<FieldError> },
+
     // -- Login
     LoginFailUsernameNotFound,
     LoginFailUserHasNoPwd { user_id: i64 },
@@ -43,11 +51,46 @@ impl From<crypt::Error> for Error {
     }
 }
 
+impl From<ValidationErrors> for Error {
+    fn from(val: ValidationErrors) -> Self {
+        Self::ValidationFailed {
+            fields: FieldError::from_validation_errors(&val),
+        }
+    }
+}
+
 impl From<serde_json::Error> for Error {
     fn from(val: serde_json::Error) -> Self {
         Self::SerdeJson(val.to_string())
     }
 }
+
+impl From<JsonRejection> for Error {
+    fn from(val: JsonRejection) -> Self {
+        Self::RestInvalidRequest {
+            status: val.status().as_u16(),
+            cause: val.body_text(),
+        }
+    }
+}
+
+impl From<PathRejection> for Error {
+    fn from(val: PathRejection) -> Self {
+        Self::RestInvalidRequest {
+            status: val.status().as_u16(),
+            cause: val.body_text(),
+        }
+    }
+}
+
+impl From<QueryRejection> for Error {
+    fn from(val: QueryRejection) -> Self {
+        Self::RestInvalidRequest {
+            status: val.status().as_u16(),
+            cause: val.body_text(),
+        }
+    }
+}
 // endregion: --- Froms
 
 // region:    --- Axum IntoResponse
@@ -93,11 +136,244 @@ impl Error {
             // -- Auth
             CtxExt(_) => (StatusCode::FORBIDDEN, ClientError::NO_AUTH),
 
+            // -- Rest
+            RestInvalidRequest { status, cause } => (
+                StatusCode::from_u16(*status).unwrap_or(StatusCode::BAD_REQUEST),
+                ClientError::INVALID_REQUEST {
+                    cause: cause.clone(),
+                },
+            ),
+
+            // -- Validation
+            ValidationFailed { fields } => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::VALIDATION_FAILED {
+                    fields: fields.clone(),
+                },
+            ),
+
             // -- Model
             Model(model::Error::EntityNotFound { entity, id }) => (
-                StatusCode::BAD_REQUEST,
+                StatusCode::NOT_FOUND,
                 ClientError::ENTITY_NOT_FOUND { entity, id: *id },
             ),
+            Model(model::Error::EntityNotFoundBy {
+                entity,
+                field,
+                value,
+            }) => (
+                StatusCode::NOT_FOUND,
+                ClientError::ENTITY_NOT_FOUND_BY {
+                    entity,
+                    field,
+                    value: value.clone(),
+                },
+            ),
+
+            Model(model::Error::VersionConflict {
+                entity,
+                id,
+                current,
+                ..
+            }) => (
+                StatusCode::CONFLICT,
+                ClientError::CONFLICT {
+                    entity,
+                    id: *id,
+                    current_version: *current,
+                },
+            ),
+            Model(model::Error::InvalidTransition {
+                entity,
+                id,
+                from,
+                to,
+            }) => (
+                StatusCode::CONFLICT,
+                ClientError::INVALID_TRANSITION {
+                    entity,
+                    id: *id,
+                    from: from.clone(),
+                    to: to.clone(),
+                },
+            ),
+            Model(model::Error::NotAdmin { .. }) => (StatusCode::FORBIDDEN, ClientError::NOT_ADMIN),
+            Model(model::Error::NotProjectLead { project_id, .. }) => (
+                StatusCode::FORBIDDEN,
+                ClientError::NOT_PROJECT_LEAD {
+                    project_id: *project_id,
+                },
+            ),
+            Model(model::Error::ProjectDenomFrozen { id, denom }) => (
+                StatusCode::CONFLICT,
+                ClientError::PROJECT_DENOM_FROZEN {
+                    id: *id,
+                    denom: denom.clone(),
+                },
+            ),
+            Model(model::Error::BountyNotOpen { id, status }) => (
+                StatusCode::CONFLICT,
+                ClientError::BOUNTY_NOT_OPEN {
+                    id: *id,
+                    status: status.clone(),
+                },
+            ),
+            Model(model::Error::BountyNotApproved { id, status }) => (
+                StatusCode::CONFLICT,
+                ClientError::BOUNTY_NOT_APPROVED {
+                    id: *id,
+                    status: status.clone(),
+                },
+            ),
+            Model(model::Error::BountyRewardFrozen { id, status }) => (
+                StatusCode::CONFLICT,
+                ClientError::BOUNTY_REWARD_FROZEN {
+                    id: *id,
+                    status: status.clone(),
+                },
+            ),
+            Model(model::Error::BountyChecklistFrozen { id, status }) => (
+                StatusCode::CONFLICT,
+                ClientError::BOUNTY_CHECKLIST_FROZEN {
+                    id: *id,
+                    status: status.clone(),
+                },
+            ),
+            Model(model::Error::NotBountyAssignee { bounty_id, .. }) => (
+                StatusCode::FORBIDDEN,
+                ClientError::NOT_BOUNTY_ASSIGNEE {
+                    bounty_id: *bounty_id,
+                },
+            ),
+            Model(model::Error::SubmissionChecklistMismatch { missing, unknown }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::SUBMISSION_CHECKLIST_MISMATCH {
+                    missing: missing.clone(),
+                    unknown: unknown.clone(),
+                },
+            ),
+            Model(model::Error::ReviewCommentRequired { id }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::REVIEW_COMMENT_REQUIRED { id: *id },
+            ),
+            Model(model::Error::NotPolicyReviewer { id, .. }) => (
+                StatusCode::FORBIDDEN,
+                ClientError::NOT_POLICY_REVIEWER { id: *id },
+            ),
+            Model(model::Error::SubmissionSelfReview { id, .. }) => (
+                StatusCode::FORBIDDEN,
+                ClientError::SUBMISSION_SELF_REVIEW { id: *id },
+            ),
+            Model(model::Error::LedgerInvalidAmount { value }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::LEDGER_INVALID_AMOUNT {
+                    value: value.to_string(),
+                },
+            ),
+            Model(model::Error::LedgerInsufficientFunds {
+                account,
+                balance,
+                amount,
+            }) => (
+                StatusCode::CONFLICT,
+                ClientError::LEDGER_INSUFFICIENT_FUNDS {
+                    account: account.to_string(),
+                    balance: *balance,
+                    amount: *amount,
+                },
+            ),
+            Model(model::Error::PayoutBountyWithoutProject { id }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::PAYOUT_BOUNTY_WITHOUT_PROJECT { id: *id },
+            ),
+            Model(model::Error::PayoutLeadMismatch { id, .. }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::PAYOUT_LEAD_MISMATCH { id: *id },
+            ),
+            Model(model::Error::PayoutInvalidTxHash { value }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::PAYOUT_INVALID_TX_HASH {
+                    value: value.clone(),
+                },
+            ),
+            Model(model::Error::PayoutBountyPending { id, payout_id }) => (
+                StatusCode::CONFLICT,
+                ClientError::PAYOUT_BOUNTY_PENDING {
+                    id: *id,
+                    payout_id: *payout_id,
+                },
+            ),
+            Model(model::Error::PayoutNotSigner { id, .. }) => (
+                StatusCode::FORBIDDEN,
+                ClientError::PAYOUT_NOT_SIGNER { id: *id },
+            ),
+            Model(model::Error::WalletProofInvalid { address, reason }) => (
+                StatusCode::FORBIDDEN,
+                ClientError::WALLET_PROOF_INVALID {
+                    address: address.clone(),
+                    reason,
+                },
+            ),
+            Model(model::Error::WebhookUrlNotAllowed { host, reason }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::WEBHOOK_URL_NOT_ALLOWED {
+                    host: host.clone(),
+                    reason,
+                },
+            ),
+            Model(model::Error::DenomUnknown { denom }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::DENOM_UNKNOWN {
+                    denom: denom.clone(),
+                },
+            ),
+            Model(model::Error::DenomDisabled { denom }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::DENOM_DISABLED {
+                    denom: denom.clone(),
+                },
+            ),
+            Model(model::Error::DenomMismatch { expected, actual }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::DENOM_MISMATCH {
+                    expected: expected.clone(),
+                    actual: actual.clone(),
+                },
+            ),
+            Model(model::Error::DenomInvalidAmount { value, .. }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::DENOM_INVALID_AMOUNT {
+                    value: value.clone(),
+                },
+            ),
+            Model(model::Error::UniqueViolation { entity, field }) => (
+                StatusCode::CONFLICT,
+                ClientError::UNIQUE_VIOLATION {
+                    entity: entity.clone(),
+                    field: field.clone(),
+                },
+            ),
+            Model(model::Error::ForeignKeyViolation { entity, constraint }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::FOREIGN_KEY_VIOLATION {
+                    entity: entity.clone(),
+                    constraint: constraint.clone(),
+                },
+            ),
+            Model(model::Error::CheckViolation { entity, constraint }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::CHECK_VIOLATION {
+                    entity: entity.clone(),
+                    constraint: constraint.clone(),
+                },
+            ),
+            Model(model::Error::NotNullViolation { entity, field }) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ClientError::NOT_NULL_VIOLATION {
+                    entity: entity.clone(),
+                    field: field.clone(),
+                },
+            ),
 
             // -- Fallback.
             _ => (
@@ -110,12 +386,189 @@ impl Error {
 
 #[derive(Debug, Serialize, strum_macros::AsRefStr)]
 #[serde(tag = "message", content = "detail")]
-#[allow(non_camel_case_types)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 pub enum ClientError {
     LOGIN_FAIL,
     NO_AUTH,
-    ENTITY_NOT_FOUND { entity: &'static str, id: i64 },
+    INVALID_REQUEST {
+        cause: String,
+    },
+    VALIDATION_FAILED {
+        fields: Vec<FieldError>,
+    },
+    ENTITY_NOT_FOUND {
+        entity: &'static str,
+        id: i64,
+    },
+    ENTITY_NOT_FOUND_BY {
+        entity: &'static str,
+        field: &'static str,
+        value: String,
+    },
+    CONFLICT {
+        entity: &'static str,
+        id: i64,
+        current_version: i64,
+    },
+    INVALID_TRANSITION {
+        entity: &'static str,
+        id: i64,
+        from: String,
+        to: String,
+    },
+    NOT_ADMIN,
+    NOT_PROJECT_LEAD {
+        project_id: Option<i64>,
+    },
+    PROJECT_DENOM_FROZEN {
+        id: i64,
+        denom: String,
+    },
+    BOUNTY_NOT_OPEN {
+        id: i64,
+        status: String,
+    },
+    BOUNTY_NOT_APPROVED {
+        id: i64,
+        status: String,
+    },
+    BOUNTY_REWARD_FROZEN {
+        id: i64,
+        status: String,
+    },
+    BOUNTY_CHECKLIST_FROZEN {
+        id: i64,
+        status: String,
+    },
+    NOT_BOUNTY_ASSIGNEE {
+        bounty_id: i64,
+    },
+    SUBMISSION_CHECKLIST_MISMATCH {
+        missing: Vec<i64>,
+        unknown: Vec<i64>,
+    },
+    REVIEW_COMMENT_REQUIRED {
+        id: i64,
+    },
+    NOT_POLICY_REVIEWER {
+        id: i64,
+    },
+    SUBMISSION_SELF_REVIEW {
+        id: i64,
+    },
+    PAYOUT_BOUNTY_WITHOUT_PROJECT {
+        id: i64,
+    },
+    PAYOUT_LEAD_MISMATCH {
+        id: i64,
+    },
+    PAYOUT_INVALID_TX_HASH {
+        value: String,
+    },
+    PAYOUT_BOUNTY_PENDING {
+        id: i64,
+        payout_id: i64,
+    },
+    PAYOUT_NOT_SIGNER {
+        id: i64,
+    },
+    LEDGER_INVALID_AMOUNT {
+        value: String,
+    },
+    LEDGER_INSUFFICIENT_FUNDS {
+        account: String,
+        balance: i64,
+        amount: i64,
+    },
+    WALLET_PROOF_INVALID {
+        address: String,
+        reason: &'static str,
+    },
+    WEBHOOK_URL_NOT_ALLOWED {
+        host: String,
+        reason: &'static str,
+    },
+    DENOM_UNKNOWN {
+        denom: String,
+    },
+    DENOM_DISABLED {
+        denom: String,
+    },
+    DENOM_MISMATCH {
+        expected: String,
+        actual: String,
+    },
+    DENOM_INVALID_AMOUNT {
+        value: String,
+    },
+    UNIQUE_VIOLATION {
+        entity: String,
+        field: String,
+    },
+    FOREIGN_KEY_VIOLATION {
+        entity: String,
+        constraint: String,
+    },
+    CHECK_VIOLATION {
+        entity: String,
+        constraint: String,
+    },
+    NOT_NULL_VIOLATION {
+        entity: String,
+        field: String,
+    },
 
     SERVICE_ERROR,
 }
+
+/// A single invalid field of a `*ForCreate` / `*ForUpdate` payload,
+/// in a shape the UI can display next to the matching form input.
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldError {
+    fn from_validation_errors(errors: &ValidationErrors) -> Vec<FieldError> {
+        let mut fields: Vec<FieldError> = errors
+            .field_errors()
+            .into_iter()
+            .flat_map(|(field, errs)| {
+                errs.iter().map(move |err| FieldError {
+                    field: field.to_string(),
+                    code: err.code.to_string(),
+                    message: err
+                        .message
+                        .as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| default_message(err)),
+                })
+            })
+            .collect();
+
+        // HashMap iteration order is random, keep the client output stable.
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+
+        fields
+    }
+}
+
+fn default_message(err: &validator::ValidationError) -> String {
+    let min = err.params.get("min");
+    let max = err.params.get("max");
+
+    match (err.code.as_ref(), min, max) {
+        ("length", Some(min), Some(max)) if min == 1 => {
+            format!("must not be empty and at most {max} characters")
+        }
+        ("length", Some(min), Some(max)) => {
+            format!("must be between {min} and {max} characters")
+        }
+        ("length", Some(min), None) => format!("must be at least {min} characters"),
+        ("length", None, Some(max)) => format!("must be at most {max} characters"),
+        (code, _, _) => format!("invalid ({code})"),
+    }
+}
 // endregion: --- Client Error
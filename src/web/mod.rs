@@ -3,7 +3,9 @@
 mod error;
 pub mod mw_auth;
 pub mod mw_res_map;
+pub mod routes_events;
 pub mod routes_login;
+pub mod routes_rest;
 pub mod routes_static;
 pub mod rpc;
 
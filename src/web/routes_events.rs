@@ -0,0 +1,93 @@
+//! Server-Sent Events route pushing the `ModelEvent`s to the clients.
+//!
+//! e.g., `GET /api/events?entity=bounty&id=1000`
+//! (all but the `notification` events)
+//!
+//! And the new notifications of the `Ctx` user (see `model::notification`),
+//! e.g., `GET /api/events/notifications`
+//!
+
+use crate::ctx::Ctx;
+use crate::model::event::{EventFilter, EventKind};
+use crate::model::notification::NotificationBmc;
+use crate::model::ModelManager;
+use axum::extract::{Query, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::get;
+use axum::Router;
+use std::convert::Infallible;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+use tracing::debug;
+
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/events", get(events_handler))
+        .route("/events/notifications", get(notifications_handler))
+        .with_state(mm)
+}
+
+async fn events_handler(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Query(filter): Query<EventFilter>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    debug!(
+        "{:<12} - events_handler - user_id: {} - {filter:?}",
+        "HANDLER",
+        ctx.user_id()
+    );
+
+    // Note: Lagged receivers just skip the missed events.
+    // (the notifications being private, they are only pushed to their user,
+    //  by `notifications_handler`)
+    let stream = BroadcastStream::new(mm.events().subscribe()).filter_map(move |event| {
+        let event = event
+            .ok()
+            .filter(|event| event.entity != "notification" && filter.matches(event))?;
+        Event::default()
+            .event(event.kind.as_ref())
+            .json_data(&event)
+            .ok()
+            .map(Ok)
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+async fn notifications_handler(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    debug!(
+        "{:<12} - notifications_handler - user_id: {}",
+        "HANDLER",
+        ctx.user_id()
+    );
+
+    // (filtered on the event user before fetching, `NotificationBmc::get`
+    //  only getting the notifications of the `Ctx` user anyway)
+    let user_id = ctx.user_id();
+    let stream = BroadcastStream::new(mm.events().subscribe())
+        .filter_map(move |event| {
+            event.ok().filter(|event| {
+                event.entity == "notification"
+                    && matches!(event.kind, EventKind::Created)
+                    && event.user_id == Some(user_id)
+            })
+        })
+        .then(move |event| {
+            let (ctx, mm) = (ctx.clone(), mm.clone());
+            async move { NotificationBmc::get(&ctx, &mm, event.id).await.ok() }
+        })
+        .filter_map(|notification| {
+            let notification = notification?;
+            Event::default()
+                .event(notification.notification_kind.as_ref())
+                .json_data(&notification)
+                .ok()
+                .map(Ok)
+        });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
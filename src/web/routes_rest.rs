@@ -0,0 +1,1467 @@
+//! RESTful resource routes, mirroring the JSON-RPC methods of `web::rpc`.
+//!
+//! Handlers go through the same `*Bmc` layer and `Ctx`, so behavior
+//! (validation, errors, client error mapping) matches the RPC exactly.
+//!
+
+use crate::ctx::Ctx;
+use crate::model::application::{
+    Application, ApplicationBmc, ApplicationFilter, ApplicationForCreate,
+};
+use crate::model::approval::{ApprovalBmc, ApprovalPolicy};
+use crate::model::bounty::{Bounty, BountyBmc, BountyFilter, BountyForCreate, BountyForUpdate};
+use crate::model::deliverable::{Deliverable, DeliverableBmc, DeliverableForCreate};
+use crate::model::denom::{
+    Denom, DenomAmount, DenomBmc, DenomFilter, DenomForCreate, DenomForUpdate,
+};
+use crate::model::job::{Job, JobBmc, JobFilter};
+use crate::model::ledger::{AccountBalance, LedgerBmc, LedgerEntry, ProjectFunding};
+use crate::model::notification::{
+    Notification, NotificationBmc, NotificationCount, NotificationFilter, NotificationPrefs,
+    NotificationPrefsBmc, NotificationPrefsForUpdate,
+};
+use crate::model::payout::{PayoutBmc, PayoutForBuild, PayoutForConfirm, PayoutWithBounties};
+use crate::model::project::{
+    Project, ProjectBmc, ProjectFilter, ProjectForCreate, ProjectForUpdate,
+};
+use crate::model::search::{self, SearchFilter, SearchHit};
+use crate::model::submission::{
+    Submission, SubmissionBmc, SubmissionForCreate, SubmissionForReview, SubmissionWithItems,
+};
+use crate::model::tag::{Tag, TagBmc, TagForCreate, TagTarget, TagWithCount};
+use crate::model::user::{User, UserBmc};
+use crate::model::wallet::{Wallet, WalletBmc, WalletChallenge, WalletForChallenge, WalletForLink};
+use crate::model::webhook::{
+    Webhook, WebhookBmc, WebhookDelivery, WebhookDeliveryBmc, WebhookEvent, WebhookFilter,
+    WebhookForCreate, WebhookForUpdate,
+};
+use crate::model::{ListOptions, ModelManager};
+use crate::web::{Error, Result};
+use crate::worker::handlers::SearchReindex;
+use crate::worker::webhook;
+use async_trait::async_trait;
+use axum::body::Body;
+use axum::extract::{FromRequest, FromRequestParts, State};
+use axum::http::request::Parts;
+use axum::http::{header, Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post, put};
+use axum::Router;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use validator::Validate;
+
+pub fn routes(mm: ModelManager) -> Router {
+    Router::new()
+        .route("/projects", get(list_projects).post(create_project))
+        .route(
+            "/projects/:id",
+            get(get_project)
+                .patch(update_project)
+                .delete(delete_project),
+        )
+        .route("/bounties", get(list_bounties).post(create_bounty))
+        .route(
+            "/bounties/:id",
+            get(get_bounty).patch(update_bounty).delete(delete_bounty),
+        )
+        .route("/bounties/:id/applications", get(list_bounty_applications))
+        .route("/applications", post(create_application))
+        .route("/applications/:id", get(get_application))
+        .route("/applications/:id/accept", post(accept_application))
+        .route("/applications/:id/reject", post(reject_application))
+        .route(
+            "/projects/:id/approval-policy",
+            get(get_approval_policy).put(set_approval_policy),
+        )
+        .route("/denoms", get(list_denoms).post(create_denom))
+        .route("/denoms/convert", get(convert_amount))
+        .route("/denoms/:id", get(get_denom).patch(update_denom))
+        .route(
+            "/projects/:id/ledger",
+            get(list_ledger_entries).post(fund_project),
+        )
+        .route("/projects/:id/balances", get(get_project_balances))
+        .route(
+            "/contributors/:address/balances",
+            get(get_contributor_balances),
+        )
+        .route("/payouts", post(build_payout))
+        .route("/payouts/:id", get(get_payout))
+        .route("/payouts/:id/confirm", post(confirm_payout))
+        .route("/payouts/:id/cancel", post(cancel_payout))
+        .route("/jobs", get(list_jobs))
+        .route("/jobs/:id", get(get_job))
+        .route("/jobs/:id/retry", post(retry_job))
+        .route("/jobs/:id/cancel", post(cancel_job))
+        .route("/search/reindex", post(reindex_search))
+        .route("/bounties/:id/deliverables", get(list_bounty_deliverables))
+        .route("/deliverables", post(create_deliverable))
+        .route("/deliverables/:id", delete(delete_deliverable))
+        .route("/bounties/:id/submissions", get(list_bounty_submissions))
+        .route("/submissions", post(create_submission))
+        .route("/submissions/:id", get(get_submission))
+        .route("/submissions/:id/review", post(review_submission))
+        .route("/projects/:id/tags", get(get_project_tags))
+        .route(
+            "/projects/:id/tags/:tag",
+            put(add_project_tag).delete(remove_project_tag),
+        )
+        .route("/bounties/:id/tags", get(get_bounty_tags))
+        .route(
+            "/bounties/:id/tags/:tag",
+            put(add_bounty_tag).delete(remove_bounty_tag),
+        )
+        .route("/tags", get(list_tags))
+        .route("/search", get(search))
+        .route("/users/:id", get(get_user))
+        .route("/users/username/:username", get(get_user_by_username))
+        .route("/users/:id/wallets", get(list_user_wallets))
+        .route("/wallets", post(link_wallet))
+        .route("/wallets/challenge", post(get_wallet_challenge))
+        .route("/wallets/:id", delete(unlink_wallet))
+        .route("/notifications", get(list_notifications))
+        .route("/notifications/unread-count", get(get_unread_count))
+        .route("/notifications/read", post(mark_notifications_read))
+        .route("/notifications/read-all", post(mark_all_notifications_read))
+        .route(
+            "/notifications/prefs",
+            get(get_notification_prefs).patch(set_notification_prefs),
+        )
+        .route("/webhooks", get(list_webhooks).post(create_webhook))
+        .route(
+            "/webhooks/:id",
+            get(get_webhook)
+                .patch(update_webhook)
+                .delete(delete_webhook),
+        )
+        .route("/webhooks/:id/deliveries", get(list_webhook_deliveries))
+        .route("/webhooks/:id/test", post(test_webhook))
+        .with_state(mm)
+}
+
+// region:    --- Extractors
+// The axum extractors, with their rejections (e.g., malformed json or id)
+// as `web::Error`, for the same client error body as the other errors.
+
+struct Json<T>(T);
+
+#[async_trait]
+impl<S, T> FromRequest<S, Body> for Json<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = Error;
+
+    async fn from_request(req: Request<Body>, state: &S) -> Result<Self> {
+        let axum::Json(value) = axum::Json::<T>::from_request(req, state).await?;
+
+        Ok(Json(value))
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+struct Path<T>(T);
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for Path<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Send,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self> {
+        let axum::extract::Path(value) =
+            axum::extract::Path::<T>::from_request_parts(parts, state).await?;
+
+        Ok(Path(value))
+    }
+}
+
+struct Query<T>(T);
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for Query<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self> {
+        let axum::extract::Query(value) =
+            axum::extract::Query::<T>::from_request_parts(parts, state).await?;
+
+        Ok(Query(value))
+    }
+}
+// endregion: --- Extractors
+
+/// Response for a newly created resource,
+/// with the `Location` header pointing to it.
+fn created<T: serde::Serialize>(location: String, entity: T) -> impl IntoResponse {
+    (
+        StatusCode::CREATED,
+        [(header::LOCATION, location)],
+        Json(entity),
+    )
+}
+
+// region:    --- Projects
+async fn list_projects(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Query(filter): Query<ProjectFilter>,
+    Query(list_options): Query<ListOptions>,
+) -> Result<Json<Vec<Project>>> {
+    debug!("{:<12} - list_projects", "HANDLER");
+
+    let projects = ProjectBmc::list(&ctx, &mm, Some(filter), Some(list_options)).await?;
+
+    Ok(Json(projects))
+}
+
+async fn create_project(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Json(data): Json<ProjectForCreate>,
+) -> Result<impl IntoResponse> {
+    debug!("{:<12} - create_project", "HANDLER");
+    data.validate()?;
+
+    let id = ProjectBmc::create(&ctx, &mm, data).await?;
+    let project = ProjectBmc::get(&ctx, &mm, id).await?;
+
+    Ok(created(format!("/api/projects/{id}"), project))
+}
+
+async fn get_project(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Project>> {
+    debug!("{:<12} - get_project", "HANDLER");
+
+    let project = ProjectBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(project))
+}
+
+async fn update_project(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+    Json(data): Json<ProjectForUpdate>,
+) -> Result<Json<Project>> {
+    debug!("{:<12} - update_project", "HANDLER");
+    data.validate()?;
+
+    ProjectBmc::update(&ctx, &mm, id, data).await?;
+    let project = ProjectBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(project))
+}
+
+async fn delete_project(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    debug!("{:<12} - delete_project", "HANDLER");
+
+    ProjectBmc::delete(&ctx, &mm, id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+// endregion: --- Projects
+
+// region:    --- Bounties
+async fn list_bounties(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Query(filter): Query<BountyFilter>,
+    Query(list_options): Query<ListOptions>,
+) -> Result<Json<Vec<Bounty>>> {
+    debug!("{:<12} - list_bounties", "HANDLER");
+
+    let bounties = BountyBmc::list(&ctx, &mm, Some(filter), Some(list_options)).await?;
+
+    Ok(Json(bounties))
+}
+
+async fn create_bounty(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Json(data): Json<BountyForCreate>,
+) -> Result<impl IntoResponse> {
+    debug!("{:<12} - create_bounty", "HANDLER");
+    data.validate()?;
+
+    let id = BountyBmc::create(&ctx, &mm, data).await?;
+    let bounty = BountyBmc::get(&ctx, &mm, id).await?;
+
+    Ok(created(format!("/api/bounties/{id}"), bounty))
+}
+
+async fn get_bounty(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Bounty>> {
+    debug!("{:<12} - get_bounty", "HANDLER");
+
+    let bounty = BountyBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(bounty))
+}
+
+async fn update_bounty(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+    Json(data): Json<BountyForUpdate>,
+) -> Result<Json<Bounty>> {
+    debug!("{:<12} - update_bounty", "HANDLER");
+    data.validate()?;
+
+    BountyBmc::update(&ctx, &mm, id, data).await?;
+    let bounty = BountyBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(bounty))
+}
+
+async fn delete_bounty(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    debug!("{:<12} - delete_bounty", "HANDLER");
+
+    BountyBmc::delete(&ctx, &mm, id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+// endregion: --- Bounties
+
+// region:    --- Applications
+async fn list_bounty_applications(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+    Query(list_options): Query<ListOptions>,
+) -> Result<Json<Vec<Application>>> {
+    debug!("{:<12} - list_bounty_applications", "HANDLER");
+
+    let filter = ApplicationFilter {
+        application_bounty_id: Some(id),
+        ..Default::default()
+    };
+    let applications = ApplicationBmc::list(&ctx, &mm, Some(filter), Some(list_options)).await?;
+
+    Ok(Json(applications))
+}
+
+async fn create_application(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Json(data): Json<ApplicationForCreate>,
+) -> Result<impl IntoResponse> {
+    debug!("{:<12} - create_application", "HANDLER");
+    data.validate()?;
+
+    let id = ApplicationBmc::create(&ctx, &mm, data).await?;
+    let application = ApplicationBmc::get(&ctx, &mm, id).await?;
+
+    Ok(created(format!("/api/applications/{id}"), application))
+}
+
+async fn get_application(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Application>> {
+    debug!("{:<12} - get_application", "HANDLER");
+
+    let application = ApplicationBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(application))
+}
+
+async fn accept_application(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Application>> {
+    debug!("{:<12} - accept_application", "HANDLER");
+
+    ApplicationBmc::accept(&ctx, &mm, id).await?;
+    let application = ApplicationBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(application))
+}
+
+async fn reject_application(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Application>> {
+    debug!("{:<12} - reject_application", "HANDLER");
+
+    ApplicationBmc::reject(&ctx, &mm, id).await?;
+    let application = ApplicationBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(application))
+}
+// endregion: --- Applications
+
+// region:    --- Approval Policies
+async fn get_approval_policy(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<ApprovalPolicy>> {
+    debug!("{:<12} - get_approval_policy", "HANDLER");
+
+    let policy = ApprovalBmc::get_policy(&ctx, &mm, id).await?;
+
+    Ok(Json(policy))
+}
+
+async fn set_approval_policy(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+    Json(data): Json<Option<ApprovalPolicy>>,
+) -> Result<Json<ApprovalPolicy>> {
+    debug!("{:<12} - set_approval_policy", "HANDLER");
+    if let Some(policy) = &data {
+        policy.validate()?;
+    }
+
+    ApprovalBmc::set_policy(&ctx, &mm, id, data).await?;
+    let policy = ApprovalBmc::get_policy(&ctx, &mm, id).await?;
+
+    Ok(Json(policy))
+}
+// endregion: --- Approval Policies
+
+// region:    --- Denoms
+async fn list_denoms(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Query(filter): Query<DenomFilter>,
+    Query(list_options): Query<ListOptions>,
+) -> Result<Json<Vec<Denom>>> {
+    debug!("{:<12} - list_denoms", "HANDLER");
+
+    let denoms = DenomBmc::list(&ctx, &mm, Some(filter), Some(list_options)).await?;
+
+    Ok(Json(denoms))
+}
+
+async fn create_denom(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Json(data): Json<DenomForCreate>,
+) -> Result<impl IntoResponse> {
+    debug!("{:<12} - create_denom", "HANDLER");
+    data.validate()?;
+
+    let id = DenomBmc::create(&ctx, &mm, data).await?;
+    let denom = DenomBmc::get(&ctx, &mm, id).await?;
+
+    Ok(created(format!("/api/denoms/{id}"), denom))
+}
+
+async fn get_denom(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Denom>> {
+    debug!("{:<12} - get_denom", "HANDLER");
+
+    let denom = DenomBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(denom))
+}
+
+async fn update_denom(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+    Json(data): Json<DenomForUpdate>,
+) -> Result<Json<Denom>> {
+    debug!("{:<12} - update_denom", "HANDLER");
+    data.validate()?;
+
+    DenomBmc::update(&ctx, &mm, id, data).await?;
+    let denom = DenomBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(denom))
+}
+
+#[derive(Deserialize)]
+struct AmountQuery {
+    amount: String,
+}
+
+async fn convert_amount(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Query(AmountQuery { amount }): Query<AmountQuery>,
+) -> Result<Json<DenomAmount>> {
+    debug!("{:<12} - convert_amount", "HANDLER");
+
+    let amount = DenomBmc::convert(&ctx, &mm, &amount).await?;
+
+    Ok(Json(amount))
+}
+// endregion: --- Denoms
+
+// region:    --- Ledger
+async fn list_ledger_entries(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<LedgerEntry>>> {
+    debug!("{:<12} - list_ledger_entries", "HANDLER");
+
+    let entries = LedgerBmc::list_entries(&ctx, &mm, id).await?;
+
+    Ok(Json(entries))
+}
+
+async fn fund_project(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+    Json(data): Json<ProjectFunding>,
+) -> Result<Json<Vec<AccountBalance>>> {
+    debug!("{:<12} - fund_project", "HANDLER");
+    data.validate()?;
+
+    LedgerBmc::fund_project(&ctx, &mm, id, data).await?;
+    let balances = LedgerBmc::project_balances(&ctx, &mm, id).await?;
+
+    Ok(Json(balances))
+}
+
+async fn get_project_balances(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<AccountBalance>>> {
+    debug!("{:<12} - get_project_balances", "HANDLER");
+
+    let balances = LedgerBmc::project_balances(&ctx, &mm, id).await?;
+
+    Ok(Json(balances))
+}
+
+async fn get_contributor_balances(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(address): Path<String>,
+) -> Result<Json<Vec<AccountBalance>>> {
+    debug!("{:<12} - get_contributor_balances", "HANDLER");
+
+    let balances = LedgerBmc::contributor_balances(&ctx, &mm, &address).await?;
+
+    Ok(Json(balances))
+}
+// endregion: --- Ledger
+
+// region:    --- Jobs
+async fn list_jobs(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Query(filter): Query<JobFilter>,
+    Query(list_options): Query<ListOptions>,
+) -> Result<Json<Vec<Job>>> {
+    debug!("{:<12} - list_jobs", "HANDLER");
+
+    let jobs = JobBmc::list(&ctx, &mm, Some(filter), Some(list_options)).await?;
+
+    Ok(Json(jobs))
+}
+
+async fn get_job(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Job>> {
+    debug!("{:<12} - get_job", "HANDLER");
+
+    let job = JobBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(job))
+}
+
+async fn retry_job(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Job>> {
+    debug!("{:<12} - retry_job", "HANDLER");
+
+    JobBmc::retry(&ctx, &mm, id).await?;
+    let job = JobBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(job))
+}
+
+async fn cancel_job(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Job>> {
+    debug!("{:<12} - cancel_job", "HANDLER");
+
+    JobBmc::cancel(&ctx, &mm, id).await?;
+    let job = JobBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(job))
+}
+
+async fn reindex_search(State(mm): State<ModelManager>, ctx: Ctx) -> Result<impl IntoResponse> {
+    debug!("{:<12} - reindex_search", "HANDLER");
+    UserBmc::check_admin(&ctx, &mm).await?;
+
+    let id = JobBmc::enqueue(&ctx, &mm, &SearchReindex {}).await?;
+    let job = JobBmc::get(&ctx, &mm, id).await?;
+
+    Ok(created(format!("/api/jobs/{id}"), job))
+}
+// endregion: --- Jobs
+
+// region:    --- Payouts
+async fn build_payout(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Json(data): Json<PayoutForBuild>,
+) -> Result<impl IntoResponse> {
+    debug!("{:<12} - build_payout", "HANDLER");
+    data.validate()?;
+
+    let payout = PayoutBmc::build(&ctx, &mm, data).await?;
+
+    Ok(created(
+        format!("/api/payouts/{}", payout.payout.id),
+        payout,
+    ))
+}
+
+async fn get_payout(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<PayoutWithBounties>> {
+    debug!("{:<12} - get_payout", "HANDLER");
+
+    let payout = PayoutBmc::get_with_bounties(&ctx, &mm, id).await?;
+
+    Ok(Json(payout))
+}
+
+async fn confirm_payout(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+    Json(data): Json<PayoutForConfirm>,
+) -> Result<Json<PayoutWithBounties>> {
+    debug!("{:<12} - confirm_payout", "HANDLER");
+    data.validate()?;
+
+    PayoutBmc::confirm(&ctx, &mm, id, &data.tx_hash).await?;
+    let payout = PayoutBmc::get_with_bounties(&ctx, &mm, id).await?;
+
+    Ok(Json(payout))
+}
+
+async fn cancel_payout(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<PayoutWithBounties>> {
+    debug!("{:<12} - cancel_payout", "HANDLER");
+
+    PayoutBmc::cancel(&ctx, &mm, id).await?;
+    let payout = PayoutBmc::get_with_bounties(&ctx, &mm, id).await?;
+
+    Ok(Json(payout))
+}
+// endregion: --- Payouts
+
+// region:    --- Submissions
+async fn list_bounty_deliverables(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<Deliverable>>> {
+    debug!("{:<12} - list_bounty_deliverables", "HANDLER");
+
+    let deliverables = DeliverableBmc::list_for_bounty(&ctx, &mm, id).await?;
+
+    Ok(Json(deliverables))
+}
+
+async fn create_deliverable(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Json(data): Json<DeliverableForCreate>,
+) -> Result<impl IntoResponse> {
+    debug!("{:<12} - create_deliverable", "HANDLER");
+    data.validate()?;
+
+    let id = DeliverableBmc::create(&ctx, &mm, data).await?;
+    let deliverable = DeliverableBmc::get(&ctx, &mm, id).await?;
+
+    Ok(created(format!("/api/deliverables/{id}"), deliverable))
+}
+
+async fn delete_deliverable(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<StatusCode> {
+    debug!("{:<12} - delete_deliverable", "HANDLER");
+
+    DeliverableBmc::delete(&ctx, &mm, id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_bounty_submissions(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<Submission>>> {
+    debug!("{:<12} - list_bounty_submissions", "HANDLER");
+
+    let submissions = SubmissionBmc::list_for_bounty(&ctx, &mm, id).await?;
+
+    Ok(Json(submissions))
+}
+
+async fn create_submission(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Json(data): Json<SubmissionForCreate>,
+) -> Result<impl IntoResponse> {
+    debug!("{:<12} - create_submission", "HANDLER");
+    data.validate()?;
+
+    let id = SubmissionBmc::create(&ctx, &mm, data).await?;
+    let submission = SubmissionBmc::get_with_items(&ctx, &mm, id).await?;
+
+    Ok(created(format!("/api/submissions/{id}"), submission))
+}
+
+async fn get_submission(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<SubmissionWithItems>> {
+    debug!("{:<12} - get_submission", "HANDLER");
+
+    let submission = SubmissionBmc::get_with_items(&ctx, &mm, id).await?;
+
+    Ok(Json(submission))
+}
+
+async fn review_submission(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+    Json(data): Json<SubmissionForReview>,
+) -> Result<Json<Submission>> {
+    debug!("{:<12} - review_submission", "HANDLER");
+    data.validate()?;
+
+    SubmissionBmc::review(&ctx, &mm, id, data).await?;
+    let submission = SubmissionBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(submission))
+}
+// endregion: --- Submissions
+
+// region:    --- Tags
+async fn list_tags(State(mm): State<ModelManager>, ctx: Ctx) -> Result<Json<Vec<TagWithCount>>> {
+    debug!("{:<12} - list_tags", "HANDLER");
+
+    let tags = TagBmc::list_with_counts(&ctx, &mm).await?;
+
+    Ok(Json(tags))
+}
+
+async fn get_project_tags(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<Tag>>> {
+    debug!("{:<12} - get_project_tags", "HANDLER");
+
+    let tags = TagBmc::list_for(&ctx, &mm, TagTarget::Project, id).await?;
+
+    Ok(Json(tags))
+}
+
+async fn add_project_tag(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path((id, tag)): Path<(i64, String)>,
+) -> Result<Json<Vec<Tag>>> {
+    debug!("{:<12} - add_project_tag", "HANDLER");
+
+    add_tag(&ctx, &mm, TagTarget::Project, id, &tag).await
+}
+
+async fn remove_project_tag(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path((id, tag)): Path<(i64, String)>,
+) -> Result<Json<Vec<Tag>>> {
+    debug!("{:<12} - remove_project_tag", "HANDLER");
+
+    let tags = TagBmc::remove_from(&ctx, &mm, TagTarget::Project, id, &tag).await?;
+
+    Ok(Json(tags))
+}
+
+async fn get_bounty_tags(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<Tag>>> {
+    debug!("{:<12} - get_bounty_tags", "HANDLER");
+
+    let tags = TagBmc::list_for(&ctx, &mm, TagTarget::Bounty, id).await?;
+
+    Ok(Json(tags))
+}
+
+async fn add_bounty_tag(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path((id, tag)): Path<(i64, String)>,
+) -> Result<Json<Vec<Tag>>> {
+    debug!("{:<12} - add_bounty_tag", "HANDLER");
+
+    add_tag(&ctx, &mm, TagTarget::Bounty, id, &tag).await
+}
+
+async fn remove_bounty_tag(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path((id, tag)): Path<(i64, String)>,
+) -> Result<Json<Vec<Tag>>> {
+    debug!("{:<12} - remove_bounty_tag", "HANDLER");
+
+    let tags = TagBmc::remove_from(&ctx, &mm, TagTarget::Bounty, id, &tag).await?;
+
+    Ok(Json(tags))
+}
+
+async fn add_tag(
+    ctx: &Ctx,
+    mm: &ModelManager,
+    target: TagTarget,
+    id: i64,
+    tag: &str,
+) -> Result<Json<Vec<Tag>>> {
+    let tag_c = TagForCreate::new(tag);
+    tag_c.validate()?;
+
+    let tags = TagBmc::add_to(ctx, mm, target, id, tag_c).await?;
+
+    Ok(Json(tags))
+}
+// endregion: --- Tags
+
+// region:    --- Search
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: String,
+}
+
+async fn search(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Query(SearchQuery { q }): Query<SearchQuery>,
+    Query(filter): Query<SearchFilter>,
+    Query(list_options): Query<ListOptions>,
+) -> Result<Json<Vec<SearchHit>>> {
+    debug!("{:<12} - search", "HANDLER");
+
+    let hits = search::search(&ctx, &mm, &q, Some(filter), Some(list_options)).await?;
+
+    Ok(Json(hits))
+}
+// endregion: --- Search
+
+// region:    --- Users
+async fn get_user(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<User>> {
+    debug!("{:<12} - get_user", "HANDLER");
+
+    let user: User = UserBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(user))
+}
+
+async fn get_user_by_username(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(username): Path<String>,
+) -> Result<Json<User>> {
+    debug!("{:<12} - get_user_by_username", "HANDLER");
+
+    let user: User = UserBmc::get_by_username(&ctx, &mm, &username).await?;
+
+    Ok(Json(user))
+}
+// endregion: --- Users
+
+// region:    --- Wallets
+async fn list_user_wallets(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<Wallet>>> {
+    debug!("{:<12} - list_user_wallets", "HANDLER");
+
+    let wallets = WalletBmc::list_for_user(&ctx, &mm, id).await?;
+
+    Ok(Json(wallets))
+}
+
+async fn get_wallet_challenge(
+    ctx: Ctx,
+    Json(data): Json<WalletForChallenge>,
+) -> Result<Json<WalletChallenge>> {
+    debug!("{:<12} - get_wallet_challenge", "HANDLER");
+    data.validate()?;
+
+    let challenge = WalletBmc::challenge(&ctx, data)?;
+
+    Ok(Json(challenge))
+}
+
+async fn link_wallet(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Json(data): Json<WalletForLink>,
+) -> Result<impl IntoResponse> {
+    debug!("{:<12} - link_wallet", "HANDLER");
+    data.validate()?;
+
+    let id = WalletBmc::link(&ctx, &mm, data).await?;
+    let wallet = WalletBmc::get(&ctx, &mm, id).await?;
+
+    Ok(created(format!("/api/wallets/{id}"), wallet))
+}
+
+async fn unlink_wallet(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Wallet>> {
+    debug!("{:<12} - unlink_wallet", "HANDLER");
+
+    let wallet = WalletBmc::get(&ctx, &mm, id).await?;
+    WalletBmc::unlink(&ctx, &mm, id).await?;
+
+    Ok(Json(wallet))
+}
+// endregion: --- Wallets
+
+// region:    --- Notifications
+#[derive(Deserialize)]
+struct NotificationIds {
+    ids: Vec<i64>,
+}
+
+async fn list_notifications(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Query(filter): Query<NotificationFilter>,
+    Query(list_options): Query<ListOptions>,
+) -> Result<Json<Vec<Notification>>> {
+    debug!("{:<12} - list_notifications", "HANDLER");
+
+    let notifications = NotificationBmc::list(&ctx, &mm, Some(filter), Some(list_options)).await?;
+
+    Ok(Json(notifications))
+}
+
+async fn get_unread_count(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+) -> Result<Json<NotificationCount>> {
+    debug!("{:<12} - get_unread_count", "HANDLER");
+
+    let count = NotificationBmc::unread_count(&ctx, &mm).await?;
+
+    Ok(Json(count))
+}
+
+async fn mark_notifications_read(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Json(data): Json<NotificationIds>,
+) -> Result<Json<NotificationCount>> {
+    debug!("{:<12} - mark_notifications_read", "HANDLER");
+
+    NotificationBmc::mark_read(&ctx, &mm, &data.ids).await?;
+    let count = NotificationBmc::unread_count(&ctx, &mm).await?;
+
+    Ok(Json(count))
+}
+
+async fn mark_all_notifications_read(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+) -> Result<Json<NotificationCount>> {
+    debug!("{:<12} - mark_all_notifications_read", "HANDLER");
+
+    NotificationBmc::mark_all_read(&ctx, &mm).await?;
+    let count = NotificationBmc::unread_count(&ctx, &mm).await?;
+
+    Ok(Json(count))
+}
+
+async fn get_notification_prefs(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+) -> Result<Json<NotificationPrefs>> {
+    debug!("{:<12} - get_notification_prefs", "HANDLER");
+
+    let prefs = NotificationPrefsBmc::get_for_user(&ctx, &mm, ctx.user_id()).await?;
+
+    Ok(Json(prefs))
+}
+
+async fn set_notification_prefs(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Json(data): Json<NotificationPrefsForUpdate>,
+) -> Result<Json<NotificationPrefs>> {
+    debug!("{:<12} - set_notification_prefs", "HANDLER");
+
+    NotificationPrefsBmc::set(&ctx, &mm, data).await?;
+    let prefs = NotificationPrefsBmc::get_for_user(&ctx, &mm, ctx.user_id()).await?;
+
+    Ok(Json(prefs))
+}
+// endregion: --- Notifications
+
+// region:    --- Webhooks
+async fn list_webhooks(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Query(filter): Query<WebhookFilter>,
+    Query(list_options): Query<ListOptions>,
+) -> Result<Json<Vec<Webhook>>> {
+    debug!("{:<12} - list_webhooks", "HANDLER");
+
+    let webhooks = WebhookBmc::list(&ctx, &mm, Some(filter), Some(list_options)).await?;
+
+    Ok(Json(webhooks))
+}
+
+async fn create_webhook(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Json(data): Json<WebhookForCreate>,
+) -> Result<impl IntoResponse> {
+    debug!("{:<12} - create_webhook", "HANDLER");
+    data.validate()?;
+
+    let id = WebhookBmc::create(&ctx, &mm, data).await?;
+    let webhook = WebhookBmc::get(&ctx, &mm, id).await?;
+
+    Ok(created(format!("/api/webhooks/{id}"), webhook))
+}
+
+async fn get_webhook(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Webhook>> {
+    debug!("{:<12} - get_webhook", "HANDLER");
+
+    let webhook = WebhookBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(webhook))
+}
+
+async fn update_webhook(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+    Json(data): Json<WebhookForUpdate>,
+) -> Result<Json<Webhook>> {
+    debug!("{:<12} - update_webhook", "HANDLER");
+    data.validate()?;
+
+    WebhookBmc::update(&ctx, &mm, id, data).await?;
+    let webhook = WebhookBmc::get(&ctx, &mm, id).await?;
+
+    Ok(Json(webhook))
+}
+
+async fn delete_webhook(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Webhook>> {
+    debug!("{:<12} - delete_webhook", "HANDLER");
+
+    let webhook = WebhookBmc::get(&ctx, &mm, id).await?;
+    WebhookBmc::delete(&ctx, &mm, id).await?;
+
+    Ok(Json(webhook))
+}
+
+async fn list_webhook_deliveries(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<Vec<WebhookDelivery>>> {
+    debug!("{:<12} - list_webhook_deliveries", "HANDLER");
+
+    WebhookBmc::get(&ctx, &mm, id).await?;
+    let deliveries = WebhookDeliveryBmc::list_for_webhook(&ctx, &mm, id, None, None).await?;
+
+    Ok(Json(deliveries))
+}
+
+async fn test_webhook(
+    State(mm): State<ModelManager>,
+    ctx: Ctx,
+    Path(id): Path<i64>,
+) -> Result<Json<WebhookDelivery>> {
+    debug!("{:<12} - test_webhook", "HANDLER");
+
+    let webhook = WebhookBmc::get(&ctx, &mm, id).await?;
+    let event = WebhookEvent::sample(webhook.webhook_project_id);
+    let delivery = webhook::deliver(&ctx, &mm, &webhook, &event).await?;
+
+    Ok(Json(delivery))
+}
+// endregion: --- Webhooks
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::{Seed, Seeded};
+    use crate::web::mw_auth::CtxExtError;
+    use crate::web::mw_res_map::mw_reponse_map;
+    use anyhow::{Context, Result};
+    use axum::middleware::{self, Next};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    const SEED_TOML: &str = r#"
+[[users]]
+username = "alice"
+pwd_clear = "alice pwd 01"
+
+[[projects]]
+project_id = "rest-project-01"
+project_name = "Rest Project 01"
+project_lead_address = "cosmos1udzkhs056fc0f2texd6cv30acg0rjept43xsph"
+project_budget = "1000"
+project_denom = "uatom"
+project_type = "dev"
+project_description = "A project over rest"
+
+[[projects]]
+project_id = "rest-project-02"
+project_name = "Rest Project 02"
+project_lead_address = "cosmos1udzkhs056fc0f2texd6cv30acg0rjept43xsph"
+project_budget = "1000"
+project_denom = "uatom"
+project_type = "dev"
+project_description = "Another project over rest"
+
+[[bounties]]
+key = "b1"
+project = "rest-project-01"
+bounty_title = "Rest bounty 01"
+bounty_assignee = ""
+bounty_reward = "100"
+bounty_deliverables = "A PR"
+bounty_description = "A bounty over rest"
+"#;
+
+    /// The rest routes with the response mapper, as the `ctx` user
+    /// (in place of the `mw_ctx_resolve` token cookie).
+    fn app(mm: ModelManager, ctx: Ctx) -> Router {
+        routes(mm)
+            .layer(middleware::map_response(mw_reponse_map))
+            .layer(middleware::from_fn(
+                move |mut req: Request<Body>, next: Next<Body>| {
+                    req.extensions_mut()
+                        .insert(Ok::<Ctx, CtxExtError>(ctx.clone()));
+                    next.run(req)
+                },
+            ))
+    }
+
+    async fn send(app: &Router, req: Request<Body>) -> Result<(StatusCode, Value)> {
+        let res = app.clone().oneshot(req).await?;
+        let status = res.status();
+        let body = hyper::body::to_bytes(res.into_body()).await?;
+
+        Ok((status, serde_json::from_slice(&body)?))
+    }
+
+    fn get_req(uri: &str) -> Result<Request<Body>> {
+        Ok(Request::get(uri).body(Body::empty())?)
+    }
+
+    fn post_req(uri: &str, body: &str) -> Result<Request<Body>> {
+        Ok(Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))?)
+    }
+
+    fn patch_req(uri: &str, body: &str) -> Result<Request<Body>> {
+        Ok(Request::patch(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))?)
+    }
+
+    async fn init_app() -> Result<(Router, Seeded)> {
+        let mm = _dev_utils::init_test().await;
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?
+            .apply(&Ctx::root_ctx(), &mm)
+            .await?;
+        let ctx = Ctx::new(*seeded.users.get("alice").context("alice")?)?;
+
+        Ok((app(mm, ctx), seeded))
+    }
+
+    #[tokio::test]
+    async fn test_list_projects_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let (app, _seeded) = init_app().await?;
+
+        // -- Exec
+        let (status, all) = send(&app, get_req("/projects")?).await?;
+        let (_, page) = send(&app, get_req("/projects?limit=1&offset=1")?).await?;
+
+        // -- Check
+        assert_eq!(status, StatusCode::OK);
+        let all = all.as_array().context("projects")?;
+        assert_eq!(all.len(), 2);
+        let page = page.as_array().context("projects")?;
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0]["project_id"], "rest-project-02");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_project_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let (app, seeded) = init_app().await?;
+        let id = *seeded.projects.get("rest-project-01").context("p1")?;
+
+        // -- Exec
+        let (status, project) = send(&app, get_req(&format!("/projects/{id}"))?).await?;
+
+        // -- Check
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(project["project_name"], "Rest Project 01");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_project_err_not_found() -> Result<()> {
+        // -- Setup & Fixtures
+        let (app, _seeded) = init_app().await?;
+
+        // -- Exec
+        let (status, body) = send(&app, get_req("/projects/999999")?).await?;
+
+        // -- Check
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["message"], "ENTITY_NOT_FOUND");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_project_err_invalid_path() -> Result<()> {
+        // -- Setup & Fixtures
+        let (app, _seeded) = init_app().await?;
+
+        // -- Exec
+        let (status, body) = send(&app, get_req("/projects/not-an-id")?).await?;
+
+        // -- Check
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["message"], "INVALID_REQUEST");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_project_err_invalid_json() -> Result<()> {
+        // -- Setup & Fixtures
+        let (app, _seeded) = init_app().await?;
+        let missing_fields = json!({"project_id": "rest-project-03"}).to_string();
+
+        // -- Exec
+        let (syntax_status, syntax_body) = send(&app, post_req("/projects", "{not json")?).await?;
+        let (data_status, data_body) = send(&app, post_req("/projects", &missing_fields)?).await?;
+
+        // -- Check
+        assert_eq!(syntax_status, StatusCode::BAD_REQUEST);
+        assert_eq!(syntax_body["error"]["message"], "INVALID_REQUEST");
+        assert_eq!(data_status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(data_body["error"]["message"], "INVALID_REQUEST");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_project_err_not_lead() -> Result<()> {
+        // -- Setup & Fixtures
+        let (app, seeded) = init_app().await?;
+        let id = *seeded.projects.get("rest-project-01").context("p1")?;
+        let uri = format!("/projects/{id}");
+        let project_u = json!({
+            "project_lead_address": "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn",
+        });
+
+        // -- Exec
+        let (status, body) = send(&app, patch_req(&uri, &project_u.to_string())?).await?;
+        let (delete_status, _) = send(&app, Request::delete(&uri).body(Body::empty())?).await?;
+
+        // -- Check
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["message"], "NOT_PROJECT_LEAD");
+        assert_eq!(delete_status, StatusCode::FORBIDDEN);
+        let (_, project) = send(&app, get_req(&uri)?).await?;
+        assert_eq!(
+            project["project_lead_address"],
+            "cosmos1udzkhs056fc0f2texd6cv30acg0rjept43xsph"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_bounty_err_not_lead() -> Result<()> {
+        // -- Setup & Fixtures
+        let (app, seeded) = init_app().await?;
+        let id = *seeded.bounties.get("b1").context("b1")?;
+        let uri = format!("/bounties/{id}");
+        let bounty_u = json!({"bounty_title": "Taken over"});
+
+        // -- Exec
+        let (status, body) = send(&app, patch_req(&uri, &bounty_u.to_string())?).await?;
+        let (delete_status, _) = send(&app, Request::delete(&uri).body(Body::empty())?).await?;
+
+        // -- Check
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["message"], "NOT_PROJECT_LEAD");
+        assert_eq!(delete_status, StatusCode::FORBIDDEN);
+        let (_, bounty) = send(&app, get_req(&uri)?).await?;
+        assert_eq!(bounty["bounty_title"], "Rest bounty 01");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_accept_application_err_not_lead() -> Result<()> {
+        // -- Setup & Fixtures
+        let (app, seeded) = init_app().await?;
+        let bounty_id = *seeded.bounties.get("b1").context("b1")?;
+        let application_c = json!({
+            "application_bounty_id": bounty_id,
+            "application_message": "I can do it",
+            "application_estimated_days": 3,
+            "application_address": "cosmos190vqdjtlpcq27xslcveglfmr4ynfwg7gqmchsn",
+        });
+        let (status, application) =
+            send(&app, post_req("/applications", &application_c.to_string())?).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let id = application["id"].as_i64().context("id")?;
+
+        // -- Exec
+        let (status, body) =
+            send(&app, post_req(&format!("/applications/{id}/accept"), "")?).await?;
+
+        // -- Check
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["message"], "NOT_PROJECT_LEAD");
+        let (_, application) = send(&app, get_req(&format!("/applications/{id}"))?).await?;
+        assert_eq!(application["application_status"], "pending");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_denom_err_not_admin() -> Result<()> {
+        // -- Setup & Fixtures
+        let (app, _seeded) = init_app().await?;
+        let denom_c = json!({
+            "denom_base": "urest",
+            "denom_display": "REST",
+            "denom_exponent": 6,
+            "denom_symbol": "REST",
+        });
+
+        // -- Exec
+        let (status, body) = send(&app, post_req("/denoms", &denom_c.to_string())?).await?;
+
+        // -- Check
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["message"], "NOT_ADMIN");
+        let (_, denoms) = send(&app, get_req("/denoms")?).await?;
+        let denoms = denoms.as_array().context("denoms")?;
+        assert!(denoms.iter().all(|denom| denom["denom_base"] != "urest"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_job_err_not_admin() -> Result<()> {
+        // -- Setup & Fixtures
+        let (app, _seeded) = init_app().await?;
+
+        // -- Exec
+        let (status, body) = send(&app, post_req("/jobs/1/retry", "")?).await?;
+        let (reindex_status, _) = send(&app, post_req("/search/reindex", "")?).await?;
+        let (list_status, _) = send(&app, get_req("/jobs")?).await?;
+        let (get_status, _) = send(&app, get_req("/jobs/1")?).await?;
+
+        // -- Check
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["message"], "NOT_ADMIN");
+        assert_eq!(reindex_status, StatusCode::FORBIDDEN);
+        assert_eq!(list_status, StatusCode::FORBIDDEN);
+        assert_eq!(get_status, StatusCode::FORBIDDEN);
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
@@ -0,0 +1,81 @@
+use crate::ctx::Ctx;
+use crate::model::application::{
+    Application, ApplicationBmc, ApplicationFilter, ApplicationForCreate,
+};
+use crate::model::ModelManager;
+use crate::web::rpc::params::ParamsList;
+use crate::web::rpc::{ParamsForCreate, ParamsIded};
+use crate::web::Result;
+use validator::Validate;
+
+// -- Apply to a bounty (as the current user)
+pub async fn create_application(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForCreate<ApplicationForCreate>,
+) -> Result<Application> {
+    let ParamsForCreate { data } = params;
+    data.validate()?;
+
+    let id = ApplicationBmc::create(&ctx, &mm, data).await?;
+    let application = ApplicationBmc::get(&ctx, &mm, id).await?;
+
+    Ok(application)
+}
+
+// -- Get application
+pub async fn get_application(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsIded,
+) -> Result<Application> {
+    let ParamsIded { id } = params;
+
+    let application = ApplicationBmc::get(&ctx, &mm, id).await?;
+
+    Ok(application)
+}
+
+// -- List applications (e.g., of a bounty)
+pub async fn list_applications(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsList<ApplicationFilter>,
+) -> Result<Vec<Application>> {
+    let ParamsList {
+        filter,
+        list_options,
+    } = params;
+
+    let applications = ApplicationBmc::list(&ctx, &mm, filter, list_options).await?;
+
+    Ok(applications)
+}
+
+// -- Accept application (assigns the bounty, rejects the other applications)
+pub async fn accept_application(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsIded,
+) -> Result<Application> {
+    let ParamsIded { id } = params;
+
+    ApplicationBmc::accept(&ctx, &mm, id).await?;
+    let application = ApplicationBmc::get(&ctx, &mm, id).await?;
+
+    Ok(application)
+}
+
+// -- Reject application
+pub async fn reject_application(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsIded,
+) -> Result<Application> {
+    let ParamsIded { id } = params;
+
+    ApplicationBmc::reject(&ctx, &mm, id).await?;
+    let application = ApplicationBmc::get(&ctx, &mm, id).await?;
+
+    Ok(application)
+}
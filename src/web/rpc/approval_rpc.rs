@@ -0,0 +1,36 @@
+use crate::ctx::Ctx;
+use crate::model::approval::{ApprovalBmc, ApprovalPolicy};
+use crate::model::ModelManager;
+use crate::web::rpc::{ParamsForUpdate, ParamsIded};
+use crate::web::Result;
+use validator::Validate;
+
+// -- Get the approval policy of a project
+pub async fn get_approval_policy(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsIded,
+) -> Result<ApprovalPolicy> {
+    let ParamsIded { id } = params;
+
+    let policy = ApprovalBmc::get_policy(&ctx, &mm, id).await?;
+
+    Ok(policy)
+}
+
+// -- Set the approval policy of a project (`null` for the default one)
+pub async fn set_approval_policy(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForUpdate<Option<ApprovalPolicy>>,
+) -> Result<ApprovalPolicy> {
+    let ParamsForUpdate { id, data } = params;
+    if let Some(policy) = &data {
+        policy.validate()?;
+    }
+
+    ApprovalBmc::set_policy(&ctx, &mm, id, data).await?;
+    let policy = ApprovalBmc::get_policy(&ctx, &mm, id).await?;
+
+    Ok(policy)
+}
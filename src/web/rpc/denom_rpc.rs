@@ -0,0 +1,75 @@
+use crate::ctx::Ctx;
+use crate::model::denom::{
+    Denom, DenomAmount, DenomBmc, DenomFilter, DenomForCreate, DenomForUpdate,
+};
+use crate::model::ModelManager;
+use crate::web::rpc::params::{ParamsForAmount, ParamsList};
+use crate::web::rpc::{ParamsForCreate, ParamsForUpdate, ParamsIded};
+use crate::web::Result;
+use validator::Validate;
+
+// -- Register a denom (admin only)
+pub async fn create_denom(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForCreate<DenomForCreate>,
+) -> Result<Denom> {
+    let ParamsForCreate { data } = params;
+    data.validate()?;
+
+    let id = DenomBmc::create(&ctx, &mm, data).await?;
+    let denom = DenomBmc::get(&ctx, &mm, id).await?;
+
+    Ok(denom)
+}
+
+pub async fn get_denom(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<Denom> {
+    let ParamsIded { id } = params;
+
+    let denom = DenomBmc::get(&ctx, &mm, id).await?;
+
+    Ok(denom)
+}
+
+pub async fn list_denoms(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsList<DenomFilter>,
+) -> Result<Vec<Denom>> {
+    let ParamsList {
+        filter,
+        list_options,
+    } = params;
+
+    let denoms = DenomBmc::list(&ctx, &mm, filter, list_options).await?;
+
+    Ok(denoms)
+}
+
+// -- Update a denom display metadata, or disable it (admin only)
+pub async fn update_denom(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForUpdate<DenomForUpdate>,
+) -> Result<Denom> {
+    let ParamsForUpdate { id, data } = params;
+    data.validate()?;
+
+    DenomBmc::update(&ctx, &mm, id, data).await?;
+    let denom = DenomBmc::get(&ctx, &mm, id).await?;
+
+    Ok(denom)
+}
+
+// -- Convert an amount (e.g., "1.5 ATOM") to base units
+pub async fn convert_amount(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForAmount,
+) -> Result<DenomAmount> {
+    let ParamsForAmount { amount } = params;
+
+    let amount = DenomBmc::convert(&ctx, &mm, &amount).await?;
+
+    Ok(amount)
+}
@@ -0,0 +1,61 @@
+use crate::ctx::Ctx;
+use crate::model::job::{Job, JobBmc, JobFilter};
+use crate::model::user::UserBmc;
+use crate::model::ModelManager;
+use crate::web::rpc::params::ParamsList;
+use crate::web::rpc::ParamsIded;
+use crate::web::Result;
+use crate::worker::handlers::SearchReindex;
+
+pub async fn list_jobs(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsList<JobFilter>,
+) -> Result<Vec<Job>> {
+    let ParamsList {
+        filter,
+        list_options,
+    } = params;
+
+    let jobs = JobBmc::list(&ctx, &mm, filter, list_options).await?;
+
+    Ok(jobs)
+}
+
+pub async fn get_job(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<Job> {
+    let ParamsIded { id } = params;
+
+    let job = JobBmc::get(&ctx, &mm, id).await?;
+
+    Ok(job)
+}
+
+// -- Queue a dead or cancelled job again (admin only)
+pub async fn retry_job(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<Job> {
+    let ParamsIded { id } = params;
+
+    JobBmc::retry(&ctx, &mm, id).await?;
+    let job = JobBmc::get(&ctx, &mm, id).await?;
+
+    Ok(job)
+}
+
+// -- Cancel a queued job (admin only)
+pub async fn cancel_job(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<Job> {
+    let ParamsIded { id } = params;
+
+    JobBmc::cancel(&ctx, &mm, id).await?;
+    let job = JobBmc::get(&ctx, &mm, id).await?;
+
+    Ok(job)
+}
+
+// -- Queue a search reindex job (admin only)
+pub async fn reindex_search(ctx: Ctx, mm: ModelManager) -> Result<Job> {
+    UserBmc::check_admin(&ctx, &mm).await?;
+
+    let id = JobBmc::enqueue(&ctx, &mm, &SearchReindex {}).await?;
+    let job = JobBmc::get(&ctx, &mm, id).await?;
+
+    Ok(job)
+}
@@ -0,0 +1,58 @@
+use crate::ctx::Ctx;
+use crate::model::ledger::{AccountBalance, LedgerBmc, LedgerEntry, ProjectFunding};
+use crate::model::ModelManager;
+use crate::web::rpc::{ParamsForAddress, ParamsForUpdate, ParamsIded};
+use crate::web::Result;
+use validator::Validate;
+
+// -- Fund a project budget (returns the project balances)
+pub async fn fund_project(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForUpdate<ProjectFunding>,
+) -> Result<Vec<AccountBalance>> {
+    let ParamsForUpdate { id, data } = params;
+    data.validate()?;
+
+    LedgerBmc::fund_project(&ctx, &mm, id, data).await?;
+    let balances = LedgerBmc::project_balances(&ctx, &mm, id).await?;
+
+    Ok(balances)
+}
+
+pub async fn get_project_balances(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsIded,
+) -> Result<Vec<AccountBalance>> {
+    let ParamsIded { id } = params;
+
+    let balances = LedgerBmc::project_balances(&ctx, &mm, id).await?;
+
+    Ok(balances)
+}
+
+pub async fn get_contributor_balances(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForAddress,
+) -> Result<Vec<AccountBalance>> {
+    let ParamsForAddress { address } = params;
+
+    let balances = LedgerBmc::contributor_balances(&ctx, &mm, &address).await?;
+
+    Ok(balances)
+}
+
+// -- The ledger entries of a project
+pub async fn list_ledger_entries(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsIded,
+) -> Result<Vec<LedgerEntry>> {
+    let ParamsIded { id } = params;
+
+    let entries = LedgerBmc::list_entries(&ctx, &mm, id).await?;
+
+    Ok(entries)
+}
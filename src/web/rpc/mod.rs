@@ -1,12 +1,53 @@
 // region:    --- Modules
 
+mod application_rpc;
+mod approval_rpc;
+mod denom_rpc;
+mod job_rpc;
+mod ledger_rpc;
+mod notification_rpc;
 mod params;
+mod payout_rpc;
+mod search_rpc;
+mod submission_rpc;
+mod tag_rpc;
 mod task_rpc;
+mod user_rpc;
+mod wallet_rpc;
+mod webhook_rpc;
 use crate::ctx::Ctx;
 use crate::model::ModelManager;
+use crate::web::rpc::application_rpc::{
+    accept_application, create_application, get_application, list_applications, reject_application,
+};
+use crate::web::rpc::approval_rpc::{get_approval_policy, set_approval_policy};
+use crate::web::rpc::denom_rpc::{
+    convert_amount, create_denom, get_denom, list_denoms, update_denom,
+};
+use crate::web::rpc::job_rpc::{cancel_job, get_job, list_jobs, reindex_search, retry_job};
+use crate::web::rpc::ledger_rpc::{
+    fund_project, get_contributor_balances, get_project_balances, list_ledger_entries,
+};
+use crate::web::rpc::notification_rpc::{
+    get_notification_prefs, get_unread_count, list_notifications, mark_all_notifications_read,
+    mark_notifications_read, set_notification_prefs,
+};
+use crate::web::rpc::payout_rpc::{build_payout, cancel_payout, confirm_payout, get_payout};
+use crate::web::rpc::search_rpc::search;
+use crate::web::rpc::submission_rpc::{
+    create_deliverable, create_submission, delete_deliverable, get_submission, list_deliverables,
+    list_submissions, review_submission,
+};
+use crate::web::rpc::tag_rpc::{add_tag, get_tags, list_tags, remove_tag};
 use crate::web::rpc::task_rpc::{
-    create_bounty, create_project, delete_bounty, delete_project, list_bounty, list_project,
-    update_bounty, update_project,
+    create_bounty, create_project, delete_bounty, delete_project, get_bounty, get_project,
+    get_project_by_project_id, list_bounty, list_project, update_bounty, update_project,
+};
+use crate::web::rpc::user_rpc::{get_user, get_user_by_username};
+use crate::web::rpc::wallet_rpc::{get_wallet_challenge, link_wallet, list_wallets, unlink_wallet};
+use crate::web::rpc::webhook_rpc::{
+    create_webhook, delete_webhook, get_webhook, list_webhook_deliveries, list_webhooks,
+    test_webhook, update_webhook,
 };
 use crate::web::{Error, Result};
 use axum::extract::State;
@@ -92,6 +133,12 @@ macro_rules! exec_rpc_fn {
         $rpc_fn($ctx, $mm, params).await.map(to_value)??
     }};
 
+    // With optional Params (all optional, `{}` when missing)
+    ($rpc_fn:expr, $ctx:expr, $mm:expr, $rpc_params:expr, optional) => {{
+        let params = $rpc_params.unwrap_or_else(|| json!({}));
+        exec_rpc_fn!($rpc_fn, $ctx, $mm, Some(params))
+    }};
+
     // Without Params
     ($rpc_fn:expr, $ctx:expr, $mm:expr) => {
         $rpc_fn($ctx, $mm).await.map(to_value)??
@@ -110,14 +157,107 @@ async fn _rpc_handler(ctx: Ctx, mm: ModelManager, rpc_req: RpcRequest) -> Result
     let result_json: Value = match rpc_method.as_str() {
         // -- Task RPC methods.
         "create_bounty" => exec_rpc_fn!(create_bounty, ctx, mm, rpc_params),
-        "list_bounty" => exec_rpc_fn!(list_bounty, ctx, mm),
+        "get_bounty" => exec_rpc_fn!(get_bounty, ctx, mm, rpc_params),
+        "list_bounty" => exec_rpc_fn!(list_bounty, ctx, mm, rpc_params, optional),
         "update_bounty" => exec_rpc_fn!(update_bounty, ctx, mm, rpc_params),
         "delete_bounty" => exec_rpc_fn!(delete_bounty, ctx, mm, rpc_params),
         "create_project" => exec_rpc_fn!(create_project, ctx, mm, rpc_params),
-        "list_project" => exec_rpc_fn!(list_project, ctx, mm),
+        "get_project" => exec_rpc_fn!(get_project, ctx, mm, rpc_params),
+        "get_project_by_project_id" => {
+            exec_rpc_fn!(get_project_by_project_id, ctx, mm, rpc_params)
+        }
+        "list_project" => exec_rpc_fn!(list_project, ctx, mm, rpc_params, optional),
         "update_project" => exec_rpc_fn!(update_project, ctx, mm, rpc_params),
         "delete_project" => exec_rpc_fn!(delete_project, ctx, mm, rpc_params),
 
+        // -- Application RPC methods.
+        "create_application" => exec_rpc_fn!(create_application, ctx, mm, rpc_params),
+        "get_application" => exec_rpc_fn!(get_application, ctx, mm, rpc_params),
+        "list_applications" => exec_rpc_fn!(list_applications, ctx, mm, rpc_params, optional),
+        "accept_application" => exec_rpc_fn!(accept_application, ctx, mm, rpc_params),
+        "reject_application" => exec_rpc_fn!(reject_application, ctx, mm, rpc_params),
+
+        // -- Submission RPC methods.
+        "create_deliverable" => exec_rpc_fn!(create_deliverable, ctx, mm, rpc_params),
+        "list_deliverables" => exec_rpc_fn!(list_deliverables, ctx, mm, rpc_params),
+        "delete_deliverable" => exec_rpc_fn!(delete_deliverable, ctx, mm, rpc_params),
+        "create_submission" => exec_rpc_fn!(create_submission, ctx, mm, rpc_params),
+        "get_submission" => exec_rpc_fn!(get_submission, ctx, mm, rpc_params),
+        "list_submissions" => exec_rpc_fn!(list_submissions, ctx, mm, rpc_params),
+        "review_submission" => exec_rpc_fn!(review_submission, ctx, mm, rpc_params),
+
+        // -- Approval RPC methods.
+        "get_approval_policy" => exec_rpc_fn!(get_approval_policy, ctx, mm, rpc_params),
+        "set_approval_policy" => exec_rpc_fn!(set_approval_policy, ctx, mm, rpc_params),
+
+        // -- Denom RPC methods.
+        "create_denom" => exec_rpc_fn!(create_denom, ctx, mm, rpc_params),
+        "get_denom" => exec_rpc_fn!(get_denom, ctx, mm, rpc_params),
+        "list_denoms" => exec_rpc_fn!(list_denoms, ctx, mm, rpc_params, optional),
+        "update_denom" => exec_rpc_fn!(update_denom, ctx, mm, rpc_params),
+        "convert_amount" => exec_rpc_fn!(convert_amount, ctx, mm, rpc_params),
+
+        // -- Ledger RPC methods.
+        "fund_project" => exec_rpc_fn!(fund_project, ctx, mm, rpc_params),
+        "get_project_balances" => exec_rpc_fn!(get_project_balances, ctx, mm, rpc_params),
+        "get_contributor_balances" => {
+            exec_rpc_fn!(get_contributor_balances, ctx, mm, rpc_params)
+        }
+        "list_ledger_entries" => exec_rpc_fn!(list_ledger_entries, ctx, mm, rpc_params),
+
+        // -- Payout RPC methods.
+        "build_payout" => exec_rpc_fn!(build_payout, ctx, mm, rpc_params),
+        "get_payout" => exec_rpc_fn!(get_payout, ctx, mm, rpc_params),
+        "confirm_payout" => exec_rpc_fn!(confirm_payout, ctx, mm, rpc_params),
+        "cancel_payout" => exec_rpc_fn!(cancel_payout, ctx, mm, rpc_params),
+
+        // -- Job RPC methods.
+        "list_jobs" => exec_rpc_fn!(list_jobs, ctx, mm, rpc_params, optional),
+        "get_job" => exec_rpc_fn!(get_job, ctx, mm, rpc_params),
+        "retry_job" => exec_rpc_fn!(retry_job, ctx, mm, rpc_params),
+        "cancel_job" => exec_rpc_fn!(cancel_job, ctx, mm, rpc_params),
+        "reindex_search" => exec_rpc_fn!(reindex_search, ctx, mm),
+
+        // -- Tag RPC methods.
+        "list_tags" => exec_rpc_fn!(list_tags, ctx, mm),
+        "get_tags" => exec_rpc_fn!(get_tags, ctx, mm, rpc_params),
+        "add_tag" => exec_rpc_fn!(add_tag, ctx, mm, rpc_params),
+        "remove_tag" => exec_rpc_fn!(remove_tag, ctx, mm, rpc_params),
+
+        // -- Search RPC methods.
+        "search" => exec_rpc_fn!(search, ctx, mm, rpc_params),
+
+        // -- User RPC methods.
+        "get_user" => exec_rpc_fn!(get_user, ctx, mm, rpc_params),
+        "get_user_by_username" => exec_rpc_fn!(get_user_by_username, ctx, mm, rpc_params),
+
+        // -- Wallet RPC methods.
+        "get_wallet_challenge" => exec_rpc_fn!(get_wallet_challenge, ctx, mm, rpc_params),
+        "link_wallet" => exec_rpc_fn!(link_wallet, ctx, mm, rpc_params),
+        "list_wallets" => exec_rpc_fn!(list_wallets, ctx, mm, rpc_params),
+        "unlink_wallet" => exec_rpc_fn!(unlink_wallet, ctx, mm, rpc_params),
+
+        // -- Notification RPC methods.
+        "list_notifications" => exec_rpc_fn!(list_notifications, ctx, mm, rpc_params, optional),
+        "get_unread_count" => exec_rpc_fn!(get_unread_count, ctx, mm),
+        "mark_notifications_read" => {
+            exec_rpc_fn!(mark_notifications_read, ctx, mm, rpc_params)
+        }
+        "mark_all_notifications_read" => exec_rpc_fn!(mark_all_notifications_read, ctx, mm),
+        "get_notification_prefs" => exec_rpc_fn!(get_notification_prefs, ctx, mm),
+        "set_notification_prefs" => exec_rpc_fn!(set_notification_prefs, ctx, mm, rpc_params),
+
+        // -- Webhook RPC methods.
+        "create_webhook" => exec_rpc_fn!(create_webhook, ctx, mm, rpc_params),
+        "get_webhook" => exec_rpc_fn!(get_webhook, ctx, mm, rpc_params),
+        "list_webhooks" => exec_rpc_fn!(list_webhooks, ctx, mm, rpc_params, optional),
+        "update_webhook" => exec_rpc_fn!(update_webhook, ctx, mm, rpc_params),
+        "delete_webhook" => exec_rpc_fn!(delete_webhook, ctx, mm, rpc_params),
+        "list_webhook_deliveries" => {
+            exec_rpc_fn!(list_webhook_deliveries, ctx, mm, rpc_params)
+        }
+        "test_webhook" => exec_rpc_fn!(test_webhook, ctx, mm, rpc_params),
+
         // -- Fallback as Err.
         _ => return Err(Error::RpcMethodUnknown(rpc_method)),
     };
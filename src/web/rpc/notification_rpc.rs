@@ -0,0 +1,71 @@
+use crate::ctx::Ctx;
+use crate::model::notification::{
+    Notification, NotificationBmc, NotificationCount, NotificationFilter, NotificationPrefs,
+    NotificationPrefsBmc, NotificationPrefsForUpdate,
+};
+use crate::model::ModelManager;
+use crate::web::rpc::params::{ParamsForData, ParamsForIds, ParamsList};
+use crate::web::Result;
+
+// -- The notifications of the user
+pub async fn list_notifications(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsList<NotificationFilter>,
+) -> Result<Vec<Notification>> {
+    let ParamsList {
+        filter,
+        list_options,
+    } = params;
+
+    let notifications = NotificationBmc::list(&ctx, &mm, filter, list_options).await?;
+
+    Ok(notifications)
+}
+
+pub async fn get_unread_count(ctx: Ctx, mm: ModelManager) -> Result<NotificationCount> {
+    let count = NotificationBmc::unread_count(&ctx, &mm).await?;
+
+    Ok(count)
+}
+
+// -- Mark notifications of the user read, returning the unread count
+pub async fn mark_notifications_read(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForIds,
+) -> Result<NotificationCount> {
+    let ParamsForIds { ids } = params;
+
+    NotificationBmc::mark_read(&ctx, &mm, &ids).await?;
+    let count = NotificationBmc::unread_count(&ctx, &mm).await?;
+
+    Ok(count)
+}
+
+pub async fn mark_all_notifications_read(ctx: Ctx, mm: ModelManager) -> Result<NotificationCount> {
+    NotificationBmc::mark_all_read(&ctx, &mm).await?;
+    let count = NotificationBmc::unread_count(&ctx, &mm).await?;
+
+    Ok(count)
+}
+
+pub async fn get_notification_prefs(ctx: Ctx, mm: ModelManager) -> Result<NotificationPrefs> {
+    let prefs = NotificationPrefsBmc::get_for_user(&ctx, &mm, ctx.user_id()).await?;
+
+    Ok(prefs)
+}
+
+// -- Enable or disable notification kinds (the unset ones unchanged)
+pub async fn set_notification_prefs(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForData<NotificationPrefsForUpdate>,
+) -> Result<NotificationPrefs> {
+    let ParamsForData { data } = params;
+
+    NotificationPrefsBmc::set(&ctx, &mm, data).await?;
+    let prefs = NotificationPrefsBmc::get_for_user(&ctx, &mm, ctx.user_id()).await?;
+
+    Ok(prefs)
+}
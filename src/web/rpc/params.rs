@@ -5,6 +5,9 @@
 //! each rpc handler function to receive the exact desired type.
 //!
 
+use crate::model::search::SearchFilter;
+use crate::model::tag::TagTarget;
+use crate::model::ListOptions;
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -22,3 +25,67 @@ pub struct ParamsForUpdate<D> {
 pub struct ParamsIded {
     pub id: i64,
 }
+
+#[derive(Deserialize)]
+pub struct ParamsForProjectId {
+    pub project_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct ParamsForBountyId {
+    pub bounty_id: i64,
+}
+
+#[derive(Deserialize)]
+pub struct ParamsForAddress {
+    pub address: String,
+}
+
+/// An amount with its denom (e.g., "1.5 ATOM").
+#[derive(Deserialize)]
+pub struct ParamsForAmount {
+    pub amount: String,
+}
+
+#[derive(Deserialize)]
+pub struct ParamsForUsername {
+    pub username: String,
+}
+
+#[derive(Deserialize)]
+pub struct ParamsForSearch {
+    pub query: String,
+    pub filter: Option<SearchFilter>,
+    pub list_options: Option<ListOptions>,
+}
+
+/// Params of the list methods, all optional.
+#[derive(Deserialize)]
+pub struct ParamsList<F> {
+    pub filter: Option<F>,
+    pub list_options: Option<ListOptions>,
+}
+
+#[derive(Deserialize)]
+pub struct ParamsForTagTarget {
+    pub entity: TagTarget,
+    pub id: i64,
+}
+
+#[derive(Deserialize)]
+pub struct ParamsForTag {
+    pub entity: TagTarget,
+    pub id: i64,
+    pub tag: String,
+}
+
+#[derive(Deserialize)]
+pub struct ParamsForIds {
+    pub ids: Vec<i64>,
+}
+
+/// Params of the methods on the `Ctx` user data (e.g., its preferences).
+#[derive(Deserialize)]
+pub struct ParamsForData<D> {
+    pub data: D,
+}
@@ -0,0 +1,61 @@
+use crate::ctx::Ctx;
+use crate::model::payout::{PayoutBmc, PayoutForBuild, PayoutForConfirm, PayoutWithBounties};
+use crate::model::ModelManager;
+use crate::web::rpc::{ParamsForCreate, ParamsForUpdate, ParamsIded};
+use crate::web::Result;
+use validator::Validate;
+
+// -- Build the unsigned payout transaction of approved bounties
+pub async fn build_payout(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForCreate<PayoutForBuild>,
+) -> Result<PayoutWithBounties> {
+    let ParamsForCreate { data } = params;
+    data.validate()?;
+
+    let payout = PayoutBmc::build(&ctx, &mm, data).await?;
+
+    Ok(payout)
+}
+
+pub async fn get_payout(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsIded,
+) -> Result<PayoutWithBounties> {
+    let ParamsIded { id } = params;
+
+    let payout = PayoutBmc::get_with_bounties(&ctx, &mm, id).await?;
+
+    Ok(payout)
+}
+
+// -- Confirm the payout with the signed tx hash (marks the bounties paid)
+pub async fn confirm_payout(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForUpdate<PayoutForConfirm>,
+) -> Result<PayoutWithBounties> {
+    let ParamsForUpdate { id, data } = params;
+    data.validate()?;
+
+    PayoutBmc::confirm(&ctx, &mm, id, &data.tx_hash).await?;
+    let payout = PayoutBmc::get_with_bounties(&ctx, &mm, id).await?;
+
+    Ok(payout)
+}
+
+// -- Cancel the pending payout (releases its bounties)
+pub async fn cancel_payout(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsIded,
+) -> Result<PayoutWithBounties> {
+    let ParamsIded { id } = params;
+
+    PayoutBmc::cancel(&ctx, &mm, id).await?;
+    let payout = PayoutBmc::get_with_bounties(&ctx, &mm, id).await?;
+
+    Ok(payout)
+}
@@ -0,0 +1,18 @@
+use crate::ctx::Ctx;
+use crate::model::search::{self, SearchHit};
+use crate::model::ModelManager;
+use crate::web::rpc::params::ParamsForSearch;
+use crate::web::Result;
+
+// -- Search projects and bounties
+pub async fn search(ctx: Ctx, mm: ModelManager, params: ParamsForSearch) -> Result<Vec<SearchHit>> {
+    let ParamsForSearch {
+        query,
+        filter,
+        list_options,
+    } = params;
+
+    let hits = search::search(&ctx, &mm, &query, filter, list_options).await?;
+
+    Ok(hits)
+}
@@ -0,0 +1,108 @@
+use crate::ctx::Ctx;
+use crate::model::deliverable::{Deliverable, DeliverableBmc, DeliverableForCreate};
+use crate::model::submission::{
+    Submission, SubmissionBmc, SubmissionForCreate, SubmissionForReview, SubmissionWithItems,
+};
+use crate::model::ModelManager;
+use crate::web::rpc::params::ParamsForBountyId;
+use crate::web::rpc::{ParamsForCreate, ParamsForUpdate, ParamsIded};
+use crate::web::Result;
+use validator::Validate;
+
+// -- Add a deliverable to the bounty checklist
+pub async fn create_deliverable(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForCreate<DeliverableForCreate>,
+) -> Result<Deliverable> {
+    let ParamsForCreate { data } = params;
+    data.validate()?;
+
+    let id = DeliverableBmc::create(&ctx, &mm, data).await?;
+    let deliverable = DeliverableBmc::get(&ctx, &mm, id).await?;
+
+    Ok(deliverable)
+}
+
+// -- List the deliverables of a bounty
+pub async fn list_deliverables(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForBountyId,
+) -> Result<Vec<Deliverable>> {
+    let ParamsForBountyId { bounty_id } = params;
+
+    let deliverables = DeliverableBmc::list_for_bounty(&ctx, &mm, bounty_id).await?;
+
+    Ok(deliverables)
+}
+
+// -- Delete deliverable
+pub async fn delete_deliverable(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsIded,
+) -> Result<Deliverable> {
+    let ParamsIded { id } = params;
+
+    let deliverable = DeliverableBmc::get(&ctx, &mm, id).await?;
+    DeliverableBmc::delete(&ctx, &mm, id).await?;
+
+    Ok(deliverable)
+}
+
+// -- Submit the work on a bounty (as its assignee)
+pub async fn create_submission(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForCreate<SubmissionForCreate>,
+) -> Result<SubmissionWithItems> {
+    let ParamsForCreate { data } = params;
+    data.validate()?;
+
+    let id = SubmissionBmc::create(&ctx, &mm, data).await?;
+    let submission = SubmissionBmc::get_with_items(&ctx, &mm, id).await?;
+
+    Ok(submission)
+}
+
+// -- Get submission (with its items)
+pub async fn get_submission(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsIded,
+) -> Result<SubmissionWithItems> {
+    let ParamsIded { id } = params;
+
+    let submission = SubmissionBmc::get_with_items(&ctx, &mm, id).await?;
+
+    Ok(submission)
+}
+
+// -- List the submissions of a bounty (the review history)
+pub async fn list_submissions(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForBountyId,
+) -> Result<Vec<Submission>> {
+    let ParamsForBountyId { bounty_id } = params;
+
+    let submissions = SubmissionBmc::list_for_bounty(&ctx, &mm, bounty_id).await?;
+
+    Ok(submissions)
+}
+
+// -- Review submission (approve, request changes, reject)
+pub async fn review_submission(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForUpdate<SubmissionForReview>,
+) -> Result<Submission> {
+    let ParamsForUpdate { id, data } = params;
+    data.validate()?;
+
+    SubmissionBmc::review(&ctx, &mm, id, data).await?;
+    let submission = SubmissionBmc::get(&ctx, &mm, id).await?;
+
+    Ok(submission)
+}
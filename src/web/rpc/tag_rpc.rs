@@ -0,0 +1,42 @@
+use crate::ctx::Ctx;
+use crate::model::tag::{Tag, TagBmc, TagForCreate, TagWithCount};
+use crate::model::ModelManager;
+use crate::web::rpc::params::{ParamsForTag, ParamsForTagTarget};
+use crate::web::Result;
+use validator::Validate;
+
+// -- List tags (with usage counts)
+pub async fn list_tags(ctx: Ctx, mm: ModelManager) -> Result<Vec<TagWithCount>> {
+    let tags = TagBmc::list_with_counts(&ctx, &mm).await?;
+
+    Ok(tags)
+}
+
+// -- Get the tags of a project or bounty
+pub async fn get_tags(ctx: Ctx, mm: ModelManager, params: ParamsForTagTarget) -> Result<Vec<Tag>> {
+    let ParamsForTagTarget { entity, id } = params;
+
+    let tags = TagBmc::list_for(&ctx, &mm, entity, id).await?;
+
+    Ok(tags)
+}
+
+// -- Add a tag to a project or bounty (project lead only)
+pub async fn add_tag(ctx: Ctx, mm: ModelManager, params: ParamsForTag) -> Result<Vec<Tag>> {
+    let ParamsForTag { entity, id, tag } = params;
+    let tag_c = TagForCreate::new(&tag);
+    tag_c.validate()?;
+
+    let tags = TagBmc::add_to(&ctx, &mm, entity, id, tag_c).await?;
+
+    Ok(tags)
+}
+
+// -- Remove a tag from a project or bounty (project lead only)
+pub async fn remove_tag(ctx: Ctx, mm: ModelManager, params: ParamsForTag) -> Result<Vec<Tag>> {
+    let ParamsForTag { entity, id, tag } = params;
+
+    let tags = TagBmc::remove_from(&ctx, &mm, entity, id, &tag).await?;
+
+    Ok(tags)
+}
@@ -1,11 +1,17 @@
 use crate::ctx::Ctx;
-use crate::model::bounty::{Bounty, BountyBmc, BountyForCreate, BountyForUpdate};
-use crate::model::project::{Project, ProjectBmc, ProjectForCreate, ProjectForUpdate};
-use crate::model::ModelManager;
+use crate::model::bounty::{Bounty, BountyBmc, BountyFilter, BountyForCreate, BountyForUpdate};
+use crate::model::project::{
+    Project, ProjectBmc, ProjectFilter, ProjectForCreate, ProjectForUpdate,
+};
+use crate::model::{self, ModelManager};
+use crate::web::rpc::params::{ParamsForProjectId, ParamsList};
 use crate::web::rpc::{ParamsForCreate, ParamsForUpdate, ParamsIded};
 use crate::web::Result;
+use validator::Validate;
 
-// -- TODO: Add permission layer for create, update and delete routes
+// -- TODO: Add permission layer for create routes
+//    (update and delete are for the project lead, see `ProjectBmc::update`
+//    and `BountyBmc::update`)
 
 // -- Create bounty
 pub async fn create_bounty(
@@ -14,6 +20,7 @@ pub async fn create_bounty(
     params: ParamsForCreate<BountyForCreate>,
 ) -> Result<Bounty> {
     let ParamsForCreate { data } = params;
+    data.validate()?;
 
     let id = BountyBmc::create(&ctx, &mm, data).await?;
     let task = BountyBmc::get(&ctx, &mm, id).await?;
@@ -21,9 +28,27 @@ pub async fn create_bounty(
     Ok(task)
 }
 
+// -- Get bounty
+pub async fn get_bounty(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<Bounty> {
+    let ParamsIded { id } = params;
+
+    let bounty = BountyBmc::get(&ctx, &mm, id).await?;
+
+    Ok(bounty)
+}
+
 // -- List bounties
-pub async fn list_bounty(ctx: Ctx, mm: ModelManager) -> Result<Vec<Bounty>> {
-    let tasks = BountyBmc::list(&ctx, &mm).await?;
+pub async fn list_bounty(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsList<BountyFilter>,
+) -> Result<Vec<Bounty>> {
+    let ParamsList {
+        filter,
+        list_options,
+    } = params;
+
+    let tasks = BountyBmc::list(&ctx, &mm, filter, list_options).await?;
 
     Ok(tasks)
 }
@@ -35,10 +60,14 @@ pub async fn update_bounty(
     params: ParamsForUpdate<BountyForUpdate>,
 ) -> Result<Bounty> {
     let ParamsForUpdate { id, data } = params;
+    data.validate()?;
 
-    BountyBmc::update(&ctx, &mm, id, data).await?;
-
-    let task = BountyBmc::get(&ctx, &mm, id).await?;
+    let task = mm
+        .transaction(|mm| async move {
+            BountyBmc::update(&ctx, &mm, id, data).await?;
+            BountyBmc::get(&ctx, &mm, id).await
+        })
+        .await?;
 
     Ok(task)
 }
@@ -47,8 +76,13 @@ pub async fn update_bounty(
 pub async fn delete_bounty(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<Bounty> {
     let ParamsIded { id } = params;
 
-    let task = BountyBmc::get(&ctx, &mm, id).await?;
-    BountyBmc::delete(&ctx, &mm, id).await?;
+    let task = mm
+        .transaction(|mm| async move {
+            let task = BountyBmc::get(&ctx, &mm, id).await?;
+            BountyBmc::delete(&ctx, &mm, id).await?;
+            Ok::<_, model::Error>(task)
+        })
+        .await?;
 
     Ok(task)
 }
@@ -60,6 +94,7 @@ pub async fn create_project(
     params: ParamsForCreate<ProjectForCreate>,
 ) -> Result<Project> {
     let ParamsForCreate { data } = params;
+    data.validate()?;
 
     let id = ProjectBmc::create(&ctx, &mm, data).await?;
     let task = ProjectBmc::get(&ctx, &mm, id).await?;
@@ -67,9 +102,40 @@ pub async fn create_project(
     Ok(task)
 }
 
+// -- Get project
+pub async fn get_project(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<Project> {
+    let ParamsIded { id } = params;
+
+    let project = ProjectBmc::get(&ctx, &mm, id).await?;
+
+    Ok(project)
+}
+
+// -- Get project by its string project_id
+pub async fn get_project_by_project_id(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForProjectId,
+) -> Result<Project> {
+    let ParamsForProjectId { project_id } = params;
+
+    let project = ProjectBmc::get_by_project_id(&ctx, &mm, &project_id).await?;
+
+    Ok(project)
+}
+
 // -- List project
-pub async fn list_project(ctx: Ctx, mm: ModelManager) -> Result<Vec<Project>> {
-    let tasks = ProjectBmc::list(&ctx, &mm).await?;
+pub async fn list_project(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsList<ProjectFilter>,
+) -> Result<Vec<Project>> {
+    let ParamsList {
+        filter,
+        list_options,
+    } = params;
+
+    let tasks = ProjectBmc::list(&ctx, &mm, filter, list_options).await?;
 
     Ok(tasks)
 }
@@ -81,10 +147,14 @@ pub async fn update_project(
     params: ParamsForUpdate<ProjectForUpdate>,
 ) -> Result<Project> {
     let ParamsForUpdate { id, data } = params;
+    data.validate()?;
 
-    ProjectBmc::update(&ctx, &mm, id, data).await?;
-
-    let task = ProjectBmc::get(&ctx, &mm, id).await?;
+    let task = mm
+        .transaction(|mm| async move {
+            ProjectBmc::update(&ctx, &mm, id, data).await?;
+            ProjectBmc::get(&ctx, &mm, id).await
+        })
+        .await?;
 
     Ok(task)
 }
@@ -93,8 +163,13 @@ pub async fn update_project(
 pub async fn delete_project(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<Project> {
     let ParamsIded { id } = params;
 
-    let task = ProjectBmc::get(&ctx, &mm, id).await?;
-    ProjectBmc::delete(&ctx, &mm, id).await?;
+    let task = mm
+        .transaction(|mm| async move {
+            let task = ProjectBmc::get(&ctx, &mm, id).await?;
+            ProjectBmc::delete(&ctx, &mm, id).await?;
+            Ok::<_, model::Error>(task)
+        })
+        .await?;
 
     Ok(task)
 }
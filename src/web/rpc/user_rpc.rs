@@ -0,0 +1,28 @@
+use crate::ctx::Ctx;
+use crate::model::user::{User, UserBmc};
+use crate::model::ModelManager;
+use crate::web::rpc::params::ParamsForUsername;
+use crate::web::rpc::ParamsIded;
+use crate::web::Result;
+
+// -- Get user
+pub async fn get_user(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<User> {
+    let ParamsIded { id } = params;
+
+    let user: User = UserBmc::get(&ctx, &mm, id).await?;
+
+    Ok(user)
+}
+
+// -- Get user by username
+pub async fn get_user_by_username(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForUsername,
+) -> Result<User> {
+    let ParamsForUsername { username } = params;
+
+    let user: User = UserBmc::get_by_username(&ctx, &mm, &username).await?;
+
+    Ok(user)
+}
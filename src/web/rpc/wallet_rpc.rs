@@ -0,0 +1,54 @@
+use crate::ctx::Ctx;
+use crate::model::wallet::{Wallet, WalletBmc, WalletChallenge, WalletForChallenge, WalletForLink};
+use crate::model::ModelManager;
+use crate::web::rpc::{ParamsForCreate, ParamsIded};
+use crate::web::Result;
+use validator::Validate;
+
+// -- The message to sign with a wallet, to link it
+pub async fn get_wallet_challenge(
+    ctx: Ctx,
+    _mm: ModelManager,
+    params: ParamsForCreate<WalletForChallenge>,
+) -> Result<WalletChallenge> {
+    let ParamsForCreate { data } = params;
+    data.validate()?;
+
+    let challenge = WalletBmc::challenge(&ctx, data)?;
+
+    Ok(challenge)
+}
+
+// -- Link a wallet address to the current user (with its signed challenge)
+pub async fn link_wallet(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForCreate<WalletForLink>,
+) -> Result<Wallet> {
+    let ParamsForCreate { data } = params;
+    data.validate()?;
+
+    let id = WalletBmc::link(&ctx, &mm, data).await?;
+    let wallet = WalletBmc::get(&ctx, &mm, id).await?;
+
+    Ok(wallet)
+}
+
+// -- List the wallets of a user
+pub async fn list_wallets(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<Vec<Wallet>> {
+    let ParamsIded { id } = params;
+
+    let wallets = WalletBmc::list_for_user(&ctx, &mm, id).await?;
+
+    Ok(wallets)
+}
+
+// -- Unlink a wallet of the current user
+pub async fn unlink_wallet(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<Wallet> {
+    let ParamsIded { id } = params;
+
+    let wallet = WalletBmc::get(&ctx, &mm, id).await?;
+    WalletBmc::unlink(&ctx, &mm, id).await?;
+
+    Ok(wallet)
+}
@@ -0,0 +1,101 @@
+use crate::ctx::Ctx;
+use crate::model::webhook::{
+    Webhook, WebhookBmc, WebhookDelivery, WebhookDeliveryBmc, WebhookEvent, WebhookFilter,
+    WebhookForCreate, WebhookForUpdate,
+};
+use crate::model::ModelManager;
+use crate::web::rpc::params::ParamsList;
+use crate::web::rpc::{ParamsForCreate, ParamsForUpdate, ParamsIded};
+use crate::web::Result;
+use crate::worker::webhook;
+use validator::Validate;
+
+// -- Subscribe a URL to event types of a project
+pub async fn create_webhook(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForCreate<WebhookForCreate>,
+) -> Result<Webhook> {
+    let ParamsForCreate { data } = params;
+    data.validate()?;
+
+    let id = WebhookBmc::create(&ctx, &mm, data).await?;
+    let webhook = WebhookBmc::get(&ctx, &mm, id).await?;
+
+    Ok(webhook)
+}
+
+pub async fn get_webhook(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<Webhook> {
+    let ParamsIded { id } = params;
+
+    let webhook = WebhookBmc::get(&ctx, &mm, id).await?;
+
+    Ok(webhook)
+}
+
+pub async fn list_webhooks(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsList<WebhookFilter>,
+) -> Result<Vec<Webhook>> {
+    let ParamsList {
+        filter,
+        list_options,
+    } = params;
+
+    let webhooks = WebhookBmc::list(&ctx, &mm, filter, list_options).await?;
+
+    Ok(webhooks)
+}
+
+pub async fn update_webhook(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsForUpdate<WebhookForUpdate>,
+) -> Result<Webhook> {
+    let ParamsForUpdate { id, data } = params;
+    data.validate()?;
+
+    WebhookBmc::update(&ctx, &mm, id, data).await?;
+    let webhook = WebhookBmc::get(&ctx, &mm, id).await?;
+
+    Ok(webhook)
+}
+
+pub async fn delete_webhook(ctx: Ctx, mm: ModelManager, params: ParamsIded) -> Result<Webhook> {
+    let ParamsIded { id } = params;
+
+    let webhook = WebhookBmc::get(&ctx, &mm, id).await?;
+    WebhookBmc::delete(&ctx, &mm, id).await?;
+
+    Ok(webhook)
+}
+
+// -- The delivery attempts of a webhook
+pub async fn list_webhook_deliveries(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsIded,
+) -> Result<Vec<WebhookDelivery>> {
+    let ParamsIded { id } = params;
+
+    WebhookBmc::get(&ctx, &mm, id).await?;
+    let deliveries = WebhookDeliveryBmc::list_for_webhook(&ctx, &mm, id, None, None).await?;
+
+    Ok(deliveries)
+}
+
+// -- Deliver a sample event now (not retried), returning the attempt
+pub async fn test_webhook(
+    ctx: Ctx,
+    mm: ModelManager,
+    params: ParamsIded,
+) -> Result<WebhookDelivery> {
+    let ParamsIded { id } = params;
+
+    let webhook = WebhookBmc::get(&ctx, &mm, id).await?;
+    let event = WebhookEvent::sample(webhook.webhook_project_id);
+    let delivery = webhook::deliver(&ctx, &mm, &webhook, &event).await?;
+
+    Ok(delivery)
+}
@@ -0,0 +1,90 @@
+//! The job handlers, registered on the workers in `main`.
+
+use crate::ctx::Ctx;
+use crate::model::job::JobPayload;
+use crate::model::webhook::{WebhookBmc, WebhookEvent};
+use crate::model::{self, search, ModelManager};
+use crate::worker::{webhook, Error, JobHandler, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+// region:    --- Search Reindex
+
+/// Recompute the search documents (see `search::reindex`).
+#[derive(Serialize, Deserialize)]
+pub struct SearchReindex {}
+
+impl JobPayload for SearchReindex {
+    const KIND: &'static str = "search_reindex";
+}
+
+pub struct SearchReindexHandler;
+
+#[async_trait]
+impl JobHandler for SearchReindexHandler {
+    type Payload = SearchReindex;
+
+    async fn handle(&self, ctx: &Ctx, mm: &ModelManager, _payload: SearchReindex) -> Result<()> {
+        let count = search::reindex(ctx, mm).await?;
+        info!("{:<12} - search reindexed {count} rows", "WORKER");
+
+        Ok(())
+    }
+}
+
+// endregion: --- Search Reindex
+
+// region:    --- Webhook Deliver
+
+/// Deliver the event to the webhook (see `worker::webhook`).
+#[derive(Serialize, Deserialize)]
+pub struct WebhookDeliver {
+    pub webhook_id: i64,
+    pub event: WebhookEvent,
+}
+
+impl JobPayload for WebhookDeliver {
+    const KIND: &'static str = "webhook_deliver";
+
+    // (backoff up to ~1h between the last attempts)
+    fn max_attempts() -> i32 {
+        10
+    }
+}
+
+pub struct WebhookDeliverHandler;
+
+#[async_trait]
+impl JobHandler for WebhookDeliverHandler {
+    type Payload = WebhookDeliver;
+
+    async fn handle(&self, ctx: &Ctx, mm: &ModelManager, payload: WebhookDeliver) -> Result<()> {
+        let WebhookDeliver { webhook_id, event } = payload;
+
+        // (deleted or disabled since queued, nothing to deliver)
+        let webhook = match WebhookBmc::get(ctx, mm, webhook_id).await {
+            Ok(webhook) if webhook.webhook_enabled => webhook,
+            Ok(_) | Err(model::Error::EntityNotFound { .. }) => {
+                info!(
+                    "{:<12} - webhook {webhook_id} gone, skip {}",
+                    "WORKER", event.id
+                );
+                return Ok(());
+            }
+            Err(ex) => return Err(ex.into()),
+        };
+
+        let delivery = webhook::deliver(ctx, mm, &webhook, &event).await?;
+        if !delivery.delivery_ok {
+            return Err(Error::Failed(format!(
+                "webhook {webhook_id} delivery failed: {}",
+                delivery.delivery_error.unwrap_or_default()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+// endregion: --- Webhook Deliver
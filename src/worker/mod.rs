@@ -0,0 +1,271 @@
+//! Background workers of the job queue (see `model::job`).
+//!
+//! Design:
+//!
+//! - A `Worker` runs the jobs of its registered `JobHandler`s (one handler
+//!   per job kind, typed by its `JobPayload`), one job at a time, polling
+//!   the queue when idle.
+//! - `spawn` starts `WORKER_COUNT` workers (see `main`), each claiming its
+//!   own jobs, so a job runs on a single worker. (named by a random
+//!   process id, unique across hosts and containers, for the job lock)
+//! - A handler error, an invalid payload, or a handler panic fails the
+//!   attempt (retried with backoff, see `JobBmc::fail`).
+//! - Handlers run as the root `Ctx`.
+//! - Db only (the in-memory storage has no job queue).
+//!
+
+// region:    --- Modules
+
+mod error;
+pub mod handlers;
+pub mod webhook;
+
+pub use self::error::{Error, Result};
+
+use crate::ctx::Ctx;
+use crate::model::job::{Job, JobBmc, JobPayload, JobStatus};
+use crate::model::ModelManager;
+use crate::scheduler::Clock;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, error};
+use uuid::Uuid;
+
+// endregion: --- Modules
+
+/// A running job not released after this is claimable again.
+const LOCK_TIMEOUT: time::Duration = time::Duration::minutes(5);
+
+#[async_trait]
+pub trait JobHandler: Send + Sync + 'static {
+    type Payload: JobPayload;
+
+    async fn handle(&self, ctx: &Ctx, mm: &ModelManager, payload: Self::Payload) -> Result<()>;
+}
+
+/// A `JobHandler` of any payload type.
+#[async_trait]
+trait DynJobHandler: Send + Sync {
+    async fn handle_value(&self, ctx: &Ctx, mm: &ModelManager, payload: Value) -> Result<()>;
+}
+
+#[async_trait]
+impl<H: JobHandler> DynJobHandler for H {
+    async fn handle_value(&self, ctx: &Ctx, mm: &ModelManager, payload: Value) -> Result<()> {
+        let payload = serde_json::from_value(payload).map_err(|ex| Error::PayloadInvalid {
+            kind: H::Payload::KIND,
+            cause: ex.to_string(),
+        })?;
+        self.handle(ctx, mm, payload).await
+    }
+}
+
+#[derive(Clone)]
+pub struct Worker {
+    mm: ModelManager,
+    clock: Arc<dyn Clock>,
+    name: String,
+    handlers: HashMap<&'static str, Arc<dyn DynJobHandler>>,
+}
+
+impl Worker {
+    pub fn new(mm: ModelManager, clock: Arc<dyn Clock>) -> Self {
+        Worker {
+            mm,
+            clock,
+            name: format!("worker-{}", Uuid::new_v4()),
+            handlers: HashMap::new(),
+        }
+    }
+
+    /// Register the handler of the `H::Payload` jobs.
+    pub fn handler<H: JobHandler>(mut self, handler: H) -> Self {
+        self.handlers.insert(H::Payload::KIND, Arc::new(handler));
+        self
+    }
+
+    /// Start `count` workers, polling the queue every `poll` when idle.
+    pub fn spawn(self, count: usize, poll: Duration) -> Vec<JoinHandle<()>> {
+        (0..count)
+            .map(|i| {
+                let mut worker = self.clone();
+                worker.name = format!("{}-{i}", self.name);
+                tokio::spawn(async move {
+                    loop {
+                        match worker.run_next().await {
+                            Ok(Some(_)) => continue,
+                            Ok(None) => {}
+                            Err(ex) => error!("{:<12} - {}: {ex:?}", "WORKER", worker.name),
+                        }
+                        tokio::time::sleep(poll).await;
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Claim and run the next due job, if any.
+    /// Returns the job id, and its new status.
+    pub async fn run_next(&self) -> Result<Option<(i64, JobStatus)>> {
+        let ctx = Ctx::root_ctx();
+        let mm = &self.mm;
+        let now = self.clock.now();
+        let kinds: Vec<&str> = self.handlers.keys().copied().collect();
+
+        let Some(job) =
+            JobBmc::claim(&ctx, mm, &self.name, &kinds, now, now - LOCK_TIMEOUT).await?
+        else {
+            return Ok(None);
+        };
+        debug!(
+            "{:<12} - {} run job {} ({}, attempt {})",
+            "WORKER", self.name, job.id, job.job_kind, job.job_attempts
+        );
+
+        let status = match self.run(&job).await {
+            Ok(()) => {
+                JobBmc::complete(&ctx, mm, job.id, &self.name).await?;
+                JobStatus::Succeeded
+            }
+            Err(ex) => {
+                let error = ex.to_string();
+                error!("{:<12} - job {} failed: {error}", "WORKER", job.id);
+                JobBmc::fail(&ctx, mm, &job, &self.name, &error, self.clock.now()).await?
+            }
+        };
+
+        Ok(Some((job.id, status)))
+    }
+
+    /// Run the job handler in its own task, so a panic fails the job only.
+    async fn run(&self, job: &Job) -> Result<()> {
+        let handler = self
+            .handlers
+            .get(job.job_kind.as_str())
+            .cloned()
+            .ok_or_else(|| Error::Failed(format!("no handler of {}", job.job_kind)))?;
+        let mm = self.mm.clone();
+        let payload = job.job_payload.0.clone();
+
+        tokio::spawn(async move { handler.handle_value(&Ctx::root_ctx(), &mm, payload).await })
+            .await
+            .map_err(|_| Error::HandlerPanicked {
+                kind: job.job_kind.clone(),
+            })?
+    }
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::scheduler::ManualClock;
+    use serde::{Deserialize, Serialize};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use time::macros::datetime;
+
+    #[derive(Serialize, Deserialize)]
+    struct FlakyPayload {
+        fail_times: usize,
+    }
+
+    impl JobPayload for FlakyPayload {
+        const KIND: &'static str = "test_flaky";
+    }
+
+    /// A `FlakyPayload` of the wrong type.
+    #[derive(Serialize, Deserialize)]
+    struct BadPayload {
+        fail_times: String,
+    }
+
+    impl JobPayload for BadPayload {
+        const KIND: &'static str = "test_flaky";
+
+        fn max_attempts() -> i32 {
+            1
+        }
+    }
+
+    /// Fails the first `fail_times` runs.
+    #[derive(Clone, Default)]
+    struct FlakyHandler {
+        runs: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl JobHandler for FlakyHandler {
+        type Payload = FlakyPayload;
+
+        async fn handle(
+            &self,
+            _ctx: &Ctx,
+            _mm: &ModelManager,
+            payload: FlakyPayload,
+        ) -> Result<()> {
+            let run = self.runs.fetch_add(1, Ordering::SeqCst);
+            if run < payload.fail_times {
+                return Err(Error::Failed(format!("run {run} failed")));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_next_retry_ok() -> anyhow::Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let clock = ManualClock::new(datetime!(2030-01-01 0:00 UTC));
+        let handler = FlakyHandler::default();
+        let worker = Worker::new(mm.clone(), Arc::new(clock.clone())).handler(handler.clone());
+        let id = JobBmc::enqueue_at(
+            &ctx,
+            &mm,
+            &FlakyPayload { fail_times: 1 },
+            Some(clock.now()),
+        )
+        .await?;
+
+        // -- Exec & Check
+        assert_eq!(worker.run_next().await?, Some((id, JobStatus::Queued)));
+        assert_eq!(worker.run_next().await?, None, "in backoff");
+
+        clock.advance(time::Duration::minutes(1));
+        assert_eq!(worker.run_next().await?, Some((id, JobStatus::Succeeded)));
+        assert_eq!(handler.runs.load(Ordering::SeqCst), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_run_next_payload_invalid_dead() -> anyhow::Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let clock = ManualClock::new(datetime!(2030-01-01 0:00 UTC));
+        let worker =
+            Worker::new(mm.clone(), Arc::new(clock.clone())).handler(FlakyHandler::default());
+        let payload = BadPayload {
+            fail_times: "x".to_string(),
+        };
+        let id = JobBmc::enqueue_at(&Ctx::root_ctx(), &mm, &payload, Some(clock.now())).await?;
+
+        // -- Exec
+        let res = worker.run_next().await?;
+
+        // -- Check
+        assert_eq!(res, Some((id, JobStatus::Dead)));
+        let job = JobBmc::get(&Ctx::root_ctx(), &mm, id).await?;
+        assert!(job
+            .job_last_error
+            .is_some_and(|error| error.starts_with("PayloadInvalid")));
+
+        Ok(())
+    }
+}
+// endregion: --- Tests
@@ -0,0 +1,401 @@
+//! Webhook dispatch and delivery (see `model::webhook`).
+//!
+//! Design:
+//!
+//! - The `WebhookDispatcher` event handler (see `main`) queues a
+//!   `WebhookDeliver` job per subscribed webhook of the event project, in
+//!   the transaction of the change (an outbox, no event lost), so the
+//!   deliveries run on the workers, retried with backoff.
+//! - A delivery POSTs the `WebhookEvent` JSON body, with the headers:
+//!   - `X-Porta-Event`, the event type,
+//!   - `X-Porta-Delivery`, the event id (the same on the retries),
+//!   - `X-Porta-Timestamp`, the delivery unix time,
+//!   - `X-Porta-Signature`, the b64u HMAC-SHA512 of the body followed by
+//!     the timestamp, keyed with the webhook secret
+//!     (`crypt::encrypt_into_b64u`).
+//! - Any non 2xx response (or no response) fails the attempt, each attempt
+//!   being logged as a `WebhookDelivery`.
+//! - The URL is checked again on delivery, and the client connects to the
+//!   public addresses only (`PublicResolver`, so a host cannot resolve to
+//!   a private one after the check), without following redirects.
+//!
+
+use crate::config;
+use crate::crypt::{encrypt_into_b64u, EncryptContent};
+use crate::ctx::Ctx;
+use crate::model::event::{EventHandler, ModelEvent};
+use crate::model::job::JobBmc;
+use crate::model::webhook::{
+    check_webhook_url, resolve_public, Webhook, WebhookBmc, WebhookDelivery, WebhookDeliveryBmc,
+    WebhookDeliveryForInsert, WebhookEvent,
+};
+use crate::model::{ModelManager, Result};
+use crate::utils::now_utc;
+use crate::worker::handlers::WebhookDeliver;
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+pub const HEADER_EVENT: &str = "X-Porta-Event";
+pub const HEADER_DELIVERY: &str = "X-Porta-Delivery";
+pub const HEADER_TIMESTAMP: &str = "X-Porta-Timestamp";
+pub const HEADER_SIGNATURE: &str = "X-Porta-Signature";
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(DELIVERY_TIMEOUT)
+        .redirect(redirect::Policy::none())
+        .dns_resolver(Arc::new(PublicResolver))
+        .build()
+        .expect("webhook http client")
+});
+
+/// Resolves the webhook hosts to their public addresses only
+/// (see `resolve_public`).
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            // (the port is set by the client)
+            let addrs = resolve_public(name.as_str(), 0, config().WEBHOOK_ALLOW_LOCAL).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Queues the deliveries of the model events, in their transaction
+/// (see `ModelManager::with_event_handler`).
+pub struct WebhookDispatcher;
+
+#[async_trait]
+impl EventHandler for WebhookDispatcher {
+    async fn handle(&self, ctx: &Ctx, mm: &ModelManager, event: &ModelEvent) -> Result<()> {
+        if let Some(project_id) = WebhookBmc::event_project_id(ctx, mm, event).await? {
+            dispatch(ctx, mm, &WebhookEvent::new(event, project_id)).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Queue the event delivery to the subscribed webhooks of its project.
+/// Returns the job ids.
+pub async fn dispatch(ctx: &Ctx, mm: &ModelManager, event: &WebhookEvent) -> Result<Vec<i64>> {
+    let webhooks =
+        WebhookBmc::list_subscribed(ctx, mm, event.project_id, &event.event_type).await?;
+
+    let mut job_ids = Vec::with_capacity(webhooks.len());
+    for webhook in webhooks {
+        let payload = WebhookDeliver {
+            webhook_id: webhook.id,
+            event: event.clone(),
+        };
+        job_ids.push(JobBmc::enqueue(ctx, mm, &payload).await?);
+    }
+
+    Ok(job_ids)
+}
+
+/// The signature of the body, at the timestamp.
+pub fn sign(secret: &str, body: &str, timestamp: &str) -> Result<String> {
+    let signature = encrypt_into_b64u(
+        secret.as_bytes(),
+        &EncryptContent {
+            content: body.to_string(),
+            salt: timestamp.to_string(),
+        },
+    )?;
+
+    Ok(signature)
+}
+
+/// POST the event to the webhook, and log the attempt.
+/// (an unreachable webhook or a non 2xx response is not an error, but a
+/// failed delivery)
+pub async fn deliver(
+    ctx: &Ctx,
+    mm: &ModelManager,
+    webhook: &Webhook,
+    event: &WebhookEvent,
+) -> Result<WebhookDelivery> {
+    let attempt = WebhookDeliveryBmc::count_attempts(ctx, mm, webhook.id, &event.id).await? + 1;
+    let now = now_utc();
+    let timestamp = now.unix_timestamp().to_string();
+    let body = serde_json::to_string(event)?;
+    let signature = sign(&webhook.webhook_secret, &body, &timestamp)?;
+
+    debug!(
+        "{:<12} - deliver {} to webhook {} (attempt {attempt})",
+        "WORKER", event.id, webhook.id
+    );
+    let (status_code, error) =
+        match check_webhook_url(&webhook.webhook_url, config().WEBHOOK_ALLOW_LOCAL).await {
+            Ok(()) => {
+                let res = CLIENT
+                    .post(&webhook.webhook_url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .header(HEADER_EVENT, &event.event_type)
+                    .header(HEADER_DELIVERY, &event.id)
+                    .header(HEADER_TIMESTAMP, &timestamp)
+                    .header(HEADER_SIGNATURE, signature)
+                    .body(body)
+                    .send()
+                    .await;
+
+                match res {
+                    Ok(res) if res.status().is_success() => {
+                        (Some(res.status().as_u16() as i32), None)
+                    }
+                    Ok(res) => (
+                        Some(res.status().as_u16() as i32),
+                        Some(format!("HTTP {}", res.status())),
+                    ),
+                    Err(ex) => (None, Some(ex.to_string())),
+                }
+            }
+            Err(ex) => (None, Some(format!("{ex:?}"))),
+        };
+
+    let delivery_i = WebhookDeliveryForInsert {
+        delivery_webhook_id: webhook.id,
+        delivery_event_id: event.id.clone(),
+        delivery_event_type: event.event_type.clone(),
+        delivery_attempt: attempt,
+        delivery_status_code: status_code,
+        delivery_ok: error.is_none(),
+        delivery_error: error,
+        delivery_time: now,
+    };
+    let id = WebhookDeliveryBmc::record(ctx, mm, delivery_i).await?;
+
+    WebhookDeliveryBmc::get(ctx, mm, id).await
+}
+
+// region:    --- Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::_dev_utils;
+    use crate::_dev_utils::seed::Seed;
+    use crate::model::bounty::{BountyBmc, BountyForCreate};
+    use crate::model::job::{JobFilter, JobPayload, JobStatus};
+    use crate::model::webhook::WebhookForCreate;
+    use crate::scheduler::ManualClock;
+    use crate::worker::handlers::WebhookDeliverHandler;
+    use crate::worker::Worker;
+    use anyhow::{Context, Result};
+    use axum::extract::State;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+
+    const SEED_TOML: &str = r#"
+[[projects]]
+key = "p1"
+project_id = "webhook-project-01"
+project_name = "Webhook project 01"
+project_lead_address = "cosmos1udzkhs056fc0f2texd6cv30acg0rjept43xsph"
+project_budget = "1000"
+project_denom = "uatom"
+project_type = "Open Source"
+project_description = "With webhooks"
+"#;
+
+    const FX_SECRET: &str = "webhook secret 01";
+
+    /// The requests received by the stand-in, as (headers, body).
+    type Received = Arc<Mutex<Vec<(HeaderMap, String)>>>;
+
+    /// A local HTTP stand-in of a webhook receiver, answering `status`.
+    async fn serve_stand_in(status: StatusCode) -> (String, Received) {
+        let received = Received::default();
+        let app =
+            Router::new()
+                .route(
+                    "/hook",
+                    post(
+                        move |State(received): State<Received>,
+                              headers: HeaderMap,
+                              body: String| async move {
+                            received.lock().unwrap().push((headers, body));
+                            status
+                        },
+                    ),
+                )
+                .with_state(received.clone());
+        let server = axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+            .serve(app.into_make_service());
+        let url = format!("http://{}/hook", server.local_addr());
+        tokio::spawn(server);
+
+        (url, received)
+    }
+
+    async fn webhook_create(
+        ctx: &Ctx,
+        mm: &ModelManager,
+        project_id: i64,
+        url: &str,
+    ) -> Result<Webhook> {
+        let webhook_c = WebhookForCreate {
+            webhook_project_id: project_id,
+            webhook_url: url.to_string(),
+            webhook_events: vec!["bounty.*".to_string()],
+            webhook_secret: FX_SECRET.to_string(),
+        };
+        let id = WebhookBmc::create(ctx, mm, webhook_c).await?;
+
+        Ok(WebhookBmc::get(ctx, mm, id).await?)
+    }
+
+    #[tokio::test]
+    async fn test_deliver_signed_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let (url, received) = serve_stand_in(StatusCode::NO_CONTENT).await;
+        let webhook = webhook_create(&ctx, &mm, project_id, &url).await?;
+        let event = WebhookEvent::sample(project_id);
+
+        // -- Exec
+        let delivery = deliver(&ctx, &mm, &webhook, &event).await?;
+
+        // -- Check
+        assert!(delivery.delivery_ok);
+        assert_eq!(delivery.delivery_status_code, Some(204));
+        assert_eq!(delivery.delivery_attempt, 1);
+
+        let received = received.lock().unwrap();
+        let (headers, body) = received.first().context("no request received")?;
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        assert_eq!(header(HEADER_EVENT), Some("webhook.test"));
+        assert_eq!(header(HEADER_DELIVERY), Some(event.id.as_str()));
+        let timestamp = header(HEADER_TIMESTAMP).context("no timestamp")?;
+        let expected = sign(FX_SECRET, body, timestamp)?;
+        assert_eq!(header(HEADER_SIGNATURE), Some(expected.as_str()));
+        let body: WebhookEvent = serde_json::from_str(body)?;
+        assert_eq!(body.project_id, project_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_retry_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test().await;
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        let (url, received) = serve_stand_in(StatusCode::SERVICE_UNAVAILABLE).await;
+        let webhook = webhook_create(&ctx, &mm, project_id, &url).await?;
+        // (ahead of the job `run_at`, the db time)
+        let clock = ManualClock::new(now_utc() + time::Duration::seconds(5));
+        let worker =
+            Worker::new(mm.clone(), Arc::new(clock.clone())).handler(WebhookDeliverHandler);
+        let event = WebhookEvent {
+            event_type: "bounty.transitioned".to_string(),
+            ..WebhookEvent::sample(project_id)
+        };
+
+        // -- Exec
+        let job_ids = dispatch(&ctx, &mm, &event).await?;
+        let project_event = WebhookEvent {
+            event_type: "project.updated".to_string(),
+            ..WebhookEvent::sample(project_id)
+        };
+        let unsubscribed = dispatch(&ctx, &mm, &project_event).await?;
+
+        // -- Check
+        assert_eq!(job_ids.len(), 1);
+        assert!(unsubscribed.is_empty());
+        assert_eq!(
+            worker.run_next().await?,
+            Some((job_ids[0], JobStatus::Queued))
+        );
+        clock.advance(time::Duration::minutes(1));
+        assert_eq!(
+            worker.run_next().await?,
+            Some((job_ids[0], JobStatus::Queued))
+        );
+
+        let deliveries =
+            WebhookDeliveryBmc::list_for_webhook(&ctx, &mm, webhook.id, None, None).await?;
+        let attempts: Vec<_> = deliveries
+            .iter()
+            .map(|d| (d.delivery_attempt, d.delivery_status_code, d.delivery_ok))
+            .collect();
+        assert_eq!(attempts, [(1, Some(503), false), (2, Some(503), false)]);
+        assert!(deliveries.iter().all(|d| d.delivery_event_id == event.id));
+        assert_eq!(received.lock().unwrap().len(), 2);
+        assert_eq!(
+            WebhookDeliver::KIND,
+            JobBmc::get(&ctx, &mm, job_ids[0]).await?.job_kind
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_outbox_ok() -> Result<()> {
+        // -- Setup & Fixtures
+        let mm = _dev_utils::init_test()
+            .await
+            .with_event_handler(WebhookDispatcher);
+        let ctx = Ctx::root_ctx();
+        let seeded = toml::from_str::<Seed>(SEED_TOML)?.apply(&ctx, &mm).await?;
+        let project_id = *seeded.projects.get("p1").context("p1")?;
+        webhook_create(&ctx, &mm, project_id, "http://127.0.0.1:9/hook").await?;
+        let bounty_c = |title: &str| BountyForCreate {
+            bounty_title: title.to_string(),
+            bounty_assignee: "".to_string(),
+            bounty_reward: "10".to_string(),
+            bounty_deliverables: "A PR".to_string(),
+            bounty_description: "Dispatched".to_string(),
+            bounty_project_id: Some(project_id),
+            bounty_deadline: None,
+        };
+        let jobs = || async {
+            let filter = JobFilter {
+                job_kind: Some(WebhookDeliver::KIND.to_string()),
+                ..Default::default()
+            };
+            JobBmc::list(&ctx, &mm, Some(filter), None).await
+        };
+
+        // -- Exec
+        let res: Result<i64> = mm
+            .transaction(|mm| async move {
+                BountyBmc::create(&Ctx::root_ctx(), &mm, bounty_c("rolled back")).await?;
+                Err(anyhow::anyhow!("fail after create"))
+            })
+            .await;
+        assert!(res.is_err());
+        let rolled_back = jobs().await?;
+        let bounty_id = BountyBmc::create(&ctx, &mm, bounty_c("committed")).await?;
+
+        // -- Check
+        assert!(
+            rolled_back.is_empty(),
+            "no delivery of a rolled back change"
+        );
+        let jobs = jobs().await?;
+        assert_eq!(jobs.len(), 1);
+        let payload: WebhookDeliver = serde_json::from_value(jobs[0].job_payload.0.clone())?;
+        assert_eq!(payload.event.event_type, "bounty.created");
+        assert_eq!(payload.event.entity_id, bounty_id);
+
+        Ok(())
+    }
+}
+// endregion: --- Tests